@@ -0,0 +1,221 @@
+//! `bytemuck` integration for heapless types
+
+use core::{mem::size_of, ptr};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    storage::Storage,
+    vec::{Vec, VecInner},
+};
+
+// NOTE(unsafe) an all-zeroes `Vec` is an empty `Vec`
+unsafe impl<T, const N: usize> Zeroable for Vec<T, N> {}
+
+impl<T: Pod, const N: usize> Vec<T, N> {
+    /// Constructs a new vector by copying the elements from their byte representation.
+    ///
+    /// `bytes` doesn't need to be aligned for `T`.
+    ///
+    /// Returns an error if the length of `bytes` is not a multiple of the size of `T`, or if
+    /// the vector cannot hold all the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<u16, 4> = Vec::try_from_bytes(&[1, 0, 2, 0]).unwrap();
+    /// # #[cfg(target_endian = "little")]
+    /// assert_eq!(v, [1, 2]);
+    ///
+    /// assert!(Vec::<u16, 4>::try_from_bytes(&[1, 0, 2]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, ()> {
+        let mut v = Self::new();
+        v.extend_from_bytes(bytes)?;
+        Ok(v)
+    }
+}
+
+impl<T: Zeroable, const N: usize> Vec<T, N> {
+    /// Constructs a new vector with `len` zeroed elements.
+    ///
+    /// Returns an error if `len` is greater than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<u32, 4> = Vec::zeroed(3).unwrap();
+    /// assert_eq!(v, [0, 0, 0]);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn zeroed(len: usize) -> Result<Self, ()> {
+        let mut v = Self::new();
+        v.resize_zeroed(len)?;
+        Ok(v)
+    }
+}
+
+impl<T: Zeroable, S: Storage> VecInner<T, S> {
+    /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the `Vec` is extended by the
+    /// difference, with each additional slot zeroed.
+    /// If `new_len` is less than `len`, the `Vec` is simply truncated.
+    ///
+    /// Returns an error, leaving the vector unmodified, if `new_len` is greater than the capacity.
+    #[allow(clippy::result_unit_err)]
+    pub fn resize_zeroed(&mut self, new_len: usize) -> Result<(), ()> {
+        if new_len > self.storage_capacity() {
+            return Err(());
+        }
+
+        let len = self.len();
+        if new_len > len {
+            // NOTE(unsafe) an all-zeroes `T` is valid and `new_len` is within the capacity
+            unsafe {
+                ptr::write_bytes(self.as_mut_ptr().add(len), 0, new_len - len);
+                self.set_len(new_len);
+            }
+        } else {
+            self.truncate(new_len);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Pod, S: Storage> VecInner<T, S> {
+    /// Returns the byte representation of the elements of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<u16, 4> = Vec::from_slice(&[0x0102, 0x0304]).unwrap();
+    /// assert_eq!(v.as_bytes().len(), 4);
+    /// # #[cfg(target_endian = "little")]
+    /// assert_eq!(v.as_bytes(), &[2, 1, 4, 3]);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.as_slice())
+    }
+
+    /// Returns the mutable byte representation of the elements of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u16, 4> = Vec::from_slice(&[0, 0]).unwrap();
+    /// v.as_bytes_mut().fill(0xff);
+    /// assert_eq!(v, [0xffff, 0xffff]);
+    /// ```
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(self.as_mut_slice())
+    }
+
+    /// Appends elements by copying them from their byte representation.
+    ///
+    /// `bytes` doesn't need to be aligned for `T`.
+    ///
+    /// Returns an error, leaving the vector unmodified, if the length of `bytes` is not a
+    /// multiple of the size of `T`, or if the vector cannot hold all the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u16, 4> = Vec::new();
+    /// v.extend_from_bytes(&0x0102u16.to_ne_bytes()).unwrap();
+    /// assert_eq!(v, [0x0102]);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        let size = size_of::<T>();
+        if size == 0 {
+            return if bytes.is_empty() { Ok(()) } else { Err(()) };
+        }
+        if !bytes.len().is_multiple_of(size) {
+            return Err(());
+        }
+
+        let count = bytes.len() / size;
+        let len = self.len();
+        if count > self.storage_capacity() - len {
+            return Err(());
+        }
+
+        // NOTE(unsafe) any bit pattern is a valid `T`, and there's room for `count` more elements
+        unsafe {
+            ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.as_mut_ptr().add(len) as *mut u8,
+                bytes.len(),
+            );
+            self.set_len(len + count);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{Pod, Zeroable};
+
+    use crate::Vec;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        id: u16,
+        value: u16,
+    }
+
+    unsafe impl Zeroable for Record {}
+    unsafe impl Pod for Record {}
+
+    #[test]
+    fn bytes_roundtrip() {
+        let mut v: Vec<Record, 4> = Vec::new();
+        v.push(Record { id: 1, value: 2 }).unwrap();
+        v.push(Record { id: 3, value: 4 }).unwrap();
+        assert_eq!(v.as_bytes().len(), 8);
+
+        let w: Vec<Record, 2> = Vec::try_from_bytes(v.as_bytes()).unwrap();
+        assert_eq!(v, w);
+
+        // Unaligned input
+        let mut buf = [0u8; 9];
+        buf[1..].copy_from_slice(v.as_bytes());
+        let w: Vec<Record, 2> = Vec::try_from_bytes(&buf[1..]).unwrap();
+        assert_eq!(v, w);
+
+        // Too many elements
+        assert!(Vec::<Record, 1>::try_from_bytes(v.as_bytes()).is_err());
+        // Not a multiple of the element size
+        assert!(Vec::<Record, 4>::try_from_bytes(&buf[..7]).is_err());
+    }
+
+    #[test]
+    fn zeroed() {
+        let mut v: Vec<Record, 4> = Vec::zeroed(2).unwrap();
+        assert_eq!(v, [Record { id: 0, value: 0 }; 2]);
+        assert!(Vec::<Record, 4>::zeroed(5).is_err());
+
+        v.as_bytes_mut()[0] = 1;
+        v.resize_zeroed(3).unwrap();
+        assert_eq!(v[0].id, u16::from_ne_bytes([1, 0]));
+        assert_eq!(v[2], Record { id: 0, value: 0 });
+
+        let empty: Vec<Record, 4> = Zeroable::zeroed();
+        assert!(empty.is_empty());
+    }
+}
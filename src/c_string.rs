@@ -0,0 +1,371 @@
+//! A fixed capacity, nul-terminated C string.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ffi::{c_char, CStr},
+    fmt, hash,
+    ops::Deref,
+};
+
+use crate::Vec;
+
+/// A fixed capacity [`CString`](https://doc.rust-lang.org/std/ffi/struct.CString.html).
+///
+/// The string always ends with a nul byte and never contains interior nul bytes, so it can be
+/// passed to C code as a `*const c_char` with [`as_ptr`](Self::as_ptr), or borrowed as a
+/// [`CStr`]. The capacity `N` includes the nul terminator.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::CString;
+///
+/// let mut name: CString<16> = CString::new();
+/// name.extend_from_bytes(b"ble-").unwrap();
+/// name.extend_from_bytes(b"sensor").unwrap();
+///
+/// assert_eq!(name.as_c_str(), c"ble-sensor");
+/// assert_eq!(name.as_bytes_with_nul(), b"ble-sensor\0");
+/// ```
+#[derive(Clone)]
+pub struct CString<const N: usize> {
+    inner: Vec<u8, N>,
+}
+
+/// The error type for the operations that add bytes to a [`CString`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtendError {
+    /// The capacity of the `CString` is too small for the given operation.
+    Capacity,
+    /// The bytes contain a nul byte at `position`, which is not their last byte.
+    InteriorNul {
+        /// The position of the nul byte in the bytes that were added.
+        position: usize,
+    },
+}
+
+impl fmt::Display for ExtendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capacity => "insufficient capacity".fmt(f),
+            Self::InteriorNul { position } => {
+                write!(f, "interior nul byte found at position {}", position)
+            }
+        }
+    }
+}
+
+impl<const N: usize> CString<N> {
+    /// Constructs a new, empty `CString`, holding only the nul terminator.
+    ///
+    /// `N` must be at least 1 to hold the nul terminator, otherwise a compile-time error is
+    /// produced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::CString;
+    ///
+    /// let empty: CString<8> = CString::new();
+    /// assert!(empty.is_empty());
+    /// assert_eq!(empty.as_bytes_with_nul(), b"\0");
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            inner: Vec::from_array([0]),
+        }
+    }
+
+    /// Constructs a `CString` by copying a [`CStr`].
+    ///
+    /// Returns `Err(())` if the `CString` cannot hold `c_str` and its nul terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::CString;
+    ///
+    /// let s: CString<4> = CString::from_c_str(c"abc").unwrap();
+    /// assert_eq!(s.as_bytes(), b"abc");
+    ///
+    /// assert!(CString::<3>::from_c_str(c"abc").is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn from_c_str(c_str: &CStr) -> Result<Self, ()> {
+        Ok(Self {
+            inner: Vec::from_slice(c_str.to_bytes_with_nul())?,
+        })
+    }
+
+    /// Constructs a `CString` from bytes that must not contain a nul byte, except possibly as
+    /// their last byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{c_string::ExtendError, CString};
+    ///
+    /// let s: CString<8> = CString::from_bytes(b"abc\0").unwrap();
+    /// assert_eq!(s.as_bytes(), b"abc");
+    ///
+    /// assert_eq!(
+    ///     CString::<8>::from_bytes(b"a\0c"),
+    ///     Err(ExtendError::InteriorNul { position: 1 })
+    /// );
+    /// assert_eq!(CString::<3>::from_bytes(b"abc"), Err(ExtendError::Capacity));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ExtendError> {
+        let mut s = Self::new();
+        s.extend_from_bytes(bytes)?;
+        Ok(s)
+    }
+
+    /// Appends bytes to the string, keeping the nul terminator at the end.
+    ///
+    /// `bytes` must not contain a nul byte, except possibly as their last byte, which is then
+    /// ignored. The string is left unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{c_string::ExtendError, CString};
+    ///
+    /// let mut s: CString<8> = CString::new();
+    /// s.extend_from_bytes(b"foo").unwrap();
+    /// s.extend_from_bytes(b"bar\0").unwrap();
+    /// assert_eq!(s.as_bytes(), b"foobar");
+    ///
+    /// assert_eq!(s.extend_from_bytes(b"xy"), Err(ExtendError::Capacity));
+    /// assert_eq!(
+    ///     s.extend_from_bytes(b"\0\0"),
+    ///     Err(ExtendError::InteriorNul { position: 0 })
+    /// );
+    /// assert_eq!(s.as_bytes(), b"foobar");
+    /// ```
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) -> Result<(), ExtendError> {
+        let bytes = match bytes.iter().position(|&b| b == 0) {
+            Some(position) if position + 1 == bytes.len() => &bytes[..position],
+            Some(position) => return Err(ExtendError::InteriorNul { position }),
+            None => bytes,
+        };
+
+        if bytes.len() > self.inner.capacity() - self.inner.len() {
+            return Err(ExtendError::Capacity);
+        }
+
+        // NOTE(unwrap) there's room for `bytes` and a new nul terminator once the current one is
+        // removed
+        self.inner.pop();
+        self.inner.extend_from_copy_slice(bytes).unwrap();
+        self.inner.push(0).unwrap();
+        Ok(())
+    }
+
+    /// Appends a non-nul byte to the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{c_string::ExtendError, CString};
+    ///
+    /// let mut s: CString<3> = CString::new();
+    /// s.push(b'a').unwrap();
+    /// assert_eq!(s.push(0), Err(ExtendError::InteriorNul { position: 0 }));
+    /// s.push(b'b').unwrap();
+    /// assert_eq!(s.push(b'c'), Err(ExtendError::Capacity));
+    /// assert_eq!(s.as_bytes(), b"ab");
+    /// ```
+    pub fn push(&mut self, byte: u8) -> Result<(), ExtendError> {
+        if byte == 0 {
+            return Err(ExtendError::InteriorNul { position: 0 });
+        }
+        self.extend_from_bytes(&[byte])
+    }
+
+    /// Returns the maximum number of bytes the string can hold, including the nul terminator.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of the string in bytes, excluding the nul terminator.
+    pub fn len(&self) -> usize {
+        self.inner.len() - 1
+    }
+
+    /// Returns `true` if the string holds only the nul terminator.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Truncates the string to an empty string.
+    pub fn clear(&mut self) {
+        self.inner.truncate(1);
+        self.inner[0] = 0;
+    }
+
+    /// Borrows the string as a [`CStr`].
+    pub fn as_c_str(&self) -> &CStr {
+        // NOTE(unsafe) the bytes end with the only nul byte
+        unsafe { CStr::from_bytes_with_nul_unchecked(&self.inner) }
+    }
+
+    /// Returns a pointer to the nul-terminated string, to be passed to C code.
+    ///
+    /// The pointer is valid as long as the `CString` is neither moved nor modified.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.inner.as_ptr().cast()
+    }
+
+    /// Returns the contents of the string, excluding the nul terminator.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner[..self.len()]
+    }
+
+    /// Returns the contents of the string, including the nul terminator.
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl<const N: usize> Default for CString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "copy")]
+impl<const N: usize> Copy for CString<N> {}
+
+impl<const N: usize> Deref for CString<N> {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> AsRef<CStr> for CString<N> {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> Borrow<CStr> for CString<N> {
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a CStr> for CString<N> {
+    type Error = ();
+
+    fn try_from(c_str: &'a CStr) -> Result<Self, Self::Error> {
+        Self::from_c_str(c_str)
+    }
+}
+
+impl<const N: usize> fmt::Debug for CString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_c_str().fmt(f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<CString<M>> for CString<N> {
+    fn eq(&self, other: &CString<M>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> PartialEq<CStr> for CString<N> {
+    fn eq(&self, other: &CStr) -> bool {
+        self.as_c_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&CStr> for CString<N> {
+    fn eq(&self, other: &&CStr) -> bool {
+        self.as_c_str() == *other
+    }
+}
+
+impl<const N: usize> Eq for CString<N> {}
+
+impl<const N: usize, const M: usize> PartialOrd<CString<M>> for CString<N> {
+    fn partial_cmp(&self, other: &CString<M>) -> Option<Ordering> {
+        self.as_bytes().partial_cmp(other.as_bytes())
+    }
+}
+
+impl<const N: usize> Ord for CString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<const N: usize> hash::Hash for CString<N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_c_str().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CString, ExtendError};
+
+    #[test]
+    fn empty() {
+        let s: CString<1> = CString::new();
+        assert!(s.is_empty());
+        assert_eq!(s.len(), 0);
+        assert_eq!(s.as_c_str(), c"");
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+        assert_eq!(CString::<1>::from_bytes(b"\0"), Ok(s));
+    }
+
+    #[test]
+    fn extend() {
+        let mut s: CString<6> = CString::new();
+        s.extend_from_bytes(b"").unwrap();
+        s.extend_from_bytes(b"ab\0").unwrap();
+        s.extend_from_bytes(b"cd").unwrap();
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.as_c_str(), c"abcd");
+
+        assert_eq!(s.extend_from_bytes(b"ef"), Err(ExtendError::Capacity));
+        assert_eq!(
+            s.extend_from_bytes(b"e\0\0"),
+            Err(ExtendError::InteriorNul { position: 1 })
+        );
+        assert_eq!(s.as_bytes_with_nul(), b"abcd\0");
+
+        s.push(b'e').unwrap();
+        assert_eq!(s.as_bytes_with_nul(), b"abcde\0");
+        assert!(s.push(b'f').is_err());
+    }
+
+    #[test]
+    fn clear() {
+        let mut s: CString<6> = CString::from_bytes(b"abc").unwrap();
+        s.clear();
+        assert!(s.is_empty());
+        assert_eq!(s.as_c_str(), c"");
+    }
+
+    #[test]
+    fn as_ptr() {
+        let s: CString<6> = CString::from_c_str(c"abc").unwrap();
+        let c_str = unsafe { core::ffi::CStr::from_ptr(s.as_ptr()) };
+        assert_eq!(c_str, c"abc");
+    }
+
+    #[test]
+    fn cmp() {
+        let a: CString<4> = CString::from_bytes(b"ab").unwrap();
+        let b: CString<8> = CString::from_bytes(b"abc").unwrap();
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert_eq!(a, c"ab");
+        assert_eq!(a, CString::<8>::try_from(c"ab").unwrap());
+    }
+}
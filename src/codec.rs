@@ -0,0 +1,355 @@
+//! Hex and Base64 encoding and decoding into heapless buffers.
+//!
+//! Encoders append to a [`String`](crate::String) or [`StringView`](crate::string::StringView),
+//! decoders append to a [`Vec`](crate::Vec) or [`VecView`](crate::VecView). On error, the output
+//! is left unchanged.
+//!
+//! This module is only available with the `codec` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use heapless::{codec, String, Vec};
+//!
+//! let mut hex: String<8> = String::new();
+//! codec::encode_hex(&[0xde, 0xad, 0xbe, 0xef], &mut hex).unwrap();
+//! assert_eq!(hex, "deadbeef");
+//!
+//! let mut bytes: Vec<u8, 4> = Vec::new();
+//! codec::decode_hex("DEADbeef", &mut bytes).unwrap();
+//! assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+//!
+//! let mut b64: String<8> = String::new();
+//! codec::encode_base64(b"hello", &mut b64).unwrap();
+//! assert_eq!(b64, "aGVsbG8=");
+//! ```
+
+use core::fmt;
+
+use crate::{storage::Storage, string::StringInner, vec::VecInner};
+
+/// The error type for the encoding and decoding functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The capacity of the output is too small for the given operation.
+    Capacity,
+    /// The input contains an invalid character at `position`.
+    InvalidCharacter {
+        /// The byte position of the invalid character in the input.
+        position: usize,
+    },
+    /// The length of the input is not valid for the encoding.
+    InvalidLength,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capacity => "insufficient capacity".fmt(f),
+            Self::InvalidCharacter { position } => {
+                write!(f, "invalid character at position {}", position)
+            }
+            Self::InvalidLength => "invalid input length".fmt(f),
+        }
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Runs `f` on `out`, truncating `out` back to its original length if `f` fails.
+fn atomically<S: Storage, F>(out: &mut VecInner<u8, S>, f: F) -> Result<(), Error>
+where
+    F: FnOnce(&mut VecInner<u8, S>) -> Result<(), Error>,
+{
+    let len = out.len();
+    let res = f(out);
+    if res.is_err() {
+        out.truncate(len);
+    }
+    res
+}
+
+/// Appends the lower case hexadecimal encoding of `input` to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{codec, String};
+///
+/// let mut s: String<6> = String::try_from("0x").unwrap();
+/// codec::encode_hex(&[0x01, 0xab], &mut s).unwrap();
+/// assert_eq!(s, "0x01ab");
+///
+/// assert_eq!(codec::encode_hex(&[0], &mut s), Err(codec::Error::Capacity));
+/// ```
+pub fn encode_hex<S: Storage>(input: &[u8], out: &mut StringInner<S>) -> Result<(), Error> {
+    // NOTE(unsafe) only ASCII characters are written
+    let out = unsafe { out.as_mut_vec() };
+    if input.len() > (out.storage_capacity() - out.len()) / 2 {
+        return Err(Error::Capacity);
+    }
+
+    for &b in input {
+        // NOTE(unsafe) there's room for two characters per input byte
+        unsafe {
+            out.push_unchecked(HEX_DIGITS[usize::from(b >> 4)]);
+            out.push_unchecked(HEX_DIGITS[usize::from(b & 0xf)]);
+        }
+    }
+    Ok(())
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes the hexadecimal `input`, in upper or lower case, and appends the bytes to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{codec, Vec};
+///
+/// let mut v: Vec<u8, 2> = Vec::new();
+/// codec::decode_hex("01aB", &mut v).unwrap();
+/// assert_eq!(v, [0x01, 0xab]);
+///
+/// v.clear();
+/// assert_eq!(codec::decode_hex("0", &mut v), Err(codec::Error::InvalidLength));
+/// assert_eq!(
+///     codec::decode_hex("0g", &mut v),
+///     Err(codec::Error::InvalidCharacter { position: 1 })
+/// );
+/// ```
+pub fn decode_hex<S: Storage>(input: &str, out: &mut VecInner<u8, S>) -> Result<(), Error> {
+    let input = input.as_bytes();
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::InvalidLength);
+    }
+
+    atomically(out, |out| {
+        for (i, pair) in input.chunks_exact(2).enumerate() {
+            let digit = |j: usize| {
+                hex_value(pair[j]).ok_or(Error::InvalidCharacter {
+                    position: 2 * i + j,
+                })
+            };
+            let b = (digit(0)? << 4) | digit(1)?;
+            out.push(b).map_err(|_| Error::Capacity)?;
+        }
+        Ok(())
+    })
+}
+
+/// Appends the standard, padded Base64 encoding of `input` to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{codec, String};
+///
+/// let mut s: String<8> = String::new();
+/// codec::encode_base64(b"heap", &mut s).unwrap();
+/// assert_eq!(s, "aGVhcA==");
+///
+/// assert_eq!(codec::encode_base64(b"x", &mut s), Err(codec::Error::Capacity));
+/// ```
+pub fn encode_base64<S: Storage>(input: &[u8], out: &mut StringInner<S>) -> Result<(), Error> {
+    // NOTE(unsafe) only ASCII characters are written
+    let out = unsafe { out.as_mut_vec() };
+    if input.len().div_ceil(3) > (out.storage_capacity() - out.len()) / 4 {
+        return Err(Error::Capacity);
+    }
+
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            let c = if i <= chunk.len() {
+                BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize]
+            } else {
+                b'='
+            };
+            // NOTE(unsafe) there's room for four characters per chunk
+            unsafe { out.push_unchecked(c) }
+        }
+    }
+    Ok(())
+}
+
+fn base64_value(c: u8) -> Option<u32> {
+    let v = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+    Some(u32::from(v))
+}
+
+/// Decodes the standard Base64 `input` and appends the bytes to `out`.
+///
+/// The padding at the end of `input` is optional, but if present, the length of `input` must be
+/// a multiple of 4.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{codec, Vec};
+///
+/// let mut v: Vec<u8, 8> = Vec::new();
+/// codec::decode_base64("aGVhcA==", &mut v).unwrap();
+/// codec::decode_base64("bGVzcw", &mut v).unwrap();
+/// assert_eq!(v, *b"heapless");
+///
+/// v.clear();
+/// assert_eq!(
+///     codec::decode_base64("aG!h", &mut v),
+///     Err(codec::Error::InvalidCharacter { position: 2 })
+/// );
+/// ```
+pub fn decode_base64<S: Storage>(input: &str, out: &mut VecInner<u8, S>) -> Result<(), Error> {
+    let input = input.as_bytes();
+    let data = match input {
+        [data @ .., b'=', b'='] | [data @ .., b'='] => {
+            if !input.len().is_multiple_of(4) {
+                return Err(Error::InvalidLength);
+            }
+            data
+        }
+        data => data,
+    };
+    if data.len() % 4 == 1 {
+        return Err(Error::InvalidLength);
+    }
+
+    atomically(out, |out| {
+        for (i, chunk) in data.chunks(4).enumerate() {
+            let mut n = 0;
+            for (j, &c) in chunk.iter().enumerate() {
+                let v = base64_value(c).ok_or(Error::InvalidCharacter {
+                    position: 4 * i + j,
+                })?;
+                n |= v << (18 - 6 * j);
+            }
+            for j in 0..chunk.len() - 1 {
+                out.push((n >> (16 - 8 * j)) as u8)
+                    .map_err(|_| Error::Capacity)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base64, decode_hex, encode_base64, encode_hex, Error};
+    use crate::{String, Vec};
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes: std::vec::Vec<u8> = (0..=255).collect();
+        let mut s: String<512> = String::new();
+        encode_hex(&bytes, &mut s).unwrap();
+        assert!(s.starts_with("000102"));
+        assert!(s.ends_with("fdfeff"));
+
+        let mut v: Vec<u8, 256> = Vec::new();
+        decode_hex(&s, &mut v).unwrap();
+        assert_eq!(v, *bytes);
+
+        let upper: String<512> = s.to_ascii_uppercase();
+        v.clear();
+        decode_hex(&upper, &mut v).unwrap();
+        assert_eq!(v, *bytes);
+    }
+
+    #[test]
+    fn hex_errors() {
+        let mut s: String<3> = String::new();
+        assert_eq!(encode_hex(&[1, 2], &mut s), Err(Error::Capacity));
+        assert_eq!(s, "");
+
+        let mut v: Vec<u8, 1> = Vec::new();
+        assert_eq!(decode_hex("0102", &mut v), Err(Error::Capacity));
+        assert_eq!(decode_hex("0ĝ", &mut v), Err(Error::InvalidLength));
+        assert_eq!(
+            decode_hex("x0", &mut v),
+            Err(Error::InvalidCharacter { position: 0 })
+        );
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn base64_vectors() {
+        // Test vectors from RFC 4648
+        for (plain, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            let mut s: String<8> = String::new();
+            encode_base64(plain.as_bytes(), &mut s).unwrap();
+            assert_eq!(s, encoded);
+
+            let mut v: Vec<u8, 6> = Vec::new();
+            decode_base64(encoded, &mut v).unwrap();
+            assert_eq!(v, plain.as_bytes());
+
+            v.clear();
+            decode_base64(encoded.trim_end_matches('='), &mut v).unwrap();
+            assert_eq!(v, plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn base64_all_bytes() {
+        let bytes: std::vec::Vec<u8> = (0..=255).collect();
+        let mut s: String<344> = String::new();
+        encode_base64(&bytes, &mut s).unwrap();
+        assert!(s.ends_with("/w=="));
+
+        let mut v: Vec<u8, 256> = Vec::new();
+        decode_base64(&s, &mut v).unwrap();
+        assert_eq!(v, *bytes);
+    }
+
+    #[test]
+    fn base64_errors() {
+        let mut s: String<7> = String::new();
+        assert_eq!(encode_base64(b"abcd", &mut s), Err(Error::Capacity));
+        assert_eq!(s, "");
+
+        let mut v: Vec<u8, 2> = Vec::new();
+        assert_eq!(decode_base64("Zm9v", &mut v), Err(Error::Capacity));
+        assert_eq!(decode_base64("Zm9vY", &mut v), Err(Error::InvalidLength));
+        assert_eq!(
+            decode_base64("Zm8=Zg", &mut v),
+            Err(Error::InvalidCharacter { position: 3 })
+        );
+        assert_eq!(decode_base64("Zg=", &mut v), Err(Error::InvalidLength));
+        assert_eq!(
+            decode_base64("Z===", &mut v),
+            Err(Error::InvalidCharacter { position: 1 })
+        );
+        assert!(v.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    binary_heap::Kind as BinaryHeapKind, BinaryHeap, Deque, HistoryBuffer, IndexMap, IndexSet,
-    LinearMap, String, Vec,
+    binary_heap::Kind as BinaryHeapKind, BinaryHeap, Deque, Entry, HistoryBuffer, IndexMap,
+    IndexPos, IndexSet, LinearMap, String, Vec,
 };
 use core::{
     fmt,
@@ -10,6 +10,68 @@ use core::{
 use hash32::BuildHasherDefault;
 use serde::de::{self, Deserialize, Deserializer, Error, MapAccess, SeqAccess};
 
+// Policies
+
+/// What to do when deserializing more distinct keys or values than fit in an [`IndexMap`] or
+/// [`IndexSet`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnOverflow {
+    /// Fail with an "invalid length" error
+    Error,
+    /// Ignore the keys or values that don't fit, keeping the first ones
+    Ignore,
+}
+
+/// What to do when deserializing a key or value that is already in an [`IndexMap`] or
+/// [`IndexSet`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnDuplicate {
+    /// Fail with a "duplicate" error
+    Error,
+    /// Keep the first key-value pair or value, ignoring the later ones
+    KeepFirst,
+    /// Keep the last value (or for maps, the last key's value), at the position of the first one
+    KeepLast,
+}
+
+/// How to deserialize untrusted input into an [`IndexMap`] or [`IndexSet`]
+///
+/// Used with [`IndexMap::deserialize_with_policy`] and [`IndexSet::deserialize_with_policy`].
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{DeserializePolicy, FnvIndexMap, OnDuplicate, OnOverflow};
+/// use serde::Deserializer;
+///
+/// // To be used on a field with `#[serde(deserialize_with = "lenient")]`
+/// fn lenient<'de, D>(deserializer: D) -> Result<FnvIndexMap<u8, u32, 4>, D::Error>
+/// where
+///     D: Deserializer<'de>,
+/// {
+///     let policy = DeserializePolicy {
+///         on_overflow: OnOverflow::Ignore,
+///         on_duplicate: OnDuplicate::KeepLast,
+///     };
+///     FnvIndexMap::deserialize_with_policy(deserializer, policy)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeserializePolicy {
+    /// What to do with the keys or values that don't fit
+    pub on_overflow: OnOverflow,
+    /// What to do with the duplicate keys or values
+    pub on_duplicate: OnDuplicate,
+}
+
+impl DeserializePolicy {
+    /// Reject any input that doesn't fit or contains duplicates
+    pub const STRICT: Self = Self {
+        on_overflow: OnOverflow::Error,
+        on_duplicate: OnDuplicate::Error,
+    };
+}
+
 // Sequential containers
 
 impl<'de, T, KIND, const N: usize> Deserialize<'de> for BinaryHeap<T, KIND, N>
@@ -54,7 +116,8 @@ where
     }
 }
 
-impl<'de, T, S, const N: usize> Deserialize<'de> for IndexSet<T, BuildHasherDefault<S>, N>
+impl<'de, T, S, const N: usize, P: IndexPos> Deserialize<'de>
+    for IndexSet<T, BuildHasherDefault<S>, N, P>
 where
     T: Eq + Hash + Deserialize<'de>,
     S: Hasher + Default,
@@ -63,14 +126,43 @@ where
     where
         D: Deserializer<'de>,
     {
-        struct ValueVisitor<'de, T, S, const N: usize>(PhantomData<(&'de (), T, S)>);
+        let policy = DeserializePolicy {
+            on_overflow: OnOverflow::Error,
+            on_duplicate: OnDuplicate::KeepFirst,
+        };
+        Self::deserialize_with_policy(deserializer, policy)
+    }
+}
+
+impl<T, S, const N: usize, P: IndexPos> IndexSet<T, BuildHasherDefault<S>, N, P>
+where
+    T: Eq + Hash,
+    S: Hasher + Default,
+{
+    /// Deserializes a set, handling the values that don't fit and the duplicate values according
+    /// to `policy`.
+    ///
+    /// The [`Deserialize`] implementation fails on values that don't fit, and keeps the first of
+    /// the duplicate values.
+    pub fn deserialize_with_policy<'de, D>(
+        deserializer: D,
+        policy: DeserializePolicy,
+    ) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, T, S, const N: usize, P>(
+            DeserializePolicy,
+            PhantomData<(&'de (), T, S, P)>,
+        );
 
-        impl<'de, T, S, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, S, N>
+        impl<'de, T, S, const N: usize, P: IndexPos> de::Visitor<'de> for ValueVisitor<'de, T, S, N, P>
         where
             T: Eq + Hash + Deserialize<'de>,
             S: Hasher + Default,
         {
-            type Value = IndexSet<T, BuildHasherDefault<S>, N>;
+            type Value = IndexSet<T, BuildHasherDefault<S>, N, P>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("a sequence")
@@ -83,7 +175,20 @@ where
                 let mut values = IndexSet::new();
 
                 while let Some(value) = seq.next_element()? {
-                    if values.insert(value).is_err() {
+                    if values.contains(&value) {
+                        match self.0.on_duplicate {
+                            OnDuplicate::Error => {
+                                return Err(A::Error::custom("duplicate value in set"))
+                            }
+                            OnDuplicate::KeepFirst => {}
+                            OnDuplicate::KeepLast => {
+                                // NOTE(unwrap) the value is in the set, so it is replaced
+                                values.replace(value).ok().unwrap();
+                            }
+                        }
+                    } else if values.insert(value).is_err()
+                        && self.0.on_overflow == OnOverflow::Error
+                    {
                         return Err(A::Error::invalid_length(values.capacity() + 1, &self))?;
                     }
                 }
@@ -91,7 +196,7 @@ where
                 Ok(values)
             }
         }
-        deserializer.deserialize_seq(ValueVisitor(PhantomData))
+        deserializer.deserialize_seq(ValueVisitor(policy, PhantomData))
     }
 }
 
@@ -112,13 +217,24 @@ where
             type Value = Vec<T, N>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("a sequence")
+                write!(
+                    formatter,
+                    "a sequence no more than {} elements long",
+                    N as u64
+                )
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
             where
                 A: SeqAccess<'de>,
             {
+                // Reject sequences known to be too long before deserializing any element
+                if let Some(len) = seq.size_hint() {
+                    if len > N {
+                        return Err(A::Error::invalid_length(len, &self));
+                    }
+                }
+
                 let mut values = Vec::new();
 
                 while let Some(value) = seq.next_element()? {
@@ -151,13 +267,24 @@ where
             type Value = Deque<T, N>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("a sequence")
+                write!(
+                    formatter,
+                    "a sequence no more than {} elements long",
+                    N as u64
+                )
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
             where
                 A: SeqAccess<'de>,
             {
+                // Reject sequences known to be too long before deserializing any element
+                if let Some(len) = seq.size_hint() {
+                    if len > N {
+                        return Err(A::Error::invalid_length(len, &self));
+                    }
+                }
+
                 let mut values = Deque::new();
 
                 while let Some(value) = seq.next_element()? {
@@ -199,6 +326,8 @@ where
             {
                 let mut values = HistoryBuffer::new();
 
+                // Samples are in chronological order, so writing them back restores the window.
+                // A longer sequence keeps only its most recent samples.
                 while let Some(value) = seq.next_element()? {
                     values.write(value);
                 }
@@ -212,7 +341,8 @@ where
 
 // Dictionaries
 
-impl<'de, K, V, S, const N: usize> Deserialize<'de> for IndexMap<K, V, BuildHasherDefault<S>, N>
+impl<'de, K, V, S, const N: usize, P: IndexPos> Deserialize<'de>
+    for IndexMap<K, V, BuildHasherDefault<S>, N, P>
 where
     K: Eq + Hash + Deserialize<'de>,
     V: Deserialize<'de>,
@@ -222,15 +352,66 @@ where
     where
         D: Deserializer<'de>,
     {
-        struct ValueVisitor<'de, K, V, S, const N: usize>(PhantomData<(&'de (), K, V, S)>);
+        let policy = DeserializePolicy {
+            on_overflow: OnOverflow::Error,
+            on_duplicate: OnDuplicate::KeepLast,
+        };
+        Self::deserialize_with_policy(deserializer, policy)
+    }
+}
 
-        impl<'de, K, V, S, const N: usize> de::Visitor<'de> for ValueVisitor<'de, K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> IndexMap<K, V, BuildHasherDefault<S>, N, P>
+where
+    K: Eq + Hash,
+    S: Default + Hasher,
+{
+    /// Deserializes a map, handling the entries that don't fit and the duplicate keys according
+    /// to `policy`.
+    ///
+    /// The [`Deserialize`] implementation fails on entries that don't fit, and keeps the last
+    /// value of the duplicate keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{DeserializePolicy, FnvIndexMap, OnDuplicate, OnOverflow};
+    /// use serde::de::value::{Error, MapDeserializer};
+    ///
+    /// let input = [(1u8, 10u32), (2, 20), (1, 11), (3, 30)];
+    ///
+    /// let de = MapDeserializer::<_, Error>::new(input.into_iter());
+    /// assert!(FnvIndexMap::<u8, u32, 2>::deserialize_with_policy(de, DeserializePolicy::STRICT).is_err());
+    ///
+    /// let policy = DeserializePolicy {
+    ///     on_overflow: OnOverflow::Ignore,
+    ///     on_duplicate: OnDuplicate::KeepFirst,
+    /// };
+    /// let de = MapDeserializer::<_, Error>::new(input.into_iter());
+    /// let map = FnvIndexMap::<u8, u32, 2>::deserialize_with_policy(de, policy).unwrap();
+    /// assert!(map.iter().eq([(&1, &10), (&2, &20)]));
+    /// ```
+    pub fn deserialize_with_policy<'de, D>(
+        deserializer: D,
+        policy: DeserializePolicy,
+    ) -> Result<Self, D::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, K, V, S, const N: usize, P>(
+            DeserializePolicy,
+            PhantomData<(&'de (), K, V, S, P)>,
+        );
+
+        impl<'de, K, V, S, const N: usize, P: IndexPos> de::Visitor<'de>
+            for ValueVisitor<'de, K, V, S, N, P>
         where
             K: Eq + Hash + Deserialize<'de>,
             V: Deserialize<'de>,
             S: Default + Hasher,
         {
-            type Value = IndexMap<K, V, BuildHasherDefault<S>, N>;
+            type Value = IndexMap<K, V, BuildHasherDefault<S>, N, P>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("a map")
@@ -243,15 +424,30 @@ where
                 let mut values = IndexMap::new();
 
                 while let Some((key, value)) = map.next_entry()? {
-                    if values.insert(key, value).is_err() {
-                        return Err(A::Error::invalid_length(values.capacity() + 1, &self))?;
+                    match values.entry(key) {
+                        Entry::Occupied(entry) => match self.0.on_duplicate {
+                            OnDuplicate::Error => {
+                                return Err(A::Error::custom("duplicate key in map"))
+                            }
+                            OnDuplicate::KeepFirst => {}
+                            OnDuplicate::KeepLast => {
+                                entry.insert(value);
+                            }
+                        },
+                        Entry::Vacant(entry) => {
+                            if entry.insert(value).is_err()
+                                && self.0.on_overflow == OnOverflow::Error
+                            {
+                                return Err(A::Error::invalid_length(N + 1, &self))?;
+                            }
+                        }
                     }
                 }
 
                 Ok(values)
             }
         }
-        deserializer.deserialize_map(ValueVisitor(PhantomData))
+        deserializer.deserialize_map(ValueVisitor(policy, PhantomData))
     }
 }
 
@@ -313,12 +509,26 @@ impl<'de, const N: usize> Deserialize<'de> for String<N> {
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                String::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_str(v)
+            }
+
+            fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
                 let mut s = String::new();
-                s.push_str(v)
-                    .map_err(|_| E::invalid_length(v.len(), &self))?;
+                s.push(v)
+                    .map_err(|_| E::invalid_length(v.len_utf8(), &self))?;
                 Ok(s)
             }
 
@@ -326,18 +536,283 @@ impl<'de, const N: usize> Deserialize<'de> for String<N> {
             where
                 E: de::Error,
             {
-                let mut s = String::new();
+                // Check the length first, so that it is reported rather than an invalid UTF-8
+                // sequence split at the capacity
+                if v.len() > N {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                let v = core::str::from_utf8(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?;
+                self.visit_str(v)
+            }
 
-                s.push_str(
-                    core::str::from_utf8(v)
-                        .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?,
-                )
-                .map_err(|_| E::invalid_length(v.len(), &self))?;
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_bytes(v)
+            }
 
-                Ok(s)
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                // Formats without a native byte string type may encode bytes as a sequence
+                if let Some(len) = seq.size_hint() {
+                    if len > N {
+                        return Err(A::Error::invalid_length(len, &self));
+                    }
+                }
+
+                let mut bytes = Vec::<u8, N>::new();
+                while let Some(byte) = seq.next_element()? {
+                    if bytes.push(byte).is_err() {
+                        return Err(A::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                String::from_utf8(bytes).map_err(|e| {
+                    A::Error::custom(format_args!("invalid UTF-8 in byte sequence: {}", e))
+                })
             }
         }
 
         deserializer.deserialize_str(ValueVisitor::<'de, N>(PhantomData))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::fmt;
+    use std::string::ToString;
+
+    use serde::de::{
+        value::{BorrowedBytesDeserializer, BorrowedStrDeserializer, Error, SeqDeserializer},
+        Deserialize,
+    };
+
+    use crate::Vec;
+
+    #[test]
+    fn vec_borrowed_str() {
+        let input = [String::from("foo"), String::from("bar")];
+        let de = SeqDeserializer::<_, Error>::new(
+            input
+                .iter()
+                .map(|s| BorrowedStrDeserializer::<Error>::new(s)),
+        );
+        let v = Vec::<&str, 4>::deserialize(de).unwrap();
+        assert_eq!(v, ["foo", "bar"]);
+        // Zero-copy: the strings point into the input
+        assert_eq!(v[0].as_ptr(), input[0].as_ptr());
+    }
+
+    #[test]
+    fn vec_borrowed_bytes() {
+        let input: [&[u8]; 2] = [b"foo", b"ba"];
+        let de = SeqDeserializer::<_, Error>::new(
+            input
+                .iter()
+                .map(|b| BorrowedBytesDeserializer::<Error>::new(b)),
+        );
+        let v = Vec::<&[u8], 4>::deserialize(de).unwrap();
+        assert_eq!(v, input);
+        assert_eq!(v[1].as_ptr(), input[1].as_ptr());
+    }
+
+    #[derive(Debug)]
+    struct TestError(std::string::String);
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for TestError {}
+
+    impl serde::de::Error for TestError {
+        fn custom<M: fmt::Display>(msg: M) -> Self {
+            Self(msg.to_string())
+        }
+    }
+
+    #[test]
+    fn string_borrowed() {
+        let s = crate::String::<4>::deserialize(BorrowedStrDeserializer::<TestError>::new("aĝc"))
+            .unwrap();
+        assert_eq!(s, "aĝc");
+
+        let de = BorrowedBytesDeserializer::<TestError>::new("aĝc".as_bytes());
+        let s = crate::String::<4>::deserialize(de).unwrap();
+        assert_eq!(s, "aĝc");
+    }
+
+    #[test]
+    fn string_errors() {
+        let de = BorrowedStrDeserializer::<TestError>::new("abcde");
+        let err = crate::String::<4>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 5, expected a string no more than 4 bytes long"
+        );
+
+        // The length is reported, even if the bytes are cut inside a character
+        let de = BorrowedBytesDeserializer::<TestError>::new("abcĝ".as_bytes());
+        let err = crate::String::<4>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 5, expected a string no more than 4 bytes long"
+        );
+
+        let de = BorrowedBytesDeserializer::<TestError>::new(b"a\xffc");
+        let err = crate::String::<4>::deserialize(de).unwrap_err();
+        assert!(err.0.starts_with("invalid value: byte array"));
+    }
+
+    #[test]
+    fn string_char() {
+        let de = serde::de::value::CharDeserializer::<TestError>::new('ĝ');
+        assert_eq!(crate::String::<2>::deserialize(de).unwrap(), "ĝ");
+        let de = serde::de::value::CharDeserializer::<TestError>::new('ĝ');
+        assert!(crate::String::<1>::deserialize(de).is_err());
+    }
+
+    #[test]
+    fn string_seq() {
+        let de = SeqDeserializer::<_, TestError>::new("aĝ".bytes());
+        assert_eq!(crate::String::<4>::deserialize(de).unwrap(), "aĝ");
+
+        let de = SeqDeserializer::<_, TestError>::new("abcde".bytes());
+        let err = crate::String::<4>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 5, expected a string no more than 4 bytes long"
+        );
+
+        let de = SeqDeserializer::<_, TestError>::new("abcde".bytes().filter(|_| true));
+        assert!(crate::String::<4>::deserialize(de).is_err());
+
+        let de = SeqDeserializer::<_, TestError>::new([b'a', 0xff].into_iter());
+        let err = crate::String::<4>::deserialize(de).unwrap_err();
+        assert!(err.0.starts_with("invalid UTF-8"));
+    }
+
+    #[test]
+    fn deque() {
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter());
+        let deque = crate::Deque::<u8, 3>::deserialize(de).unwrap();
+        assert!(deque.iter().eq(&[1, 2, 3]));
+
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter());
+        let err = crate::Deque::<u8, 2>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 3, expected a sequence no more than 2 elements long"
+        );
+
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter().filter(|_| true));
+        let err = crate::Deque::<u8, 2>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 3, expected a sequence no more than 2 elements long"
+        );
+    }
+
+    #[test]
+    fn history_buffer() {
+        let mut buffer = crate::HistoryBuffer::<u8, 4>::new();
+        buffer.extend([1, 2, 3, 4, 5, 6]);
+
+        // Serialization yields the samples in chronological order
+        let de = SeqDeserializer::<_, TestError>::new(buffer.oldest_ordered().copied());
+        let restored = crate::HistoryBuffer::<u8, 4>::deserialize(de).unwrap();
+        assert_eq!(restored, buffer);
+        assert!(restored.oldest_ordered().eq(&[3, 4, 5, 6]));
+        assert_eq!(restored.recent(), Some(&6));
+
+        // A longer sequence keeps the most recent samples
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter());
+        let restored = crate::HistoryBuffer::<u8, 2>::deserialize(de).unwrap();
+        assert!(restored.oldest_ordered().eq(&[2, 3]));
+
+        let de = SeqDeserializer::<_, TestError>::new(core::iter::empty::<u8>());
+        let restored = crate::HistoryBuffer::<u8, 2>::deserialize(de).unwrap();
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn index_map_policy() {
+        use super::{DeserializePolicy, OnDuplicate, OnOverflow};
+        use crate::FnvIndexMap;
+        use serde::de::value::MapDeserializer;
+
+        type Map = FnvIndexMap<u8, u8, 2>;
+        let input = [(1u8, 1u8), (2, 2), (1, 3), (3, 4), (2, 5)];
+        let de = || MapDeserializer::<_, TestError>::new(input.into_iter());
+
+        // By default, the last duplicate wins and overflowing is an error
+        let err = Map::deserialize(de()).unwrap_err();
+        assert_eq!(err.0, "invalid length 3, expected a map");
+        let map = Map::deserialize(MapDeserializer::<_, TestError>::new(
+            input.into_iter().filter(|(k, _)| *k != 3),
+        ))
+        .unwrap();
+        assert!(map.iter().eq([(&1, &3), (&2, &5)]));
+
+        let err = Map::deserialize_with_policy(de(), DeserializePolicy::STRICT).unwrap_err();
+        assert_eq!(err.0, "duplicate key in map");
+
+        let policy = |on_duplicate| DeserializePolicy {
+            on_overflow: OnOverflow::Ignore,
+            on_duplicate,
+        };
+        let map = Map::deserialize_with_policy(de(), policy(OnDuplicate::KeepFirst)).unwrap();
+        assert!(map.iter().eq([(&1, &1), (&2, &2)]));
+        let map = Map::deserialize_with_policy(de(), policy(OnDuplicate::KeepLast)).unwrap();
+        assert!(map.iter().eq([(&1, &3), (&2, &5)]));
+    }
+
+    #[test]
+    fn index_set_policy() {
+        use super::{DeserializePolicy, OnDuplicate, OnOverflow};
+        use crate::FnvIndexSet;
+
+        type Set = FnvIndexSet<u8, 2>;
+        let input = [1u8, 2, 1, 3];
+        let de = || SeqDeserializer::<_, TestError>::new(input.into_iter());
+
+        let err = Set::deserialize(de()).unwrap_err();
+        assert_eq!(err.0, "invalid length 3, expected a sequence");
+        let set =
+            Set::deserialize(SeqDeserializer::<_, TestError>::new([2u8, 2].into_iter())).unwrap();
+        assert!(set.iter().eq(&[2]));
+
+        let err = Set::deserialize_with_policy(de(), DeserializePolicy::STRICT).unwrap_err();
+        assert_eq!(err.0, "duplicate value in set");
+
+        let policy = DeserializePolicy {
+            on_overflow: OnOverflow::Ignore,
+            on_duplicate: OnDuplicate::KeepLast,
+        };
+        let set = Set::deserialize_with_policy(de(), policy).unwrap();
+        assert!(set.iter().eq(&[1, 2]));
+    }
+
+    #[test]
+    fn vec_capacity_error() {
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter());
+        let err = Vec::<u8, 2>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 3, expected a sequence no more than 2 elements long"
+        );
+
+        // Without a size hint, deserialization stops at the first element that doesn't fit
+        let de = SeqDeserializer::<_, TestError>::new([1u8, 2, 3].into_iter().filter(|_| true));
+        let err = Vec::<u8, 2>::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.0,
+            "invalid length 3, expected a sequence no more than 2 elements long"
+        );
+    }
+}
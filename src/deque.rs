@@ -36,11 +36,15 @@
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
 use core::fmt;
+use core::hash;
 use core::iter::FusedIterator;
-use core::mem::MaybeUninit;
-use core::{ptr, slice};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut, Range, RangeBounds};
+use core::ptr::{self, NonNull};
+use core::slice;
 
-use crate::storage::{OwnedStorage, Storage, ViewStorage};
+use crate::storage::{OwnedStorage, SealedStorage, Storage, ViewStorage};
 
 /// Base struct for [`Deque`] and [`DequeView`], generic over the [`Storage`].
 ///
@@ -60,6 +64,9 @@ pub struct DequeInner<T, S: Storage> {
 
 /// A fixed capacity double-ended queue.
 ///
+/// When `N` is a power of two, wrapping the indices around the end of the buffer is done with a
+/// bitmask instead of a comparison, which makes `push_*` and `pop_*` cheaper.
+///
 /// # Examples
 ///
 /// ```
@@ -131,6 +138,24 @@ pub type Deque<T, const N: usize> = DequeInner<T, OwnedStorage<N>>;
 ///     println!("{}", x);
 /// }
 /// ```
+///
+/// Functions taking a `&mut DequeView<T>` work with deques of any capacity:
+///
+/// ```
+/// use heapless::deque::{Deque, DequeView};
+///
+/// fn refill(deque: &mut DequeView<u8>) {
+///     while deque.push_back(0).is_ok() {}
+/// }
+///
+/// let mut small: Deque<u8, 2> = Deque::new();
+/// let mut large: Deque<u8, 8> = Deque::new();
+/// refill(&mut small);
+/// refill(large.as_mut_view());
+/// assert_eq!(small.len(), 2);
+/// assert_eq!(large.len(), 8);
+/// assert_eq!(large.as_view(), <&DequeView<u8>>::from(&large));
+/// ```
 pub type DequeView<T> = DequeInner<T, ViewStorage>;
 
 impl<T, const N: usize> Deque<T, N> {
@@ -161,6 +186,81 @@ impl<T, const N: usize> Deque<T, N> {
         }
     }
 
+    /// Constructs a new deque with a fixed capacity of `N`, initialized with the provided
+    /// slice.
+    ///
+    /// The elements are stored contiguously, starting at the front of the buffer.
+    ///
+    /// Returns `Err(())` if the deque cannot hold all the elements of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let deque: Deque<u8, 8> = Deque::from_slice(&[1, 2, 3]).unwrap();
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    ///
+    /// assert!(Deque::<u8, 2>::from_slice(&[1, 2, 3]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn from_slice(other: &[T]) -> Result<Self, ()>
+    where
+        T: Clone,
+    {
+        if other.len() > N {
+            return Err(());
+        }
+
+        let mut deque = Self::new();
+        for item in other {
+            // NOTE(unsafe) the deque can hold all the elements of `other`
+            unsafe { deque.push_back_unchecked(item.clone()) }
+        }
+        Ok(deque)
+    }
+
+    /// Constructs a new deque with a fixed capacity of `N`, initialized with the provided array.
+    ///
+    /// The elements are stored contiguously, starting at the front of the buffer.
+    ///
+    /// If the length of the provided array is greater than the capacity of the deque a
+    /// compile-time error will be produced.
+    ///
+    /// This function can be used in `const` contexts:
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// static PRIORITIES: Deque<u8, 4> = Deque::from_array([3, 1, 2]);
+    /// assert!(PRIORITIES.iter().eq(&[3, 1, 2]));
+    /// ```
+    pub const fn from_array<const M: usize>(src: [T; M]) -> Self {
+        // Const assert N >= M
+        crate::sealed::greater_than_eq::<N, M>();
+
+        // We've got to copy `src`, but we're functionally moving it. Don't run
+        // any Drop code for T.
+        let src = ManuallyDrop::new(src);
+
+        let mut deque = Self::new();
+        // NOTE(unsafe) src elements are not going to drop as src itself
+        // is wrapped in a ManuallyDrop, and `N >= M`.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &src as *const ManuallyDrop<[T; M]> as *const T,
+                deque.buffer.as_mut_ptr() as *mut T,
+                M,
+            );
+        }
+        if M == N {
+            deque.full = true;
+        } else {
+            deque.back = M;
+        }
+        deque
+    }
+
     /// Returns the maximum number of elements the deque can hold.
     ///
     /// This method is not available on a `DequeView`, use [`storage_capacity`](DequeInner::storage_capacity) instead.
@@ -190,6 +290,96 @@ impl<T, const N: usize> Deque<T, N> {
     pub fn as_mut_view(&mut self) -> &mut DequeView<T> {
         self
     }
+
+    /// Removes the specified range from the deque in bulk, returning all removed elements as an
+    /// iterator. If the iterator is dropped before being fully consumed, it drops the remaining
+    /// removed elements.
+    ///
+    /// The returned iterator keeps a mutable borrow on the deque to optimize its implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the deque.
+    ///
+    /// # Leaking
+    ///
+    /// If the returned iterator goes out of scope without being dropped (due to
+    /// [`core::mem::forget`], for example), the deque may have lost and leaked elements
+    /// arbitrarily, including elements outside the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{Deque, Vec};
+    ///
+    /// // Timer deadlines, sorted front-to-back
+    /// let mut timers: Deque<u32, 8> = Deque::new();
+    /// timers.extend([10, 20, 30, 40]);
+    ///
+    /// let now = 25;
+    /// let expired = timers.iter().take_while(|&&deadline| deadline <= now).count();
+    /// let fired: Vec<u32, 8> = timers.drain(..expired).collect();
+    /// assert_eq!(fired, [10, 20]);
+    /// assert_eq!(timers.iter().copied().collect::<Vec<_, 8>>(), [30, 40]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_mut_view().drain(range)
+    }
+}
+
+impl<T> DequeView<T> {
+    /// Removes the specified range from the deque in bulk, returning all removed elements as an
+    /// iterator. If the iterator is dropped before being fully consumed, it drops the remaining
+    /// removed elements.
+    ///
+    /// The returned iterator keeps a mutable borrow on the deque to optimize its implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the deque.
+    ///
+    /// # Leaking
+    ///
+    /// If the returned iterator goes out of scope without being dropped (due to
+    /// [`core::mem::forget`], for example), the deque may have lost and leaked elements
+    /// arbitrarily, including elements outside the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::deque::{Deque, DequeView};
+    ///
+    /// let mut buf: Deque<u8, 8> = Deque::new();
+    /// buf.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let view: &mut DequeView<u8> = &mut buf;
+    /// assert!(view.drain(1..4).eq([2, 3, 4]));
+    /// assert!(view.iter().eq(&[1, 5]));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.storage_len();
+        let Range { start, end } = crate::slice::range(range, ..len);
+
+        // Shorten the deque to `start` elements, to be safe in case `Drain` is leaked.
+        // NOTE(unsafe) `start <= len`
+        unsafe { self.set_len(start) };
+        Drain {
+            idx: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            deque: NonNull::from(self),
+            _marker: PhantomData,
+        }
+    }
 }
 
 impl<T, S: Storage> DequeInner<T, S> {
@@ -198,17 +388,27 @@ impl<T, S: Storage> DequeInner<T, S> {
         self.buffer.borrow().len()
     }
 
+    // NOTE for owned storage the capacity is a constant, so the power of two check is resolved at
+    // compile time
+    #[inline]
     fn increment(&self, i: usize) -> usize {
-        if i + 1 == self.storage_capacity() {
+        let cap = self.storage_capacity();
+        if cap.is_power_of_two() {
+            (i + 1) & (cap - 1)
+        } else if i + 1 == cap {
             0
         } else {
             i + 1
         }
     }
 
+    #[inline]
     fn decrement(&self, i: usize) -> usize {
-        if i == 0 {
-            self.storage_capacity() - 1
+        let cap = self.storage_capacity();
+        if cap.is_power_of_two() {
+            i.wrapping_sub(1) & (cap - 1)
+        } else if i == 0 {
+            cap - 1
         } else {
             i - 1
         }
@@ -255,6 +455,25 @@ impl<T, S: Storage> DequeInner<T, S> {
     }
 
     /// Returns a pair of slices which contain, in order, the contents of the `Deque`.
+    ///
+    /// The second slice is empty unless the contents wrap around the end of the buffer. Use
+    /// [`make_contiguous`](DequeInner::make_contiguous) to get a single slice, for example to
+    /// hand the contents to a DMA transfer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut tx: Deque<u8, 4> = Deque::new();
+    /// tx.extend(*b"abc");
+    /// tx.pop_front();
+    /// tx.extend(*b"de");
+    /// assert_eq!(tx.as_slices(), (&b"bcd"[..], &b"e"[..]));
+    ///
+    /// assert_eq!(tx.make_contiguous(), b"bcde");
+    /// assert_eq!(tx.as_slices(), (&b"bcde"[..], &b""[..]));
+    /// ```
     pub fn as_slices(&self) -> (&[T], &[T]) {
         // NOTE(unsafe) avoid bound checks in the slicing operation
         unsafe {
@@ -281,6 +500,22 @@ impl<T, S: Storage> DequeInner<T, S> {
     }
 
     /// Returns a pair of mutable slices which contain, in order, the contents of the `Deque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u8, 4> = Deque::new();
+    /// deque.extend([1, 2, 3]);
+    /// deque.pop_front();
+    /// deque.extend([4, 5]);
+    ///
+    /// let (a, b) = deque.as_mut_slices();
+    /// a.fill(0);
+    /// b[0] = 9;
+    /// assert!(deque.iter().eq(&[0, 0, 0, 9]));
+    /// ```
     pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
         let ptr = self.buffer.borrow_mut().as_mut_ptr();
 
@@ -401,7 +636,7 @@ impl<T, S: Storage> DequeInner<T, S> {
             }
 
             self.front = back;
-            self.back = 0;
+            self.back = self.to_physical_index(len);
         } else {
             // `free` is smaller than both `head_len` and `tail_len`.
             // the general algorithm for this first moves the slices
@@ -480,7 +715,7 @@ impl<T, S: Storage> DequeInner<T, S> {
                     // the used part of the buffer now is `0..self.len`, so set
                     // `head` to the beginning of that range.
                     self.front = 0;
-                    self.back = len;
+                    self.back = self.to_physical_index(len);
                 }
             }
         }
@@ -580,6 +815,61 @@ impl<T, S: Storage> DequeInner<T, S> {
         }
     }
 
+    /// Appends an `item` to the back of the deque, removing the front item first if the deque is
+    /// full.
+    ///
+    /// Returns the removed item, if any. This keeps the most recent items, like a ring buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut log: Deque<&str, 2> = Deque::new();
+    /// assert_eq!(log.push_back_overwrite("boot"), None);
+    /// assert_eq!(log.push_back_overwrite("link up"), None);
+    /// assert_eq!(log.push_back_overwrite("link down"), Some("boot"));
+    /// assert!(log.iter().eq(&["link up", "link down"]));
+    /// ```
+    pub fn push_back_overwrite(&mut self, item: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            // NOTE(unsafe) a full deque is not empty
+            Some(unsafe { self.pop_front_unchecked() })
+        } else {
+            None
+        };
+        // NOTE(unsafe) the deque is not full anymore
+        unsafe { self.push_back_unchecked(item) };
+        evicted
+    }
+
+    /// Appends an `item` to the front of the deque, removing the back item first if the deque is
+    /// full.
+    ///
+    /// Returns the removed item, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u8, 2> = Deque::new();
+    /// deque.extend([1, 2]);
+    /// assert_eq!(deque.push_front_overwrite(0), Some(2));
+    /// assert!(deque.iter().eq(&[0, 1]));
+    /// ```
+    pub fn push_front_overwrite(&mut self, item: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            // NOTE(unsafe) a full deque is not empty
+            Some(unsafe { self.pop_back_unchecked() })
+        } else {
+            None
+        };
+        // NOTE(unsafe) the deque is not full anymore
+        unsafe { self.push_front_unchecked(item) };
+        evicted
+    }
+
     /// Removes an item from the front of the deque and returns it, without checking that the deque
     /// is not empty
     ///
@@ -652,120 +942,858 @@ impl<T, S: Storage> DequeInner<T, S> {
         }
     }
 
-    /// Returns a reference to the element at the given index.
+    /// Moves all the elements of `other` to the back of the deque, leaving `other` empty.
     ///
-    /// Index 0 is the front of the `Deque`.
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index < self.storage_len() {
-            let idx = self.to_physical_index(index);
-            Some(unsafe { self.buffer.borrow().get_unchecked(idx).assume_init_ref() })
-        } else {
-            None
+    /// Returns an error, leaving both deques unmodified, if `self` cannot hold all the elements
+    /// of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut local: Deque<u32, 8> = Deque::new();
+    /// local.extend([1, 2]);
+    /// let mut stolen: Deque<u32, 4> = Deque::new();
+    /// stolen.extend([3, 4, 5]);
+    ///
+    /// local.append(&mut stolen).unwrap();
+    /// assert!(local.iter().eq(&[1, 2, 3, 4, 5]));
+    /// assert!(stolen.is_empty());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn append(&mut self, other: &mut DequeView<T>) -> Result<(), ()> {
+        let len = self.storage_len();
+        let other_len = other.storage_len();
+        if other_len > self.storage_capacity() - len {
+            return Err(());
         }
-    }
 
-    /// Returns a mutable reference to the element at the given index.
-    ///
-    /// Index 0 is the front of the `Deque`.
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index < self.storage_len() {
-            let idx = self.to_physical_index(index);
-            Some(unsafe {
-                self.buffer
-                    .borrow_mut()
-                    .get_unchecked_mut(idx)
-                    .assume_init_mut()
-            })
-        } else {
-            None
+        let (a, b) = other.as_slices();
+        // NOTE(unsafe) there's room for the elements of `other` after the back of the deque, and
+        // `other` is emptied without dropping them
+        unsafe {
+            self.copy_from_slice_at(self.back, a);
+            self.set_len(len + a.len());
+            self.copy_from_slice_at(self.back, b);
+            self.set_len(len + other_len);
+            other.set_len(0);
         }
+        Ok(())
     }
 
-    /// Returns a reference to the element at the given index without checking if it exists.
+    /// Splits the deque into two at the given index.
     ///
-    /// # Safety
+    /// Returns a new deque containing the elements in the range `[at, len)`. After the call, the
+    /// original deque will be left containing the elements `[0, at)`.
     ///
-    /// The element at the given `index` must exist (i.e. `index < self.len()`).
-    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
-        debug_assert!(index < self.storage_len());
+    /// Returns an error, leaving the original deque unmodified, if the returned deque cannot hold
+    /// `len - at` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut work: Deque<u32, 8> = Deque::new();
+    /// work.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let half: Deque<u32, 4> = work.split_off(3).unwrap();
+    /// assert!(work.iter().eq(&[1, 2, 3]));
+    /// assert!(half.iter().eq(&[4, 5]));
+    ///
+    /// assert!(work.split_off::<2>(0).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<Deque<T, M>, ()> {
+        let len = self.storage_len();
+        assert!(at <= len, "`at` out of bounds");
+        if len - at > M {
+            return Err(());
+        }
 
-        let idx = self.to_physical_index(index);
-        self.buffer.borrow().get_unchecked(idx).assume_init_ref()
+        let mut other = Deque::new();
+        let (a, b) = self.as_slices();
+        let (a, b) = if at < a.len() {
+            (&a[at..], b)
+        } else {
+            (&b[at - a.len()..], &[][..])
+        };
+        // NOTE(unsafe) `other` can hold the `len - at` elements, which are removed from `self`
+        // without dropping them
+        unsafe {
+            other.copy_from_slice_at(0, a);
+            other.copy_from_slice_at(a.len() % M, b);
+            other.set_len(len - at);
+            self.set_len(at);
+        }
+        Ok(other)
     }
 
-    /// Returns a mutable reference to the element at the given index without checking if it exists.
+    /// Returns the free space of the deque, following its back, as a pair of slices of
+    /// `MaybeUninit<T>`.
     ///
-    /// # Safety
+    /// The first slice starts right after the back of the deque, and the second one, which is
+    /// empty unless the free space wraps around the end of the buffer, follows it. Once elements
+    /// have been written to the start of this space, they can be appended to the deque with
+    /// [`commit`](DequeInner::commit), for example after a DMA transfer or a `read()` call wrote
+    /// directly into the deque.
     ///
-    /// The element at the given `index` must exist (i.e. `index < self.len()`).
-    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
-        debug_assert!(index < self.storage_len());
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use heapless::Deque;
+    ///
+    /// // Stand-in for a UART driver writing received bytes into a buffer
+    /// fn read(buf: &mut [MaybeUninit<u8>], data: &[u8]) -> usize {
+    ///     let n = buf.len().min(data.len());
+    ///     for (dst, src) in buf.iter_mut().zip(&data[..n]) {
+    ///         dst.write(*src);
+    ///     }
+    ///     n
+    /// }
+    ///
+    /// let mut rx: Deque<u8, 8> = Deque::new();
+    /// rx.extend(*b"xxxxxxAT");
+    /// rx.drain(..6);
+    ///
+    /// let (first, second) = rx.spare_capacity_mut();
+    /// assert_eq!(first.len() + second.len(), 6);
+    /// let n = read(first, b"+OK\r\n");
+    /// // SAFETY: `read` initialized the first `n` elements of the free space
+    /// unsafe { rx.commit(n) };
+    ///
+    /// assert!(rx.iter().eq(b"AT+OK\r\n"));
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        if self.full {
+            return (&mut [], &mut []);
+        }
 
-        let idx = self.to_physical_index(index);
-        self.buffer
-            .borrow_mut()
-            .get_unchecked_mut(idx)
-            .assume_init_mut()
+        let (front, back) = (self.front, self.back);
+        let buffer = self.buffer.borrow_mut();
+        if front <= back {
+            let (head, tail) = buffer.split_at_mut(back);
+            (tail, &mut head[..front])
+        } else {
+            (&mut buffer[back..front], &mut [])
+        }
     }
 
-    /// Swaps elements at indices `i` and `j`.
+    /// Appends the first `n` elements of the free space returned by
+    /// [`spare_capacity_mut`](DequeInner::spare_capacity_mut) to the back of the deque.
     ///
     /// # Panics
     ///
-    /// Panics if either `i` or `j` are out of bounds.
-    pub fn swap(&mut self, i: usize, j: usize) {
-        let len = self.storage_len();
-        assert!(i < len);
-        assert!(j < len);
-        unsafe { self.swap_unchecked(i, j) }
-    }
-
-    /// Swaps elements at indices `i` and `j` without checking that they exist.
+    /// Panics if `n` is greater than the free space of the deque.
     ///
     /// # Safety
     ///
-    /// Elements at indexes `i` and `j` must exist (i.e. `i < self.len()` and `j < self.len()`).
-    pub unsafe fn swap_unchecked(&mut self, i: usize, j: usize) {
-        debug_assert!(i < self.storage_len());
-        debug_assert!(j < self.storage_len());
-        let idx_i = self.to_physical_index(i);
-        let idx_j = self.to_physical_index(j);
-
-        let buffer = self.buffer.borrow_mut();
-        let buffer_ptr = buffer.as_mut_ptr();
-        let ptr_i = buffer_ptr.add(idx_i);
-        let ptr_j = buffer_ptr.add(idx_j);
-        ptr::swap(ptr_i, ptr_j);
+    /// The first `n` elements of the free space must have been initialized.
+    pub unsafe fn commit(&mut self, n: usize) {
+        let len = self.storage_len();
+        assert!(n <= self.storage_capacity() - len);
+        self.set_len(len + n);
     }
 
-    /// Removes an element from anywhere in the deque and returns it, replacing it with the first
-    /// element.
+    /// Inserts an element at position `index` within the deque, shifting the elements on the
+    /// shorter side of `index` to make room.
     ///
-    /// This does not preserve ordering, but is *O*(1).
+    /// Element at index 0 is the front of the queue.
     ///
-    /// Returns `None` if `index` is out of bounds.
+    /// Returns back the `item` if the deque is full.
     ///
-    /// Element at index 0 is the front of the queue.
-    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut sorted: Deque<u8, 4> = Deque::new();
+    /// for x in [30, 10, 20] {
+    ///     let index = sorted.binary_search(&x).unwrap_or_else(|i| i);
+    ///     sorted.insert(index, x).unwrap();
+    /// }
+    /// assert!(sorted.iter().eq(&[10, 20, 30]));
+    /// ```
+    pub fn insert(&mut self, index: usize, item: T) -> Result<(), T> {
         let len = self.storage_len();
-        if len > 0 && index < len {
-            Some(unsafe {
-                self.swap_unchecked(index, 0);
-                self.pop_front_unchecked()
-            })
-        } else {
-            None
+        assert!(index <= len, "index out of bounds");
+        if self.is_full() {
+            return Err(item);
+        }
+
+        // NOTE(unsafe) all the indices are less than `len + 1`, and the deque isn't full
+        unsafe {
+            if index < len - index {
+                // Shift the elements before `index` towards the front
+                self.front = self.decrement(self.front);
+                for i in 0..index {
+                    self.move_slot(i + 1, i);
+                }
+            } else {
+                // Shift the elements from `index` towards the back
+                for i in (index..len).rev() {
+                    self.move_slot(i, i + 1);
+                }
+            }
+            self.slot(index).write(item);
+            self.set_len(len + 1);
         }
+        Ok(())
     }
 
-    /// Removes an element from anywhere in the deque and returns it, replacing it with the last
-    /// element.
+    /// Removes and returns the element at `index` from the deque, shifting the elements on the
+    /// shorter side of `index` to close the gap.
     ///
-    /// This does not preserve ordering, but is *O*(1).
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u8, 4> = Deque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(deque.remove(1), Some(2));
+    /// assert!(deque.iter().eq(&[1, 3, 4]));
+    /// assert_eq!(deque.remove(3), None);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.storage_len();
+        if index >= len {
+            return None;
+        }
+
+        // NOTE(unsafe) all the indices are less than `len`, and the element at `index` is
+        // overwritten after being read
+        unsafe {
+            let item = self.slot(index).read();
+            if index < len - index - 1 {
+                // Shift the elements before `index` towards the back
+                for i in (0..index).rev() {
+                    self.move_slot(i, i + 1);
+                }
+                self.front = self.increment(self.front);
+            } else {
+                // Shift the elements after `index` towards the front
+                for i in index + 1..len {
+                    self.move_slot(i, i - 1);
+                }
+            }
+            self.set_len(len - 1);
+            Some(item)
+        }
+    }
+
+    /// Returns a pointer to the slot at `index` from the front, which may be uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// `index` must not exceed the capacity.
+    unsafe fn slot(&mut self, index: usize) -> *mut T {
+        let idx = self.to_physical_index(index);
+        self.buffer.borrow_mut().as_mut_ptr().add(idx).cast()
+    }
+
+    /// Returns the slots from `start` to `end` counted from the front, which may be
+    /// uninitialized, as two contiguous parts of the buffer.
+    ///
+    /// # Safety
+    ///
+    /// `start` must not exceed `end`, and neither may exceed the capacity.
+    unsafe fn slots(&mut self, start: usize, end: usize) -> (*mut [T], *mut [T]) {
+        let cap = self.storage_capacity();
+        let len = end - start;
+        let start = self.to_physical_index(start);
+        let buffer: *mut T = self.buffer.borrow_mut().as_mut_ptr().cast();
+        let first = len.min(cap - start);
+        (
+            ptr::slice_from_raw_parts_mut(buffer.add(start), first),
+            ptr::slice_from_raw_parts_mut(buffer, len - first),
+        )
+    }
+
+    /// Moves the element in the slot at `from` to the slot at `to`, both counted from the front.
+    ///
+    /// # Safety
+    ///
+    /// Neither index may exceed the capacity, and the slot at `from` must be initialized.
+    unsafe fn move_slot(&mut self, from: usize, to: usize) {
+        let (from, to) = (self.to_physical_index(from), self.to_physical_index(to));
+        // Both pointers must come from the same borrow of the buffer
+        let buffer: *mut T = self.buffer.borrow_mut().as_mut_ptr().cast();
+        ptr::copy_nonoverlapping(buffer.add(from), buffer.add(to), 1);
+    }
+
+    /// Resizes the deque in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the deque is extended at the back by the
+    /// difference, with each additional slot filled with `value`. If `new_len` is less than the
+    /// length, the deque is truncated at the back.
+    ///
+    /// Returns an error, leaving the deque unmodified, if `new_len` is greater than the capacity.
+    ///
+    /// See also [`resize_with`](Self::resize_with).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut jitter: Deque<u8, 8> = Deque::new();
+    /// jitter.resize(4, 0).unwrap();
+    /// assert!(jitter.iter().eq(&[0, 0, 0, 0]));
+    ///
+    /// jitter.resize(2, 0).unwrap();
+    /// assert_eq!(jitter.storage_len(), 2);
+    /// assert_eq!(jitter.resize(9, 0), Err(()));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), ()>
+    where
+        T: Clone,
+    {
+        self.resize_with(new_len, || value.clone())
+    }
+
+    /// Resizes the deque in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the deque is extended at the back by the
+    /// difference, with each additional slot filled with the result of calling the closure `f`.
+    /// The return values from `f` end up in the deque in the order they have been generated.
+    ///
+    /// If `new_len` is less than the length, the deque is truncated at the back.
+    ///
+    /// Returns an error, leaving the deque unmodified, if `new_len` is greater than the capacity.
+    ///
+    /// See also [`resize`](Self::resize).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u32, 8> = Deque::new();
+    /// deque.push_back(1).unwrap();
+    /// let mut p = 1;
+    /// deque
+    ///     .resize_with(4, || {
+    ///         p *= 2;
+    ///         p
+    ///     })
+    ///     .unwrap();
+    /// assert!(deque.iter().eq(&[1, 2, 4, 8]));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F) -> Result<(), ()>
+    where
+        F: FnMut() -> T,
+    {
+        if new_len > self.storage_capacity() {
+            return Err(());
+        }
+
+        let len = self.storage_len();
+        if new_len > len {
+            for _ in len..new_len {
+                // NOTE(unsafe) `new_len` is within the capacity
+                unsafe { self.push_back_unchecked(f()) };
+            }
+        } else {
+            self.truncate_back(new_len);
+        }
+
+        Ok(())
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the deque's current length, this has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u8, 4> = Deque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    ///
+    /// deque.truncate_back(1);
+    /// assert!(deque.iter().eq(&[1]));
+    /// ```
+    pub fn truncate_back(&mut self, len: usize) {
+        let old_len = self.storage_len();
+        if len >= old_len {
+            return;
+        }
+
+        // NOTE(unsafe) the length is shrunk before dropping the removed elements, so that they
+        // can't be dropped twice if a destructor panics
+        unsafe {
+            self.set_len(len);
+            let (drop_a, drop_b) = self.slots(len, old_len);
+            ptr::drop_in_place(drop_a);
+            ptr::drop_in_place(drop_b);
+        }
+    }
+
+    /// Shortens the deque, keeping the last `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the deque's current length, this has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// // Drop the oldest samples in bulk when falling behind
+    /// let mut samples: Deque<u8, 4> = Deque::new();
+    /// samples.extend([1, 2, 3, 4]);
+    ///
+    /// samples.truncate_front(1);
+    /// assert!(samples.iter().eq(&[4]));
+    /// ```
+    pub fn truncate_front(&mut self, len: usize) {
+        let old_len = self.storage_len();
+        if len >= old_len {
+            return;
+        }
+
+        let removed = old_len - len;
+        // NOTE(unsafe) the front is moved past the removed elements before dropping them, so that
+        // they can't be dropped twice if a destructor panics
+        unsafe {
+            self.front = self.to_physical_index(removed);
+            self.set_len(len);
+            // The removed elements are now the last ones before the front
+            let cap = self.storage_capacity();
+            let (drop_a, drop_b) = self.slots(cap - removed, cap);
+            ptr::drop_in_place(drop_a);
+            ptr::drop_in_place(drop_b);
+        }
+    }
+
+    /// Rotates the deque `n` places to the left, so that the element at index `n` becomes the
+    /// front.
+    ///
+    /// If the deque is full this only moves its indices, otherwise `min(n, len - n)` elements
+    /// are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut tasks: Deque<char, 4> = Deque::new();
+    /// tasks.extend(['a', 'b', 'c']);
+    ///
+    /// tasks.rotate_left(1);
+    /// assert!(tasks.iter().eq(&['b', 'c', 'a']));
+    /// tasks.rotate_left(2);
+    /// assert!(tasks.iter().eq(&['a', 'b', 'c']));
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.storage_len();
+        assert!(n <= len);
+        if self.full {
+            self.front = self.to_physical_index(n);
+            self.back = self.front;
+        } else if n <= len - n {
+            for _ in 0..n {
+                // NOTE(unsafe) the deque is neither empty nor full
+                unsafe {
+                    let item = self.pop_front_unchecked();
+                    self.push_back_unchecked(item);
+                }
+            }
+        } else {
+            self.rotate_right(len - n);
+        }
+    }
+
+    /// Rotates the deque `n` places to the right, so that the element at index `len - n`
+    /// becomes the front.
+    ///
+    /// If the deque is full this only moves its indices, otherwise `min(n, len - n)` elements
+    /// are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut tasks: Deque<char, 3> = Deque::new();
+    /// tasks.extend(['a', 'b', 'c']);
+    ///
+    /// tasks.rotate_right(1);
+    /// assert!(tasks.iter().eq(&['c', 'a', 'b']));
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.storage_len();
+        assert!(n <= len);
+        if self.full {
+            self.front = self.to_physical_index(len - n);
+            self.back = self.front;
+        } else if n <= len - n {
+            for _ in 0..n {
+                // NOTE(unsafe) the deque is neither empty nor full
+                unsafe {
+                    let item = self.pop_back_unchecked();
+                    self.push_front_unchecked(item);
+                }
+            }
+        } else {
+            self.rotate_left(len - n);
+        }
+    }
+
+    /// Binary searches this sorted deque for a given element.
+    ///
+    /// If the value is found then [`Result::Ok`] is returned, containing the index of the
+    /// matching element. If there are multiple matches, then any one of the matches could be
+    /// returned. If the value is not found then [`Result::Err`] is returned, containing the index
+    /// where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// The search is done over the two slices of the deque, without rearranging it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<i32, 8> = Deque::new();
+    /// deque.extend([3, 5, 8]);
+    /// deque.push_front(1).unwrap();
+    ///
+    /// assert_eq!(deque.binary_search(&5), Ok(2));
+    /// assert_eq!(deque.binary_search(&4), Err(2));
+    /// assert_eq!(deque.binary_search(&9), Err(4));
+    /// ```
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|e| e.cmp(x))
+    }
+
+    /// Binary searches this sorted deque with a comparator function.
+    ///
+    /// The comparator function should return an order code that indicates whether its argument
+    /// is `Less`, `Equal` or `Greater` than the desired target.
+    ///
+    /// See [`binary_search`](DequeInner::binary_search) for the meaning of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<(u32, char), 8> = Deque::new();
+    /// deque.extend([(10, 'a'), (20, 'b'), (30, 'c')]);
+    ///
+    /// assert_eq!(deque.binary_search_by(|(t, _)| t.cmp(&20)), Ok(1));
+    /// assert_eq!(deque.binary_search_by(|(t, _)| t.cmp(&25)), Err(2));
+    /// ```
+    pub fn binary_search_by<'a, F>(&'a self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        let (front, back) = self.as_slices();
+        match back.first().map(&mut f) {
+            Some(Ordering::Equal) => Ok(front.len()),
+            Some(Ordering::Less) => back
+                .binary_search_by(f)
+                .map(|idx| idx + front.len())
+                .map_err(|idx| idx + front.len()),
+            _ => front.binary_search_by(f),
+        }
+    }
+
+    /// Binary searches this sorted deque with a key extraction function.
+    ///
+    /// See [`binary_search`](DequeInner::binary_search) for the meaning of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<(u32, char), 8> = Deque::new();
+    /// deque.extend([(10, 'a'), (20, 'b'), (30, 'c')]);
+    ///
+    /// assert_eq!(deque.binary_search_by_key(&30, |&(t, _)| t), Ok(2));
+    /// assert_eq!(deque.binary_search_by_key(&5, |&(t, _)| t), Err(0));
+    /// ```
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|k| f(k).cmp(b))
+    }
+
+    /// Copies `other` bitwise into the buffer, starting at the physical index `start` and
+    /// wrapping around the end of the buffer.
+    ///
+    /// # Safety
+    ///
+    /// `start` must be less than the capacity, and `other` must fit in the free space of the
+    /// deque starting at `start`. Unless `T: Copy`, the elements of `other` must not be used
+    /// afterwards.
+    unsafe fn copy_from_slice_at(&mut self, start: usize, other: &[T]) {
+        let capacity = self.storage_capacity();
+        let first = other.len().min(capacity - start);
+        let buffer_ptr: *mut T = self.buffer.borrow_mut().as_mut_ptr().cast();
+        ptr::copy_nonoverlapping(other.as_ptr(), buffer_ptr.add(start), first);
+        ptr::copy_nonoverlapping(other.as_ptr().add(first), buffer_ptr, other.len() - first);
+    }
+
+    /// Appends the elements of `other` to the back of the deque.
+    ///
+    /// This copies at most two contiguous segments, unlike repeated calls to
+    /// [`push_back`](DequeInner::push_back).
+    ///
+    /// Returns `Err(())` without modifying the deque if it cannot hold all the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut rx: Deque<u8, 8> = Deque::new();
+    /// rx.extend_back_from_slice(b"AT").unwrap();
+    /// rx.extend_back_from_slice(b"+OK\r\n").unwrap();
+    /// assert!(rx.iter().eq(b"AT+OK\r\n"));
+    ///
+    /// assert!(rx.extend_back_from_slice(b"\r\n").is_err());
+    /// assert_eq!(rx.len(), 7);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn extend_back_from_slice(&mut self, other: &[T]) -> Result<(), ()>
+    where
+        T: Copy,
+    {
+        let len = self.storage_len();
+        if other.len() > self.storage_capacity() - len {
+            return Err(());
+        }
+
+        // NOTE(unsafe) there's room for `other` after the back of the deque
+        unsafe {
+            self.copy_from_slice_at(self.back, other);
+            self.set_len(len + other.len());
+        }
+        Ok(())
+    }
+
+    /// Prepends the elements of `other` to the front of the deque, keeping their order: the first
+    /// element of `other` becomes the front of the deque.
+    ///
+    /// This copies at most two contiguous segments, unlike repeated calls to
+    /// [`push_front`](DequeInner::push_front).
+    ///
+    /// Returns `Err(())` without modifying the deque if it cannot hold all the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut frame: Deque<u8, 8> = Deque::new();
+    /// frame.extend_back_from_slice(b"data").unwrap();
+    /// frame.extend_front_from_slice(&[0x7e, 4]).unwrap();
+    /// assert!(frame.iter().eq(&[0x7e, 4, b'd', b'a', b't', b'a']));
+    ///
+    /// assert!(frame.extend_front_from_slice(&[0; 3]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn extend_front_from_slice(&mut self, other: &[T]) -> Result<(), ()>
+    where
+        T: Copy,
+    {
+        let len = self.storage_len();
+        let capacity = self.storage_capacity();
+        if other.len() > capacity - len {
+            return Err(());
+        }
+        if other.is_empty() {
+            return Ok(());
+        }
+
+        let front = if self.front >= other.len() {
+            self.front - other.len()
+        } else {
+            self.front + capacity - other.len()
+        };
+        // NOTE(unsafe) there's room for `other` before the front of the deque
+        unsafe {
+            self.copy_from_slice_at(front, other);
+        }
+        self.front = front;
+        self.full = len + other.len() == capacity;
+        Ok(())
+    }
+
+    /// Returns a reference to the element at the given index.
+    ///
+    /// Index 0 is the front of the `Deque`.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.storage_len() {
+            let idx = self.to_physical_index(index);
+            Some(unsafe { self.buffer.borrow().get_unchecked(idx).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at the given index.
+    ///
+    /// Index 0 is the front of the `Deque`.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.storage_len() {
+            let idx = self.to_physical_index(index);
+            Some(unsafe {
+                self.buffer
+                    .borrow_mut()
+                    .get_unchecked_mut(idx)
+                    .assume_init_mut()
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the element at the given index without checking if it exists.
+    ///
+    /// # Safety
+    ///
+    /// The element at the given `index` must exist (i.e. `index < self.len()`).
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        debug_assert!(index < self.storage_len());
+
+        let idx = self.to_physical_index(index);
+        self.buffer.borrow().get_unchecked(idx).assume_init_ref()
+    }
+
+    /// Returns a mutable reference to the element at the given index without checking if it exists.
+    ///
+    /// # Safety
+    ///
+    /// The element at the given `index` must exist (i.e. `index < self.len()`).
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        debug_assert!(index < self.storage_len());
+
+        let idx = self.to_physical_index(index);
+        self.buffer
+            .borrow_mut()
+            .get_unchecked_mut(idx)
+            .assume_init_mut()
+    }
+
+    /// Swaps elements at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `i` or `j` are out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.storage_len();
+        assert!(i < len);
+        assert!(j < len);
+        unsafe { self.swap_unchecked(i, j) }
+    }
+
+    /// Swaps elements at indices `i` and `j` without checking that they exist.
+    ///
+    /// # Safety
+    ///
+    /// Elements at indexes `i` and `j` must exist (i.e. `i < self.len()` and `j < self.len()`).
+    pub unsafe fn swap_unchecked(&mut self, i: usize, j: usize) {
+        debug_assert!(i < self.storage_len());
+        debug_assert!(j < self.storage_len());
+        let idx_i = self.to_physical_index(i);
+        let idx_j = self.to_physical_index(j);
+
+        let buffer = self.buffer.borrow_mut();
+        let buffer_ptr = buffer.as_mut_ptr();
+        let ptr_i = buffer_ptr.add(idx_i);
+        let ptr_j = buffer_ptr.add(idx_j);
+        ptr::swap(ptr_i, ptr_j);
+    }
+
+    /// Removes an element from anywhere in the deque and returns it, replacing it with the first
+    /// element.
+    ///
+    /// This does not preserve ordering, but is *O*(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut handles: Deque<u8, 4> = Deque::new();
+    /// handles.extend([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(handles.swap_remove_front(2), Some(3));
+    /// assert!(handles.iter().eq(&[2, 1, 4]));
+    /// assert_eq!(handles.swap_remove_front(3), None);
+    /// ```
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        let len = self.storage_len();
+        if len > 0 && index < len {
+            Some(unsafe {
+                self.swap_unchecked(index, 0);
+                self.pop_front_unchecked()
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Removes an element from anywhere in the deque and returns it, replacing it with the last
+    /// element.
+    ///
+    /// This does not preserve ordering, but is *O*(1).
     ///
     /// Returns `None` if `index` is out of bounds.
     ///
     /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut handles: Deque<u8, 4> = Deque::new();
+    /// handles.extend([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(handles.swap_remove_back(0), Some(1));
+    /// assert!(handles.iter().eq(&[4, 2, 3]));
+    /// assert_eq!(handles.swap_remove_back(3), None);
+    /// ```
     pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
         let len = self.storage_len();
         if len > 0 && index < len {
@@ -778,10 +1806,25 @@ impl<T, S: Storage> DequeInner<T, S> {
         }
     }
 
+    /// Sets the length of the deque, keeping its front.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed the capacity, and the first `len` elements must be initialized.
+    unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.storage_capacity());
+        self.back = self.to_physical_index(len);
+        self.full = len == self.storage_capacity();
+    }
+
+    #[inline]
     fn to_physical_index(&self, index: usize) -> usize {
+        let cap = self.storage_capacity();
         let mut res = self.front + index;
-        if res >= self.storage_capacity() {
-            res -= self.storage_capacity();
+        if cap.is_power_of_two() {
+            res &= cap - 1;
+        } else if res >= cap {
+            res -= cap;
         }
         res
     }
@@ -801,6 +1844,87 @@ impl<T, S: Storage> DequeInner<T, S> {
             inner: start.iter_mut().chain(end),
         }
     }
+
+    /// Returns an iterator over the elements in the given range of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut samples: Deque<i32, 4> = Deque::new();
+    /// samples.extend([1, 2, 3]);
+    /// samples.pop_front();
+    /// samples.extend([4, 5]);
+    ///
+    /// // The window wraps around the end of the buffer
+    /// let window: i32 = samples.range(1..4).sum();
+    /// assert_eq!(window, 12);
+    /// assert!(samples.range(..2).eq(&[2, 3]));
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = crate::slice::range(range, ..self.storage_len());
+        let (a, b) = self.as_slices();
+        let (a, b) = if start < a.len() {
+            (
+                &a[start..end.min(a.len())],
+                &b[..end.saturating_sub(a.len())],
+            )
+        } else {
+            (&b[start - a.len()..end - a.len()], &[][..])
+        };
+        Iter {
+            inner: a.iter().chain(b),
+        }
+    }
+
+    /// Returns an iterator that allows modifying the elements in the given range of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<i32, 4> = Deque::new();
+    /// deque.extend([1, 2, 3, 4]);
+    ///
+    /// for x in deque.range_mut(2..) {
+    ///     *x *= 10;
+    /// }
+    /// assert!(deque.iter().eq(&[1, 2, 30, 40]));
+    /// ```
+    pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = crate::slice::range(range, ..self.storage_len());
+        let (a, b) = self.as_mut_slices();
+        let a_len = a.len();
+        let (a, b) = if start < a_len {
+            (
+                &mut a[start..end.min(a_len)],
+                &mut b[..end.saturating_sub(a_len)],
+            )
+        } else {
+            (&mut b[start - a_len..end - a_len], &mut [][..])
+        };
+        IterMut {
+            inner: a.iter_mut().chain(b),
+        }
+    }
 }
 
 /// Iterator over the contents of a [`Deque`]
@@ -858,6 +1982,146 @@ impl<T> DoubleEndedIterator for IterMut<'_, T> {
 impl<T> ExactSizeIterator for IterMut<'_, T> {}
 impl<T> FusedIterator for IterMut<'_, T> {}
 
+/// A draining iterator for [`Deque`].
+///
+/// This `struct` is created by [`Deque::drain`]. See its documentation for more.
+pub struct Drain<'a, T> {
+    /// Index of the next element to yield from the front
+    idx: usize,
+    /// Index following the next element to yield from the back
+    end: usize,
+    /// Index of the elements following the drained range
+    tail_start: usize,
+    /// Number of elements following the drained range
+    tail_len: usize,
+    deque: NonNull<DequeView<T>>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<T> Drain<'_, T> {
+    /// Returns a pointer to the element at `index` in the original deque.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the length of the original deque.
+    unsafe fn ptr_at(&mut self, index: usize) -> *mut T {
+        let deque = self.deque.as_ptr();
+        let idx = (*deque).to_physical_index(index);
+        // Go through a raw place so that earlier pointers into the buffer stay valid
+        let buffer = ViewStorage::as_ptr(ptr::addr_of_mut!((*deque).buffer));
+        buffer.add(idx).cast()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // NOTE(unsafe) the remaining elements are initialized
+        let deque = unsafe { self.deque.as_ref() };
+        let remaining = (self.idx..self.end).map(|i| unsafe {
+            deque
+                .buffer
+                .borrow()
+                .get_unchecked(deque.to_physical_index(i))
+                .assume_init_ref()
+        });
+        f.write_str("Drain(")?;
+        f.debug_list().entries(remaining).finish()?;
+        f.write_str(")")
+    }
+}
+
+unsafe impl<T: Sync> Sync for Drain<'_, T> {}
+unsafe impl<T: Send> Send for Drain<'_, T> {}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.idx == self.end {
+            return None;
+        }
+        // NOTE(unsafe) the element is initialized, and won't be read again
+        let item = unsafe { self.ptr_at(self.idx).read() };
+        self.idx += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.idx == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // NOTE(unsafe) the element is initialized, and won't be read again
+        Some(unsafe { self.ptr_at(self.end).read() })
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        /// Closes the gap left by the drained elements to restore the original `Deque`.
+        struct DropGuard<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<T> Drop for DropGuard<'_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                // Drop the remaining elements if dropping one of them panicked
+                for item in &mut *drain {
+                    drop(item);
+                }
+
+                unsafe {
+                    let head_len = drain.deque.as_ref().storage_len();
+                    let drain_len = drain.tail_start - head_len;
+                    let tail_len = drain.tail_len;
+
+                    if drain_len > 0 {
+                        // Move the shorter side of the deque over the gap
+                        if head_len < tail_len {
+                            for i in (0..head_len).rev() {
+                                let src = drain.ptr_at(i);
+                                let dst = drain.ptr_at(i + drain_len);
+                                ptr::copy_nonoverlapping(src, dst, 1);
+                            }
+                            let deque = drain.deque.as_mut();
+                            deque.front = deque.to_physical_index(drain_len);
+                        } else {
+                            for i in 0..tail_len {
+                                let src = drain.ptr_at(drain.tail_start + i);
+                                let dst = drain.ptr_at(head_len + i);
+                                ptr::copy_nonoverlapping(src, dst, 1);
+                            }
+                        }
+                    }
+                    drain.deque.as_mut().set_len(head_len + tail_len);
+                }
+            }
+        }
+
+        let guard = DropGuard(self);
+        while guard.0.idx < guard.0.end {
+            // NOTE(unsafe) the element is initialized, and won't be read again
+            unsafe {
+                let ptr = guard.0.ptr_at(guard.0.idx);
+                guard.0.idx += 1;
+                ptr::drop_in_place(ptr);
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
 // Trait implementations
 
 impl<T, const N: usize> Default for Deque<T, N> {
@@ -881,6 +2145,58 @@ impl<T: fmt::Debug, S: Storage> fmt::Debug for DequeInner<T, S> {
     }
 }
 
+/// Index 0 is the front of the `Deque`.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::Deque;
+///
+/// let mut deque: Deque<u8, 4> = Deque::new();
+/// deque.extend([1, 2, 3]);
+/// deque.pop_front();
+/// deque.extend([4, 5]);
+///
+/// assert_eq!(deque[0], 2);
+/// assert_eq!(deque[3], 5);
+/// ```
+impl<T, S: Storage> Index<usize> for DequeInner<T, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+/// Index 0 is the front of the `Deque`.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+impl<T, S: Storage> IndexMut<usize> for DequeInner<T, S> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<'a, T: Clone, const N: usize> TryFrom<&'a [T]> for Deque<T, N> {
+    type Error = ();
+
+    fn try_from(slice: &'a [T]) -> Result<Self, Self::Error> {
+        Self::from_slice(slice)
+    }
+}
+
+impl<T, const N: usize, const M: usize> From<[T; M]> for Deque<T, N> {
+    fn from(array: [T; M]) -> Self {
+        Self::from_array(array)
+    }
+}
+
 /// As with the standard library's `VecDeque`, items are added via `push_back`.
 impl<T, S: Storage> Extend<T> for DequeInner<T, S> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
@@ -903,11 +2219,56 @@ pub struct IntoIter<T, const N: usize> {
     deque: Deque<T, N>,
 }
 
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Returns the remaining items of this iterator as a pair of slices, front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<u8, 4> = Deque::new();
+    /// deque.extend([1, 2, 3]);
+    ///
+    /// let mut iter = deque.into_iter();
+    /// iter.next();
+    /// assert_eq!(iter.as_slices(), (&[2, 3][..], &[][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.deque.as_slices()
+    }
+
+    /// Returns the remaining items of this iterator as a pair of mutable slices, front to back.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.deque.as_mut_slices()
+    }
+}
+
 impl<T, const N: usize> Iterator for IntoIter<T, N> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         self.deque.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.deque).finish()
+    }
 }
 
 impl<T, const N: usize> IntoIterator for Deque<T, N> {
@@ -959,9 +2320,12 @@ where
     }
 }
 
-impl<T: PartialEq, const N: usize> PartialEq for Deque<T, N> {
-    fn eq(&self, other: &Self) -> bool {
-        if self.len() != other.len() {
+impl<A, B, SA: Storage, SB: Storage> PartialEq<DequeInner<B, SB>> for DequeInner<A, SA>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &DequeInner<B, SB>) -> bool {
+        if self.storage_len() != other.storage_len() {
             return false;
         }
         let (sa, sb) = self.as_slices();
@@ -999,7 +2363,32 @@ impl<T: PartialEq, const N: usize> PartialEq for Deque<T, N> {
     }
 }
 
-impl<T: Eq, const N: usize> Eq for Deque<T, N> {}
+impl<T: Eq, S: Storage> Eq for DequeInner<T, S> {}
+
+impl<T, S: Storage> hash::Hash for DequeInner<T, S>
+where
+    T: hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        // Hash the same way regardless of where the elements wrap around
+        state.write_usize(self.storage_len());
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a Deque<T, N>> for &'a DequeView<T> {
+    fn from(deque: &'a Deque<T, N>) -> Self {
+        deque
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a mut Deque<T, N>> for &'a mut DequeView<T> {
+    fn from(deque: &'a mut Deque<T, N>) -> Self {
+        deque
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1050,6 +2439,663 @@ mod tests {
         assert_eq!(Droppable::count(), 0);
     }
 
+    #[test]
+    fn drain() {
+        use std::format;
+
+        // Deque with a wrapped buffer: [3, 4, 5, _, _, 0, 1, 2]
+        fn wrapped() -> Deque<i32, 8> {
+            let mut v = Deque::new();
+            for i in 0..5 {
+                v.push_back(i - 5).unwrap();
+                v.pop_front().unwrap();
+            }
+            v.extend(0..6);
+            v
+        }
+
+        for start in 0..=6 {
+            for end in start..=6 {
+                let mut v = wrapped();
+                assert!(v.drain(start..end).eq(start as i32..end as i32));
+                assert!(v.iter().copied().eq((0..start as i32).chain(end as i32..6)));
+                assert_eq!(v.len(), 6 - (end - start));
+            }
+        }
+
+        let mut v = wrapped();
+        let mut drain = v.drain(1..5);
+        assert_eq!(drain.len(), 4);
+        assert_eq!(format!("{:?}", drain), "Drain([1, 2, 3, 4])");
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(drain.len(), 2);
+        core::mem::drop(drain);
+        assert!(v.iter().eq(&[0, 5]));
+
+        let mut v = wrapped();
+        v.drain(..);
+        assert!(v.is_empty());
+        v.extend(0..8);
+        assert!(v.is_full());
+        assert!(v.drain(6..).eq([6, 7]));
+        assert!(!v.is_full());
+        v.push_back(8).unwrap();
+        assert!(v.iter().copied().eq((0..6).chain([8])));
+
+        let mut v = wrapped();
+        core::mem::forget(v.drain(2..3));
+        assert!(v.iter().eq(&[0, 1]));
+    }
+
+    #[test]
+    fn range() {
+        let mut v: Deque<i32, 8> = Deque::new();
+        for i in 0..5 {
+            v.push_back(i - 5).unwrap();
+            v.pop_front().unwrap();
+        }
+        v.extend(0..6);
+        // The buffer is now [3, 4, 5, _, _, 0, 1, 2]
+        for start in 0..=6 {
+            for end in start..=6 {
+                assert!(v.range(start..end).copied().eq(start as i32..end as i32));
+                assert!(v
+                    .range_mut(start..end)
+                    .rev()
+                    .map(|x| *x)
+                    .eq((start as i32..end as i32).rev()));
+                assert_eq!(v.range(start..end).len(), end - start);
+            }
+        }
+
+        for x in v.range_mut(2..5) {
+            *x = -*x;
+        }
+        assert!(v.iter().eq(&[0, 1, -2, -3, -4, 5]));
+        assert_eq!(v.range(..).len(), 6);
+        assert_eq!(v.range(6..).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn range_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2]);
+        v.range(..3);
+    }
+
+    #[test]
+    fn rotate() {
+        for capacity_left in 0..3 {
+            for n in 0..=5 {
+                let mut v: Deque<i32, 7> = Deque::new();
+                for _ in 0..3 {
+                    v.push_back(0).unwrap();
+                    v.pop_front().unwrap();
+                }
+                v.extend(0..7 - capacity_left);
+                let len = v.len() as i32;
+                v.rotate_left(n);
+                assert!(v.iter().copied().eq((0..len).map(|i| (i + n as i32) % len)));
+                v.rotate_right(n);
+                assert!(v.iter().copied().eq(0..len));
+                v.rotate_right(n);
+                assert!(v
+                    .iter()
+                    .copied()
+                    .eq((0..len).map(|i| (i + len - n as i32) % len)));
+                assert_eq!(v.len(), len as usize);
+            }
+        }
+
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.rotate_left(0);
+        v.rotate_right(0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2]);
+        v.rotate_left(3);
+    }
+
+    #[test]
+    fn binary_search() {
+        for offset in 0..8 {
+            let mut v: Deque<i32, 8> = Deque::new();
+            for _ in 0..offset {
+                v.push_back(0).unwrap();
+                v.pop_front().unwrap();
+            }
+            assert_eq!(v.binary_search(&1), Err(0));
+            v.extend([1, 3, 5, 7, 9, 11]);
+            for (i, x) in [1, 3, 5, 7, 9, 11].iter().enumerate() {
+                assert_eq!(v.binary_search(x), Ok(i));
+                assert_eq!(v.binary_search(&(x - 1)), Err(i));
+            }
+            assert_eq!(v.binary_search(&12), Err(6));
+            assert_eq!(v.binary_search_by_key(&6, |x| x / 2 + 1), Ok(5));
+        }
+    }
+
+    #[test]
+    fn append() {
+        for offset in 0..4 {
+            let mut v: Deque<i32, 4> = Deque::new();
+            let mut w: Deque<i32, 4> = Deque::new();
+            for _ in 0..offset {
+                v.push_back(0).unwrap();
+                v.pop_front().unwrap();
+                w.push_front(0).unwrap();
+                w.pop_back().unwrap();
+            }
+            v.push_back(1).unwrap();
+            w.extend([2, 3, 4, 5]);
+            assert!(v.append(&mut w).is_err());
+            assert_eq!(w.len(), 4);
+            w.pop_back().unwrap();
+            v.append(&mut w).unwrap();
+            assert!(v.is_full());
+            assert!(v.iter().eq(&[1, 2, 3, 4]));
+            assert!(w.is_empty());
+            w.push_back(6).unwrap();
+            assert!(w.iter().eq(&[6]));
+
+            w.clear();
+            v.append(&mut w).unwrap();
+            assert_eq!(v.len(), 4);
+        }
+    }
+
+    #[test]
+    fn split_off() {
+        for offset in 0..6 {
+            for at in 0..=5 {
+                let mut v: Deque<i32, 6> = Deque::new();
+                for _ in 0..offset {
+                    v.push_back(0).unwrap();
+                    v.pop_front().unwrap();
+                }
+                v.extend(0..5);
+                let w: Deque<i32, 5> = v.split_off(at).unwrap();
+                assert!(v.iter().copied().eq(0..at as i32));
+                assert!(w.iter().copied().eq(at as i32..5));
+                assert_eq!(w.len(), 5 - at);
+                v.push_back(9).unwrap();
+                assert_eq!(v.back(), Some(&9));
+            }
+        }
+
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2, 3]);
+        assert!(v.split_off::<1>(1).is_err());
+        assert_eq!(v.len(), 3);
+        let w: Deque<i32, 2> = v.split_off(1).unwrap();
+        assert!(w.is_full());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2]);
+        let _ = v.split_off::<4>(3);
+    }
+
+    #[test]
+    #[cfg(not(feature="copy"))]
+    fn append_split_off_drop() {
+        droppable!();
+
+        {
+            let mut v: Deque<Droppable, 4> = Deque::new();
+            let mut w: Deque<Droppable, 4> = Deque::new();
+            v.push_back(Droppable::new()).ok().unwrap();
+            w.push_back(Droppable::new()).ok().unwrap();
+            w.push_back(Droppable::new()).ok().unwrap();
+            v.append(&mut w).unwrap();
+            assert_eq!(Droppable::count(), 3);
+            let x: Deque<Droppable, 2> = v.split_off(1).unwrap();
+            assert_eq!(x.len(), 2);
+            assert_eq!(Droppable::count(), 3);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn push_overwrite() {
+        let mut v: Deque<i32, 3> = Deque::new();
+        for i in 0..3 {
+            assert_eq!(v.push_back_overwrite(i), None);
+        }
+        for i in 3..8 {
+            assert_eq!(v.push_back_overwrite(i), Some(i - 3));
+            assert!(v.iter().copied().eq(i - 2..=i));
+        }
+        assert_eq!(v.push_front_overwrite(4), Some(7));
+        assert!(v.iter().eq(&[4, 5, 6]));
+        v.pop_front();
+        assert_eq!(v.push_front_overwrite(3), None);
+        assert!(v.iter().eq(&[3, 5, 6]));
+    }
+
+    #[test]
+    #[cfg(not(feature="copy"))]
+    fn push_overwrite_drop() {
+        droppable!();
+
+        {
+            let mut v: Deque<Droppable, 2> = Deque::new();
+            for _ in 0..5 {
+                core::mem::drop(v.push_back_overwrite(Droppable::new()));
+            }
+            assert_eq!(Droppable::count(), 2);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn from_slice() {
+        let v: Deque<i32, 4> = Deque::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(v.as_slices(), (&[1, 2, 3][..], &[][..]));
+        let mut v: Deque<i32, 4> = Deque::try_from(&[1, 2, 3, 4][..]).unwrap();
+        assert!(v.is_full());
+        assert_eq!(v.pop_back(), Some(4));
+        assert!(Deque::<i32, 3>::from_slice(&[1, 2, 3, 4]).is_err());
+        let v: Deque<i32, 3> = Deque::from_slice(&[]).unwrap();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn from_array() {
+        let mut v: Deque<i32, 4> = Deque::from_array([1, 2, 3]);
+        assert_eq!(v.as_slices(), (&[1, 2, 3][..], &[][..]));
+        v.push_back(4).unwrap();
+        assert!(v.is_full());
+
+        let mut v: Deque<i32, 3> = [1, 2, 3].into();
+        assert!(v.is_full());
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.pop_front(), Some(1));
+        v.push_back(4).unwrap();
+        assert!(v.iter().eq(&[2, 3, 4]));
+
+        let v: Deque<i32, 3> = Deque::from_array([]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature="copy"))]
+    fn from_array_drop() {
+        droppable!();
+
+        {
+            let v: Deque<Droppable, 3> = Deque::from_array([Droppable::new(), Droppable::new()]);
+            assert_eq!(v.len(), 2);
+            assert_eq!(Droppable::count(), 2);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn spare_capacity() {
+        for offset in 0..4 {
+            let mut v: Deque<u8, 4> = Deque::new();
+            for _ in 0..offset {
+                v.push_back(0).unwrap();
+                v.pop_front().unwrap();
+            }
+            let (a, b) = v.spare_capacity_mut();
+            assert_eq!(a.len(), 4 - offset);
+            assert_eq!(b.len(), offset);
+            for (i, slot) in a.iter_mut().chain(b.iter_mut()).take(3).enumerate() {
+                slot.write(i as u8);
+            }
+            unsafe { v.commit(3) };
+            assert!(v.iter().eq(&[0, 1, 2]));
+
+            let (a, b) = v.spare_capacity_mut();
+            assert_eq!(a.len() + b.len(), 1);
+            a.iter_mut().chain(b.iter_mut()).for_each(|slot| {
+                slot.write(3);
+            });
+            unsafe { v.commit(1) };
+            assert!(v.is_full());
+            assert!(v.iter().eq(&[0, 1, 2, 3]));
+            assert_eq!(v.spare_capacity_mut().0.len(), 0);
+            unsafe { v.commit(0) };
+
+            v.pop_front();
+            v.pop_front();
+            let (a, b) = v.spare_capacity_mut();
+            assert_eq!(a.len() + b.len(), 2);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn commit_overflow() {
+        let mut v: Deque<u8, 4> = Deque::new();
+        v.extend([1, 2, 3]);
+        unsafe { v.commit(2) };
+    }
+
+    #[test]
+    fn wrap_power_of_two_and_not() {
+        fn check<const N: usize>() {
+            let mut v: Deque<usize, N> = Deque::new();
+            let mut model = std::collections::VecDeque::new();
+            for i in 0..4 * N + 3 {
+                if i % 3 == 2 {
+                    assert_eq!(v.pop_front(), model.pop_front());
+                } else if i % 2 == 0 {
+                    if v.push_back(i).is_ok() {
+                        model.push_back(i);
+                    }
+                } else if v.push_front(i).is_ok() {
+                    model.push_front(i);
+                }
+                assert!(v.iter().eq(model.iter()));
+                assert_eq!(v.get(model.len()), None);
+                for (j, x) in model.iter().enumerate() {
+                    assert_eq!(v[j], *x);
+                }
+            }
+        }
+
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<7>();
+        check::<8>();
+    }
+
+    #[test]
+    fn into_iter_double_ended() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(0).unwrap();
+        v.pop_front();
+        v.extend([1, 2, 3, 4]);
+
+        let mut iter = v.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.as_slices(), (&[1, 2, 3][..], &[4][..]));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next(), Some(1));
+        iter.as_mut_slices().0[0] = 7;
+        assert_eq!(format!("{:?}", iter), "IntoIter([7, 3])");
+        assert_eq!(iter.len(), 2);
+        assert!(iter.by_ref().rev().eq([3, 7]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let v: Deque<i32, 4> = Deque::from_array([1, 2, 3]);
+        assert_eq!(
+            v.iter().rev().copied().collect::<std::vec::Vec<_>>(),
+            [3, 2, 1]
+        );
+        assert_eq!(v.iter().len(), 3);
+    }
+
+    #[test]
+    fn resize() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(0).unwrap();
+        v.push_back(0).unwrap();
+        v.pop_front();
+        v.pop_front();
+        v.push_back(1).unwrap();
+
+        v.resize(4, 7).unwrap();
+        assert!(v.iter().eq(&[1, 7, 7, 7]));
+        assert!(v.is_full());
+        assert_eq!(v.resize(5, 0), Err(()));
+        assert!(v.iter().eq(&[1, 7, 7, 7]));
+
+        let mut n = 0;
+        v.resize_with(2, || unreachable!()).unwrap();
+        assert!(v.iter().eq(&[1, 7]));
+        v.resize_with(3, || {
+            n += 1;
+            n
+        })
+        .unwrap();
+        assert!(v.iter().eq(&[1, 7, 1]));
+        v.resize(0, 0).unwrap();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn resize_drop() {
+        droppable!();
+
+        let mut v: Deque<Droppable, 4> = Deque::new();
+        v.resize_with(3, Droppable::new).unwrap();
+        assert_eq!(Droppable::count(), 3);
+        v.resize_with(1, Droppable::new).unwrap();
+        assert_eq!(Droppable::count(), 1);
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn truncate() {
+        for offset in 0..4 {
+            for len in 0..=4 {
+                for keep in 0..=5 {
+                    let mut v: Deque<i32, 4> = Deque::new();
+                    for _ in 0..offset {
+                        v.push_back(0).unwrap();
+                        v.pop_front();
+                    }
+                    v.extend(0..len);
+                    let mut w = v.clone();
+
+                    v.truncate_back(keep as usize);
+                    assert!(v.iter().copied().eq(0..len.min(keep)));
+                    w.truncate_front(keep as usize);
+                    assert!(w.iter().copied().eq((len - keep).max(0)..len));
+
+                    // The free space is usable from both ends
+                    while v.push_back(-1).is_ok() {}
+                    assert!(v.is_full());
+                    while w.push_front(-1).is_ok() {}
+                    assert!(w.is_full());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn truncate_drop() {
+        droppable!();
+
+        let mut v: Deque<Droppable, 4> = Deque::new();
+        v.push_back(Droppable::new()).ok().unwrap();
+        v.pop_front().unwrap();
+        for _ in 0..4 {
+            v.push_back(Droppable::new()).ok().unwrap();
+        }
+        assert_eq!(Droppable::count(), 4);
+        v.truncate_front(3);
+        assert_eq!(Droppable::count(), 3);
+        v.truncate_back(1);
+        assert_eq!(Droppable::count(), 1);
+        v.truncate_back(0);
+        assert_eq!(Droppable::count(), 0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn view_parity() {
+        use super::DequeView;
+
+        fn fill(deque: &mut DequeView<i32>) {
+            deque.clear();
+            deque.extend_back_from_slice(&[3, 4]).unwrap();
+            deque.extend_front_from_slice(&[1, 2]).unwrap();
+            deque.insert(2, 9).unwrap();
+            deque.rotate_left(1);
+            assert_eq!(deque.remove(1), Some(9));
+            deque.make_contiguous();
+        }
+
+        let mut a: Deque<i32, 5> = Deque::new();
+        let mut b: Deque<i32, 8> = Deque::new();
+        fill(&mut a);
+        fill((&mut b).into());
+        assert_eq!(a, b);
+        assert_eq!(a.as_view(), b.as_view());
+        assert!(a.iter().eq(&[2, 3, 4, 1]));
+        assert_eq!(format!("{:?}", a.as_view()), "[2, 3, 4, 1]");
+        b.push_back(5).unwrap();
+        assert_ne!(a.as_view(), b.as_view());
+        assert_eq!(a.as_mut_view().drain(..).count(), 4);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn hash_ignores_layout() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut a: Deque<i32, 4> = Deque::new();
+        a.extend([1, 2, 3]);
+        let mut b: Deque<i32, 4> = Deque::new();
+        b.push_back(0).unwrap();
+        b.push_back(0).unwrap();
+        b.pop_front();
+        b.pop_front();
+        b.extend([1, 2, 3]);
+        assert_ne!(a.as_slices(), b.as_slices());
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn insert_remove() {
+        for offset in 0..5 {
+            for len in 0..5 {
+                for index in 0..=len {
+                    let mut v: Deque<i32, 5> = Deque::new();
+                    for _ in 0..offset {
+                        v.push_back(0).unwrap();
+                        v.pop_front().unwrap();
+                    }
+                    v.extend(0..len as i32);
+                    v.insert(index, -1).unwrap();
+                    let expected = (0..index as i32)
+                        .chain([-1])
+                        .chain(index as i32..len as i32);
+                    assert!(v.iter().copied().eq(expected));
+                    assert_eq!(v.len(), len + 1);
+                    assert_eq!(v.is_full(), len == 4);
+
+                    assert_eq!(v.remove(index), Some(-1));
+                    assert!(v.iter().copied().eq(0..len as i32));
+                    assert!(!v.is_full());
+                    assert_eq!(v.remove(len), None);
+                    v.push_back(9).unwrap();
+                    assert_eq!(v.back(), Some(&9));
+                    assert_eq!(v.front(), Some(if len == 0 { &9 } else { &0 }));
+                }
+            }
+        }
+
+        let mut v: Deque<i32, 2> = Deque::from_array([1, 2]);
+        assert_eq!(v.insert(1, 3), Err(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(1).unwrap();
+        let _ = v.insert(2, 0);
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        for offset in 0..4 {
+            let mut v: Deque<i32, 4> = Deque::new();
+            for _ in 0..offset {
+                v.push_back(0).unwrap();
+                v.pop_front().unwrap();
+            }
+            v.extend_back_from_slice(&[]).unwrap();
+            v.extend_front_from_slice(&[]).unwrap();
+            assert!(v.is_empty());
+            v.extend_back_from_slice(&[2, 3]).unwrap();
+            v.extend_front_from_slice(&[1]).unwrap();
+            assert!(v.extend_back_from_slice(&[4, 5]).is_err());
+            assert!(v.extend_front_from_slice(&[4, 5]).is_err());
+            assert!(v.iter().eq(&[1, 2, 3]));
+            v.extend_front_from_slice(&[0]).unwrap();
+            assert!(v.is_full());
+            assert!(v.iter().eq(&[0, 1, 2, 3]));
+            assert!(v.extend_back_from_slice(&[4]).is_err());
+            assert!(v.extend_front_from_slice(&[4]).is_err());
+
+            v.clear();
+            for _ in 0..offset {
+                v.push_back(0).unwrap();
+                v.pop_front().unwrap();
+            }
+            v.extend_back_from_slice(&[1, 2, 3, 4]).unwrap();
+            assert!(v.is_full());
+            assert!(v.iter().eq(&[1, 2, 3, 4]));
+            v.clear();
+            v.extend_front_from_slice(&[1, 2, 3, 4]).unwrap();
+            assert!(v.is_full());
+            assert!(v.iter().eq(&[1, 2, 3, 4]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2]);
+        v.drain(1..3);
+    }
+
+    #[test]
+    #[cfg(not(feature="copy"))]
+    fn drain_drop() {
+        droppable!();
+
+        {
+            let mut v: Deque<Droppable, 4> = Deque::new();
+            v.push_front(Droppable::new()).ok().unwrap();
+            for _ in 0..3 {
+                v.push_back(Droppable::new()).ok().unwrap();
+            }
+            let mut drain = v.drain(1..3);
+            drain.next().unwrap();
+            assert_eq!(Droppable::count(), 3);
+            core::mem::drop(drain);
+            assert_eq!(Droppable::count(), 2);
+            assert_eq!(v.len(), 2);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
     #[test]
     fn full() {
         let mut v: Deque<i32, 4> = Deque::new();
@@ -1360,6 +3406,28 @@ mod tests {
         assert_eq!(q.as_slices(), ([5, 6, 7, 8].as_slice(), [].as_slice()));
     }
 
+    #[test]
+    fn make_contiguous_all_layouts() {
+        for offset in 0..8 {
+            for len in 0..=8 {
+                let mut q: Deque<u8, 8> = Deque::new();
+                for _ in 0..offset {
+                    q.push_back(0).unwrap();
+                    q.pop_front().unwrap();
+                }
+                q.extend(0..len);
+                assert!(q.make_contiguous().iter().copied().eq(0..len));
+                assert_eq!(q.as_slices().1, &[]);
+                assert!(q.as_slices().0.iter().copied().eq(0..len));
+                assert_eq!(q.is_full(), len == 8);
+                if len < 8 {
+                    q.push_back(len).unwrap();
+                    assert!(q.iter().copied().eq(0..=len));
+                }
+            }
+        }
+    }
+
     #[test]
     fn get() {
         let mut q: Deque<i32, 4> = Deque::new();
@@ -1387,6 +3455,27 @@ mod tests {
         assert_eq!(q.get(3), Some(&4));
     }
 
+    #[test]
+    fn index() {
+        let mut q: Deque<i32, 4> = Deque::new();
+        q.extend([0, 1, 2]);
+        q.pop_front().unwrap();
+        q.extend([3, 4]);
+        for i in 0..4 {
+            assert_eq!(q[i], i as i32 + 1);
+            q[i] *= 10;
+        }
+        assert!(q.iter().eq(&[10, 20, 30, 40]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut q: Deque<i32, 4> = Deque::new();
+        q.push_back(0).unwrap();
+        let _ = q[1];
+    }
+
     #[test]
     fn get_mut() {
         let mut q: Deque<i32, 4> = Deque::new();
@@ -0,0 +1,87 @@
+//! `embedded-io` implementations for heapless types
+
+use embedded_io::{ErrorKind, ErrorType, Write, WriteReady};
+
+use crate::{storage::Storage, vec::VecInner};
+
+impl<S: Storage> ErrorType for VecInner<u8, S> {
+    type Error = ErrorKind;
+}
+
+/// Appends as many bytes as fit in the remaining capacity of the vector.
+///
+/// Once the vector is full, `write` fails with [`ErrorKind::WriteZero`].
+impl<S: Storage> Write for VecInner<u8, S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.storage_capacity() - self.len());
+        if n == 0 && !buf.is_empty() {
+            return Err(ErrorKind::WriteZero);
+        }
+        // NOTE(unwrap) `n` bytes fit in the vector
+        self.extend_from_copy_slice(&buf[..n]).unwrap();
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Storage> WriteReady for VecInner<u8, S> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_full())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_io::{BufRead, Error, ErrorKind, Read, ReadExactError, Write, WriteReady};
+
+    use crate::{Vec, VecView};
+
+    #[test]
+    fn write_full() {
+        let mut v = Vec::<u8, 4>::from_slice(b"abc").unwrap();
+        assert_eq!(v.write_ready(), Ok(true));
+        // A partial write succeeds, and only a write of nothing fails
+        assert_eq!(v.write(b"de"), Ok(1));
+        assert_eq!(v.write_ready(), Ok(false));
+        assert_eq!(v.write(b""), Ok(0));
+        let err = v.write(b"e").unwrap_err();
+        assert_eq!(err, ErrorKind::WriteZero);
+        assert_eq!(Error::kind(&err), ErrorKind::WriteZero);
+        assert_eq!(v, b"abcd");
+    }
+
+    #[test]
+    fn write_fmt_overflow() {
+        let mut v = Vec::<u8, 4>::new();
+        let view: &mut VecView<u8> = &mut v;
+        assert!(write!(view, "{}", 123456).is_err());
+        assert_eq!(v, b"1234");
+    }
+
+    #[test]
+    fn reader() {
+        let v = Vec::<u8, 8>::from_slice(b"hello").unwrap();
+        let mut reader = v.reader();
+        assert_eq!(reader.fill_buf().unwrap(), b"hello");
+        reader.consume(1);
+        assert_eq!(reader.position(), 1);
+
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf), Ok(4));
+        assert_eq!(&buf[..4], b"ello");
+        // EOF
+        assert_eq!(reader.read(&mut buf), Ok(0));
+        reader.consume(1);
+        assert_eq!(reader.position(), 5);
+        assert_eq!(
+            v.reader().read_exact(&mut buf),
+            Err(ReadExactError::UnexpectedEof)
+        );
+
+        // The vector is left untouched
+        assert_eq!(v, b"hello");
+    }
+}
@@ -0,0 +1,593 @@
+//! Integer formatting for heapless types that doesn't go through `core::fmt`
+
+use crate::{storage::Storage, string::StringInner, vec::VecInner};
+
+/// Enough room for `i128::MIN`.
+const MAX_LEN: usize = 40;
+
+/// The decimal representations of all the numbers from `00` to `99`.
+const DIGITS_LUT: &[u8; 200] = b"\
+      0001020304050607080910111213141516171819\
+      2021222324252627282930313233343536373839\
+      4041424344454647484950515253545556575859\
+      6061626364656667686970717273747576777879\
+      8081828384858687888990919293949596979899";
+
+macro_rules! impl_format {
+    ($($name:ident: $uint:ty,)*) => {
+        $(
+            /// Writes the decimal representation of `n` at the end of `buf`, two digits at a time,
+            /// and returns the index of the first digit.
+            fn $name(mut n: $uint, buf: &mut [u8; MAX_LEN]) -> usize {
+                let mut cur = MAX_LEN;
+                while n >= 100 {
+                    let d = (n % 100) as usize * 2;
+                    n /= 100;
+                    cur -= 2;
+                    buf[cur..cur + 2].copy_from_slice(&DIGITS_LUT[d..d + 2]);
+                }
+                if n >= 10 {
+                    let d = n as usize * 2;
+                    cur -= 2;
+                    buf[cur..cur + 2].copy_from_slice(&DIGITS_LUT[d..d + 2]);
+                } else {
+                    cur -= 1;
+                    buf[cur] = b'0' + n as u8;
+                }
+                cur
+            }
+        )*
+    };
+}
+
+impl_format!(
+    format_u32: u32,
+    format_u64: u64,
+    format_usize: usize,
+    format_u128: u128,
+);
+
+macro_rules! impl_write_unsigned {
+    ($($write:ident: $int:ty => $format:ident($uint:ty),)*) => {
+        impl<S: Storage> VecInner<u8, S> {
+            $(
+                #[doc = concat!("Appends the decimal representation of a `", stringify!($int), "` to the vector.")]
+                ///
+                /// Unlike `write!`, this doesn't go through the `core::fmt` machinery.
+                ///
+                /// Returns `Err(())` without modifying the vector if it cannot hold the
+                /// representation.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use heapless::Vec;
+                ///
+                /// let mut v: Vec<u8, 4> = Vec::new();
+                #[doc = concat!("v.", stringify!($write), "(42).unwrap();")]
+                /// assert_eq!(v, *b"42");
+                #[doc = concat!("assert!(v.", stringify!($write), "(123).is_err());")]
+                /// ```
+                #[allow(clippy::result_unit_err)]
+                pub fn $write(&mut self, n: $int) -> Result<(), ()> {
+                    let mut buf = [0; MAX_LEN];
+                    let start = $format(n as $uint, &mut buf);
+                    self.extend_from_copy_slice(&buf[start..])
+                }
+            )*
+        }
+
+        impl<S: Storage> StringInner<S> {
+            $(
+                #[doc = concat!("Appends the decimal representation of a `", stringify!($int), "` to the string.")]
+                ///
+                /// Unlike `write!`, this doesn't go through the `core::fmt` machinery.
+                ///
+                /// Returns `Err(())` without modifying the string if it cannot hold the
+                /// representation.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use heapless::String;
+                ///
+                /// let mut s: String<8> = String::try_from("n=").unwrap();
+                #[doc = concat!("s.", stringify!($write), "(42).unwrap();")]
+                /// assert_eq!(s, "n=42");
+                /// ```
+                #[allow(clippy::result_unit_err)]
+                pub fn $write(&mut self, n: $int) -> Result<(), ()> {
+                    // NOTE(unsafe) only ASCII digits are written
+                    unsafe { self.as_mut_vec() }.$write(n)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! impl_write_signed {
+    ($($write:ident: $int:ty => $format:ident($uint:ty),)*) => {
+        impl<S: Storage> VecInner<u8, S> {
+            $(
+                #[doc = concat!("Appends the decimal representation of an `", stringify!($int), "` to the vector.")]
+                ///
+                /// Unlike `write!`, this doesn't go through the `core::fmt` machinery.
+                ///
+                /// Returns `Err(())` without modifying the vector if it cannot hold the
+                /// representation.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use heapless::Vec;
+                ///
+                /// let mut v: Vec<u8, 4> = Vec::new();
+                #[doc = concat!("v.", stringify!($write), "(-42).unwrap();")]
+                /// assert_eq!(v, *b"-42");
+                #[doc = concat!("assert!(v.", stringify!($write), "(-1).is_err());")]
+                /// ```
+                #[allow(clippy::result_unit_err)]
+                pub fn $write(&mut self, n: $int) -> Result<(), ()> {
+                    let mut buf = [0; MAX_LEN];
+                    let mut start = $format(n.unsigned_abs() as $uint, &mut buf);
+                    if n < 0 {
+                        start -= 1;
+                        buf[start] = b'-';
+                    }
+                    self.extend_from_copy_slice(&buf[start..])
+                }
+            )*
+        }
+
+        impl<S: Storage> StringInner<S> {
+            $(
+                #[doc = concat!("Appends the decimal representation of an `", stringify!($int), "` to the string.")]
+                ///
+                /// Unlike `write!`, this doesn't go through the `core::fmt` machinery.
+                ///
+                /// Returns `Err(())` without modifying the string if it cannot hold the
+                /// representation.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use heapless::String;
+                ///
+                /// let mut s: String<8> = String::try_from("n=").unwrap();
+                #[doc = concat!("s.", stringify!($write), "(-42).unwrap();")]
+                /// assert_eq!(s, "n=-42");
+                /// ```
+                #[allow(clippy::result_unit_err)]
+                pub fn $write(&mut self, n: $int) -> Result<(), ()> {
+                    // NOTE(unsafe) only ASCII digits and `-` are written
+                    unsafe { self.as_mut_vec() }.$write(n)
+                }
+            )*
+        }
+    };
+}
+
+impl_write_unsigned!(
+    write_u8: u8 => format_u32(u32),
+    write_u16: u16 => format_u32(u32),
+    write_u32: u32 => format_u32(u32),
+    write_u64: u64 => format_u64(u64),
+    write_u128: u128 => format_u128(u128),
+    write_usize: usize => format_usize(usize),
+);
+
+impl_write_signed!(
+    write_i8: i8 => format_u32(u32),
+    write_i16: i16 => format_u32(u32),
+    write_i32: i32 => format_u32(u32),
+    write_i64: i64 => format_u64(u64),
+    write_i128: i128 => format_u128(u128),
+    write_isize: isize => format_usize(usize),
+);
+
+/// Lower case hexadecimal digits.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Upper case hexadecimal digits.
+const UPPER_HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Unsigned integer types that can be written in base `2^bits`.
+pub(crate) trait Radix: Copy {
+    /// Appends `self` in base `2^bits`, zero-padded to at least `width` digits, to `vec`.
+    fn write_radix<S: Storage>(
+        self,
+        vec: &mut VecInner<u8, S>,
+        bits: u32,
+        digits: &[u8; 16],
+        width: usize,
+    ) -> Result<(), ()>;
+}
+
+macro_rules! impl_radix {
+    ($($uint:ty),*) => {
+        $(
+            impl Radix for $uint {
+                fn write_radix<S: Storage>(
+                    mut self,
+                    vec: &mut VecInner<u8, S>,
+                    bits: u32,
+                    digits: &[u8; 16],
+                    width: usize,
+                ) -> Result<(), ()> {
+                    // Enough room for the binary representation
+                    let mut buf = [0; <$uint>::BITS as usize];
+                    let mut cur = buf.len();
+                    let mask = (1 << bits) - 1;
+                    loop {
+                        cur -= 1;
+                        buf[cur] = digits[(self & mask) as usize];
+                        self >>= bits;
+                        if self == 0 {
+                            break;
+                        }
+                    }
+                    vec.write_padded(&buf[cur..], width)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix!(u32, u64, u128);
+
+macro_rules! impl_radix_via {
+    ($($int:ty => $uint:ty),*) => {
+        $(
+            impl Radix for $int {
+                #[inline]
+                fn write_radix<S: Storage>(
+                    self,
+                    vec: &mut VecInner<u8, S>,
+                    bits: u32,
+                    digits: &[u8; 16],
+                    width: usize,
+                ) -> Result<(), ()> {
+                    (self as $uint).write_radix(vec, bits, digits, width)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_via!(u8 => u32, u16 => u32);
+#[cfg(target_pointer_width = "64")]
+impl_radix_via!(usize => u64);
+#[cfg(not(target_pointer_width = "64"))]
+impl_radix_via!(usize => u32);
+
+impl<S: Storage> VecInner<u8, S> {
+    /// Appends `digits`, zero-padded to at least `width` digits.
+    fn write_padded(&mut self, digits: &[u8], width: usize) -> Result<(), ()> {
+        let pad = width.saturating_sub(digits.len());
+        if pad + digits.len() > self.storage_capacity() - self.len() {
+            return Err(());
+        }
+        for _ in 0..pad {
+            // NOTE(unsafe) there's room for the padding and the digits
+            unsafe { self.push_unchecked(b'0') }
+        }
+        self.extend_from_copy_slice(digits)
+    }
+
+    /// Appends the lower case hexadecimal representation of `n`, zero-padded to at least `width`
+    /// digits, to the vector.
+    ///
+    /// No `0x` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 16> = Vec::new();
+    /// v.write_hex(0xbeefu16, 0).unwrap();
+    /// v.write_hex(0xau8, 2).unwrap();
+    /// assert_eq!(v, *b"beef0a");
+    ///
+    /// let address: usize = 0x2000_0400;
+    /// v.clear();
+    /// v.write_hex(address, 8).unwrap();
+    /// assert_eq!(v, *b"20000400");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_hex<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        n.write_radix(self, 4, HEX_DIGITS, width)
+    }
+
+    /// Appends the upper case hexadecimal representation of `n`, zero-padded to at least `width`
+    /// digits, to the vector.
+    ///
+    /// No `0x` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::new();
+    /// v.write_upper_hex(0x7ffu32, 8).unwrap();
+    /// assert_eq!(v, *b"000007FF");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_upper_hex<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        n.write_radix(self, 4, UPPER_HEX_DIGITS, width)
+    }
+
+    /// Appends the octal representation of `n`, zero-padded to at least `width` digits, to the
+    /// vector.
+    ///
+    /// No `0o` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::new();
+    /// v.write_oct(0o755u16, 4).unwrap();
+    /// assert_eq!(v, *b"0755");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_oct<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        // Octal digits don't line up with 4 bits, so use a digit per 3 bits
+        n.write_radix(self, 3, HEX_DIGITS, width)
+    }
+
+    /// Appends the binary representation of `n`, zero-padded to at least `width` digits, to the
+    /// vector.
+    ///
+    /// No `0b` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::new();
+    /// v.write_bin(0b101u8, 8).unwrap();
+    /// assert_eq!(v, *b"00000101");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_bin<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        n.write_radix(self, 1, HEX_DIGITS, width)
+    }
+}
+
+impl<S: Storage> StringInner<S> {
+    /// Appends the lower case hexadecimal representation of `n`, zero-padded to at least `width`
+    /// digits, to the string.
+    ///
+    /// No `0x` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::try_from("0x").unwrap();
+    /// s.write_hex(0x1fu8, 4).unwrap();
+    /// assert_eq!(s, "0x001f");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_hex<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        // NOTE(unsafe) only ASCII digits are written
+        unsafe { self.as_mut_vec() }.write_hex(n, width)
+    }
+
+    /// Appends the upper case hexadecimal representation of `n`, zero-padded to at least `width`
+    /// digits, to the string.
+    ///
+    /// No `0x` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("ID=").unwrap();
+    /// s.write_upper_hex(0x18daf110u32, 8).unwrap();
+    /// assert_eq!(s, "ID=18DAF110");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_upper_hex<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        // NOTE(unsafe) only ASCII digits are written
+        unsafe { self.as_mut_vec() }.write_upper_hex(n, width)
+    }
+
+    /// Appends the octal representation of `n`, zero-padded to at least `width` digits, to the
+    /// string.
+    ///
+    /// No `0o` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::new();
+    /// s.write_oct(8u8, 0).unwrap();
+    /// assert_eq!(s, "10");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_oct<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        // NOTE(unsafe) only ASCII digits are written
+        unsafe { self.as_mut_vec() }.write_oct(n, width)
+    }
+
+    /// Appends the binary representation of `n`, zero-padded to at least `width` digits, to the
+    /// string.
+    ///
+    /// No `0b` prefix is written. Unlike `write!`, this doesn't go through the `core::fmt`
+    /// machinery.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("0b").unwrap();
+    /// s.write_bin(0x81u8, 8).unwrap();
+    /// assert_eq!(s, "0b10000001");
+    /// ```
+    #[allow(clippy::result_unit_err, private_bounds)]
+    pub fn write_bin<I: Radix>(&mut self, n: I, width: usize) -> Result<(), ()> {
+        // NOTE(unsafe) only ASCII digits are written
+        unsafe { self.as_mut_vec() }.write_bin(n, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{format, string::ToString};
+
+    use crate::{String, Vec};
+
+    #[test]
+    fn unsigned() {
+        for n in [0, 1, 9, 10, 99, 100, 101, 999, 1000, 12345, u32::MAX] {
+            let mut s: String<40> = String::new();
+            s.write_u32(n).unwrap();
+            assert_eq!(s, n.to_string().as_str());
+        }
+
+        let mut s: String<64> = String::new();
+        s.write_u8(u8::MAX).unwrap();
+        s.write_u16(u16::MAX).unwrap();
+        s.write_u64(u64::MAX).unwrap();
+        s.write_usize(usize::MAX).unwrap();
+        assert_eq!(
+            s,
+            format!("{}{}{}{}", u8::MAX, u16::MAX, u64::MAX, usize::MAX).as_str()
+        );
+
+        let mut s: String<40> = String::new();
+        s.write_u128(u128::MAX).unwrap();
+        assert_eq!(s, u128::MAX.to_string().as_str());
+    }
+
+    #[test]
+    fn signed() {
+        for n in [0, 1, -1, 10, -10, -99, -100, i64::MAX, i64::MIN] {
+            let mut s: String<40> = String::new();
+            s.write_i64(n).unwrap();
+            assert_eq!(s, n.to_string().as_str());
+        }
+
+        let mut s: String<80> = String::new();
+        s.write_i8(i8::MIN).unwrap();
+        s.write_i16(i16::MIN).unwrap();
+        s.write_i32(i32::MIN).unwrap();
+        s.write_isize(isize::MIN).unwrap();
+        assert_eq!(
+            s,
+            format!("{}{}{}{}", i8::MIN, i16::MIN, i32::MIN, isize::MIN).as_str()
+        );
+
+        let mut s: String<40> = String::new();
+        s.write_i128(i128::MIN).unwrap();
+        assert_eq!(s, i128::MIN.to_string().as_str());
+    }
+
+    #[test]
+    fn overflow() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(b"x").unwrap();
+        assert!(v.write_u32(1000).is_err());
+        assert!(v.write_i32(-100).is_err());
+        assert_eq!(v, *b"x");
+        v.write_i8(-10).unwrap();
+        assert_eq!(v, *b"x-10");
+    }
+
+    #[test]
+    fn radix() {
+        for n in [0u64, 1, 7, 8, 15, 16, 255, 0xdead_beef, u64::MAX] {
+            let mut s: String<256> = String::new();
+            s.write_hex(n, 0).unwrap();
+            s.push(' ').unwrap();
+            s.write_upper_hex(n, 0).unwrap();
+            s.push(' ').unwrap();
+            s.write_oct(n, 0).unwrap();
+            s.push(' ').unwrap();
+            s.write_bin(n, 0).unwrap();
+            assert_eq!(s, format!("{n:x} {n:X} {n:o} {n:b}").as_str());
+        }
+    }
+
+    #[test]
+    fn radix_wide() {
+        let mut s: String<256> = String::new();
+        s.write_hex(usize::MAX, 0).unwrap();
+        s.push(' ').unwrap();
+        s.write_upper_hex(u128::MAX, 0).unwrap();
+        s.push(' ').unwrap();
+        s.write_oct(u128::MAX, 0).unwrap();
+        s.push(' ').unwrap();
+        s.write_bin(u128::MAX, 0).unwrap();
+        assert_eq!(
+            s,
+            format!(
+                "{:x} {:X} {:o} {:b}",
+                usize::MAX,
+                u128::MAX,
+                u128::MAX,
+                u128::MAX
+            )
+            .as_str()
+        );
+
+        let mut v: Vec<u8, 32> = Vec::new();
+        let address: usize = 0x2000_0400;
+        let width = 2 * core::mem::size_of::<usize>();
+        v.write_hex(address, width).unwrap();
+        assert_eq!(v, format!("{address:0width$x}").as_bytes());
+    }
+
+    #[test]
+    fn radix_width() {
+        for width in [0, 1, 2, 5, 20, 70] {
+            let mut s: String<256> = String::new();
+            s.write_hex(0x1au8, width).unwrap();
+            s.push(' ').unwrap();
+            s.write_bin(0b101u8, width).unwrap();
+            assert_eq!(s, format!("{:0width$x} {:0width$b}", 0x1a, 0b101).as_str());
+        }
+    }
+
+    #[test]
+    fn radix_overflow() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(b"x").unwrap();
+        assert!(v.write_hex(0x1234u16, 0).is_err());
+        assert!(v.write_bin(1u8, 4).is_err());
+        assert_eq!(v, *b"x");
+        v.write_oct(7u8, 3).unwrap();
+        assert_eq!(v, *b"x007");
+    }
+}
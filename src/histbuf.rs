@@ -29,14 +29,18 @@
 //! // Now we can prepare an average of all values, which comes out to 4.
 //! let avg = buf.as_slice().iter().sum::<usize>() / buf.len();
 //! assert_eq!(avg, 4);
+//!
+//! // Or use the built-in statistics.
+//! assert_eq!(buf.mean(), Some(4));
 //! ```
 
 use core::borrow::Borrow;
 use core::borrow::BorrowMut;
 use core::fmt;
+use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::mem::MaybeUninit;
-use core::ops::Deref;
+use core::ops::{Add, Deref, Div, Mul, Sub};
 use core::ptr;
 use core::slice;
 
@@ -148,6 +152,46 @@ impl<T, const N: usize> HistoryBuffer<T, N> {
             filled: false,
         }
     }
+
+    /// Get a reference to the `HistoryBuffer`, erasing the `N` const-generic.
+    ///
+    /// ```rust
+    /// # use heapless::histbuf::{HistoryBuffer, HistoryBufferView};
+    /// let buffer: HistoryBuffer<u8, 10> = HistoryBuffer::new();
+    /// let view: &HistoryBufferView<u8> = buffer.as_view();
+    /// ```
+    ///
+    /// It is often preferable to do the same through type coerction, since `HistoryBuffer<T, N>` implements `Unsize<HistoryBufferView<T>>`:
+    ///
+    /// ```rust
+    /// # use heapless::histbuf::{HistoryBuffer, HistoryBufferView};
+    /// let buffer: HistoryBuffer<u8, 10> = HistoryBuffer::new();
+    /// let view: &HistoryBufferView<u8> = &buffer;
+    /// ```
+    #[inline]
+    pub const fn as_view(&self) -> &HistoryBufferView<T> {
+        self
+    }
+
+    /// Get a mutable reference to the `HistoryBuffer`, erasing the `N` const-generic.
+    ///
+    /// ```rust
+    /// # use heapless::histbuf::{HistoryBuffer, HistoryBufferView};
+    /// let mut buffer: HistoryBuffer<u8, 10> = HistoryBuffer::new();
+    /// let view: &mut HistoryBufferView<u8> = buffer.as_mut_view();
+    /// ```
+    ///
+    /// It is often preferable to do the same through type coerction, since `HistoryBuffer<T, N>` implements `Unsize<HistoryBufferView<T>>`:
+    ///
+    /// ```rust
+    /// # use heapless::histbuf::{HistoryBuffer, HistoryBufferView};
+    /// let mut buffer: HistoryBuffer<u8, 10> = HistoryBuffer::new();
+    /// let view: &mut HistoryBufferView<u8> = &mut buffer;
+    /// ```
+    #[inline]
+    pub fn as_mut_view(&mut self) -> &mut HistoryBufferView<T> {
+        self
+    }
 }
 
 impl<T, const N: usize> HistoryBuffer<T, N>
@@ -264,8 +308,10 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
 
     /// Clones and writes all elements in a slice to the buffer.
     ///
-    /// If the slice is longer than the buffer, only the last `self.len()`
+    /// If the slice is longer than the buffer, only the last `self.capacity()`
     /// elements will actually be stored.
+    ///
+    /// For `Copy` types, [`write_slice`](Self::write_slice) is faster.
     pub fn extend_from_slice(&mut self, other: &[T])
     where
         T: Clone,
@@ -275,6 +321,52 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
         }
     }
 
+    /// Copies and writes all elements in a slice to the buffer, overwriting the oldest values.
+    ///
+    /// This is equivalent to [`extend_from_slice`](Self::extend_from_slice), but copies the
+    /// elements with at most two `memcpy`s. If the slice is longer than the buffer, only the last
+    /// `self.capacity()` elements are copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u16, 6> = HistoryBuffer::new();
+    /// buffer.write_slice(&[1, 2, 3, 4]);
+    /// buffer.write_slice(&[5, 6, 7, 8]);
+    /// assert!(buffer.oldest_ordered().eq(&[3, 4, 5, 6, 7, 8]));
+    /// ```
+    pub fn write_slice(&mut self, other: &[T])
+    where
+        T: Copy,
+    {
+        let cap = self.capacity();
+        if other.is_empty() || cap == 0 {
+            return;
+        }
+
+        // Skip the elements that would be overwritten by the end of the slice, but keep the
+        // write position where writing them one by one would have left it
+        let skipped = other.len().saturating_sub(cap);
+        let start = (self.write_at + skipped) % cap;
+        let other = &other[skipped..];
+
+        let (head, tail) = other.split_at(other.len().min(cap - start));
+        let data = self.data.borrow_mut().as_mut_ptr() as *mut T;
+        // NOTE(unsafe) `head` fits after `start` and `tail` fits before it. `T: Copy` so the
+        // overwritten elements don't need to be dropped
+        unsafe {
+            ptr::copy_nonoverlapping(head.as_ptr(), data.add(start), head.len());
+            ptr::copy_nonoverlapping(tail.as_ptr(), data, tail.len());
+        }
+
+        if self.write_at + skipped + other.len() >= cap {
+            self.filled = true;
+        }
+        self.write_at = (start + other.len()) % cap;
+    }
+
     /// Returns a reference to the most recently written value.
     ///
     /// # Examples
@@ -292,6 +384,26 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
             .map(|i| unsafe { &*self.data.borrow()[i].as_ptr() })
     }
 
+    /// Returns a mutable reference to the most recently written value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new();
+    /// x.write(4);
+    /// x.write(10);
+    /// if let Some(recent) = x.recent_mut() {
+    ///     *recent *= 2;
+    /// }
+    /// assert_eq!(x.recent(), Some(&20));
+    /// ```
+    pub fn recent_mut(&mut self) -> Option<&mut T> {
+        self.recent_index()
+            .map(|i| unsafe { &mut *self.data.borrow_mut()[i].as_mut_ptr() })
+    }
+
     /// Returns index of the most recently written value in the underlying slice.
     ///
     /// # Examples
@@ -333,6 +445,26 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
             .map(|i| unsafe { &*self.data.borrow()[i].as_ptr() })
     }
 
+    /// Returns a mutable reference to the oldest value in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new();
+    /// x.write(4);
+    /// x.write(10);
+    /// if let Some(oldest) = x.oldest_mut() {
+    ///     *oldest = 5;
+    /// }
+    /// assert_eq!(x.oldest(), Some(&5));
+    /// ```
+    pub fn oldest_mut(&mut self) -> Option<&mut T> {
+        self.oldest_index()
+            .map(|i| unsafe { &mut *self.data.borrow_mut()[i].as_mut_ptr() })
+    }
+
     /// Returns index of the oldest value in the underlying slice.
     ///
     /// # Examples
@@ -363,6 +495,10 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
 
     /// Returns a pair of slices which contain, in order, the contents of the buffer.
     ///
+    /// The first slice holds the oldest elements and the second one the newest, so the whole
+    /// window can be processed with two slice operations instead of an element iterator. See also
+    /// [`as_mut_slices`](Self::as_mut_slices).
+    ///
     /// # Examples
     ///
     /// ```
@@ -383,6 +519,34 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
         }
     }
 
+    /// Returns a pair of mutable slices which contain, in order, the contents of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+    /// buffer.extend([0, 0, 0]);
+    /// buffer.extend([1, 2, 3, 4, 5, 6]);
+    /// let (old, _new) = buffer.as_mut_slices();
+    /// old[0] = 7;
+    /// assert_eq!(buffer.as_slices(), (&[7, 2, 3][..], &[4, 5, 6][..]));
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+        let buffer = unsafe {
+            slice::from_raw_parts_mut(self.data.borrow_mut().as_mut_ptr() as *mut T, len)
+        };
+
+        if !self.filled {
+            (buffer, &mut [])
+        } else {
+            let (new, old) = buffer.split_at_mut(self.write_at);
+            (old, new)
+        }
+    }
+
     /// Returns double ended iterator for iterating over the buffer from
     /// the oldest to the newest and back.
     ///
@@ -398,6 +562,18 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
     ///     assert_eq!(x, y)
     /// }
     /// ```
+    ///
+    /// The buffer can be decimated with [`step_by`](Iterator::step_by), which keeps every
+    /// `n`-th element without collecting them:
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+    /// buffer.extend([0, 0, 0, 1, 2, 3, 4, 5, 6]);
+    /// assert!(buffer.oldest_ordered().step_by(2).eq(&[1, 3, 5]));
+    /// assert!(buffer.oldest_ordered().step_by(4).rev().eq(&[5, 1]));
+    /// ```
     pub fn oldest_ordered(&self) -> OldestOrderedInner<'_, T, S> {
         let (old, new) = self.as_slices();
         OldestOrderedInner {
@@ -405,8 +581,299 @@ impl<T, S: Storage> HistoryBufferInner<T, S> {
             inner: old.iter().chain(new),
         }
     }
+
+    /// Returns double ended iterator over the `n` most recent elements of the buffer, from the
+    /// oldest to the newest.
+    ///
+    /// If the buffer holds less than `n` elements, all of them are returned. Use
+    /// [`rev`](Iterator::rev) to iterate from the newest to the oldest instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+    /// buffer.extend([0, 0, 0, 1, 2, 3, 4, 5, 6]);
+    /// assert!(buffer.recent_n(3).eq(&[4, 5, 6]));
+    /// assert!(buffer.recent_n(2).rev().eq(&[6, 5]));
+    /// assert_eq!(buffer.recent_n(10).len(), 6);
+    /// ```
+    pub fn recent_n(&self, n: usize) -> OldestOrderedView<'_, T> {
+        let len = self.len();
+        self.ordered_range(len.saturating_sub(n), len)
+    }
+
+    /// Returns an iterator over the elements between the chronological indices `start` and
+    /// `end`.
+    fn ordered_range(&self, start: usize, end: usize) -> OldestOrderedView<'_, T> {
+        let (old, new) = self.as_slices();
+        let split = old.len();
+        let old = &old[start.min(split)..end.min(split)];
+        let new = &new[start.max(split) - split..end.max(split) - split];
+        OldestOrderedView {
+            phantom: PhantomData,
+            inner: old.iter().chain(new),
+        }
+    }
+
+    /// Returns double ended iterator for mutably iterating over the buffer from
+    /// the oldest to the newest and back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<i16, 4> = HistoryBuffer::new();
+    /// buffer.extend([10, 11, 12, 13, 14, 15]);
+    ///
+    /// // Apply a calibration offset to the samples in the window
+    /// for (i, sample) in buffer.oldest_ordered_mut().enumerate() {
+    ///     *sample -= 10 + i as i16;
+    /// }
+    /// assert!(buffer.oldest_ordered().eq(&[2, 2, 2, 2]));
+    /// ```
+    pub fn oldest_ordered_mut(&mut self) -> OldestOrderedMut<'_, T> {
+        let (old, new) = self.as_mut_slices();
+        OldestOrderedMut {
+            inner: old.iter_mut().chain(new),
+        }
+    }
 }
 
+/// Statistics over the elements of the buffer.
+///
+/// These only consider the elements that have been written, and ignore their order.
+impl<T, S: Storage> HistoryBufferInner<T, S> {
+    /// Returns the smallest element of the buffer, or `None` if it is empty.
+    ///
+    /// If several elements are equally minimum, the oldest one, in the order they were written,
+    /// is returned. Elements that can't be compared (like `NaN`) are skipped unless they are the
+    /// oldest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// assert_eq!(buffer.min(), None);
+    /// buffer.extend([1, 5, 3, 4, 2]);
+    /// assert_eq!(buffer.min(), Some(&2));
+    /// ```
+    pub fn min(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.oldest_ordered()
+            .reduce(|min, x| if x < min { x } else { min })
+    }
+
+    /// Returns the largest element of the buffer, or `None` if it is empty.
+    ///
+    /// If several elements are equally maximum, the oldest one, in the order they were written,
+    /// is returned. Elements that can't be compared (like `NaN`) are skipped unless they are the
+    /// oldest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+    /// assert_eq!(buffer.max(), None);
+    /// buffer.extend([9.0, 0.5, 3.0, -4.0, 2.0]);
+    /// assert_eq!(buffer.max(), Some(&3.0));
+    /// ```
+    pub fn max(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.oldest_ordered()
+            .reduce(|max, x| if x > max { x } else { max })
+    }
+
+    /// Returns the sum of the elements of the buffer.
+    ///
+    /// The sum is computed in the wider [`Numeric::Accumulator`] type, so that it doesn't overflow
+    /// for the elements of a buffer of 8, 16 or 32-bit integers. An empty buffer sums to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// assert_eq!(buffer.sum(), 0);
+    /// buffer.extend([1, 200, 200, 200, 200]);
+    /// assert_eq!(buffer.sum(), 800u32);
+    /// ```
+    pub fn sum(&self) -> T::Accumulator
+    where
+        T: Numeric,
+    {
+        let zero = <T::Accumulator as Accumulate<T>>::ZERO;
+        self.as_slice()
+            .iter()
+            .fold(zero, |sum, &x| sum + T::Accumulator::widen(x))
+    }
+
+    /// Returns the arithmetic mean of the elements of the buffer, or `None` if it is empty.
+    ///
+    /// The mean is computed from the [`sum`](Self::sum), so it is rounded towards zero for
+    /// integer types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+    /// assert_eq!(buffer.mean(), None);
+    /// buffer.extend([100.0, 1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(buffer.mean(), Some(2.5));
+    /// ```
+    pub fn mean(&self) -> Option<T>
+    where
+        T: Numeric,
+    {
+        self.mean_accumulator().map(Accumulate::narrow)
+    }
+
+    fn mean_accumulator(&self) -> Option<T::Accumulator>
+    where
+        T: Numeric,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.sum() / T::Accumulator::from_usize(self.len()))
+    }
+
+    /// Returns the population variance of the elements of the buffer, or `None` if it is empty.
+    ///
+    /// The variance is computed in the wider [`Numeric::Accumulator`] type from the
+    /// [`mean`](Self::mean), so it is rounded towards zero for integer types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u16, 4> = HistoryBuffer::new();
+    /// assert_eq!(buffer.variance(), None);
+    /// buffer.extend([100, 2, 4, 4, 6]);
+    /// assert_eq!(buffer.mean(), Some(4));
+    /// assert_eq!(buffer.variance(), Some(2));
+    /// ```
+    pub fn variance(&self) -> Option<T::Accumulator>
+    where
+        T: Numeric,
+    {
+        let mean = self.mean_accumulator()?;
+        let zero = <T::Accumulator as Accumulate<T>>::ZERO;
+        let sum = self.as_slice().iter().fold(zero, |sum, &x| {
+            let x = T::Accumulator::widen(x);
+            // Avoid negative intermediate values for unsigned types
+            let deviation = if x > mean { x - mean } else { mean - x };
+            sum + deviation * deviation
+        });
+        Some(sum / T::Accumulator::from_usize(self.len()))
+    }
+}
+
+/// Numeric types that [`HistoryBufferInner::sum`], [`HistoryBufferInner::mean`] and
+/// [`HistoryBufferInner::variance`] can be computed over.
+///
+/// This trait is sealed: it is implemented for all the primitive integer and floating point
+/// types.
+#[allow(private_bounds)]
+pub trait Numeric: SealedNumeric {
+    /// The type the statistics are computed in.
+    ///
+    /// It is wider than the element type for integers, so that the sum of 8, 16 and 32-bit
+    /// integers doesn't overflow: `u8` accumulates in `u32`, `u16`, `u32` and `usize` in `u64`,
+    /// `u64` and `u128` in `u128`, and signed integers in their signed counterparts. The squared
+    /// deviations of the variance can still overflow it for 32-bit and wider integers far from
+    /// their mean. Floating point types accumulate in themselves.
+    type Accumulator: Copy
+        + PartialOrd
+        + Add<Output = Self::Accumulator>
+        + Sub<Output = Self::Accumulator>
+        + Mul<Output = Self::Accumulator>
+        + Div<Output = Self::Accumulator>
+        + Accumulate<Self>;
+}
+
+pub(crate) trait SealedNumeric: Copy + PartialOrd {}
+
+/// Conversions between a [`Numeric`] type `T` and its accumulator.
+pub(crate) trait Accumulate<T>: Sized {
+    /// The additive identity of the accumulator.
+    const ZERO: Self;
+
+    /// Converts to the accumulator, without loss.
+    fn widen(x: T) -> Self;
+
+    /// Converts from the accumulator.
+    ///
+    /// This behaves like an `as` cast, so `self` must be in the range of `T`, as the mean of
+    /// elements of `T` is.
+    fn narrow(self) -> T;
+
+    /// Converts a number of elements to the accumulator.
+    fn from_usize(n: usize) -> Self;
+}
+
+macro_rules! impl_numeric {
+    ($($ty:ty => $acc:ty, $zero:expr),* $(,)?) => {
+        $(
+            impl Numeric for $ty {
+                type Accumulator = $acc;
+            }
+
+            impl SealedNumeric for $ty {}
+
+            impl Accumulate<$ty> for $acc {
+                const ZERO: Self = $zero;
+
+                #[inline]
+                fn widen(x: $ty) -> Self {
+                    x as Self
+                }
+
+                #[inline]
+                fn narrow(self) -> $ty {
+                    self as $ty
+                }
+
+                #[inline]
+                fn from_usize(n: usize) -> Self {
+                    n as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(
+    u8 => u32, 0,
+    u16 => u64, 0,
+    u32 => u64, 0,
+    u64 => u128, 0,
+    u128 => u128, 0,
+    usize => u64, 0,
+    i8 => i32, 0,
+    i16 => i64, 0,
+    i32 => i64, 0,
+    i64 => i128, 0,
+    i128 => i128, 0,
+    isize => i64, 0,
+    f32 => f32, 0.0,
+    f64 => f64, 0.0,
+);
+
 impl<T, S: Storage> Extend<T> for HistoryBufferInner<T, S> {
     fn extend<I>(&mut self, iter: I)
     where
@@ -430,12 +897,8 @@ where
     }
 }
 
-#[cfg(feature="copy")]
-impl<T, const N: usize> Copy for HistoryBuffer<T, N>
-where
-    T: Copy,
-{
-}
+#[cfg(feature = "copy")]
+impl<T, const N: usize> Copy for HistoryBuffer<T, N> where T: Copy {}
 
 impl<T, const N: usize> Clone for HistoryBuffer<T, N>
 where
@@ -452,7 +915,7 @@ where
     }
 }
 
-#[cfg(not(feature="copy"))]
+#[cfg(not(feature = "copy"))]
 impl<T, S: Storage> Drop for HistoryBufferInner<T, S> {
     fn drop(&mut self) {
         unsafe { self.drop_contents() }
@@ -489,11 +952,11 @@ impl<T, const N: usize> Default for HistoryBuffer<T, N> {
     }
 }
 
-impl<T, S: Storage> PartialEq for HistoryBufferInner<T, S>
+impl<T, S: Storage, S2: Storage> PartialEq<HistoryBufferInner<T, S2>> for HistoryBufferInner<T, S>
 where
     T: PartialEq,
 {
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &HistoryBufferInner<T, S2>) -> bool {
         self.oldest_ordered().eq(other.oldest_ordered())
     }
 }
@@ -563,15 +1026,151 @@ impl<'a, T, S: Storage> Iterator for OldestOrderedInner<'a, T, S> {
     }
 }
 
-impl<T, const N: usize> DoubleEndedIterator for OldestOrdered<'_, T, N> {
+impl<T, S: Storage> DoubleEndedIterator for OldestOrderedInner<'_, T, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T, S: Storage> ExactSizeIterator for OldestOrderedInner<'_, T, S> {}
+impl<T, S: Storage> FusedIterator for OldestOrderedInner<'_, T, S> {}
+
+/// Double ended iterator on the underlying buffer ordered from the oldest data
+/// to the newest, with mutable access to the elements.
+///
+/// This struct is created by [`HistoryBufferInner::oldest_ordered_mut`].
+pub struct OldestOrderedMut<'a, T> {
+    inner: core::iter::Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for OldestOrderedMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for OldestOrderedMut<'_, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back()
     }
 }
 
+impl<T> ExactSizeIterator for OldestOrderedMut<'_, T> {}
+impl<T> FusedIterator for OldestOrderedMut<'_, T> {}
+
+/// A [`HistoryBuffer`] of timestamped samples, supporting time window queries.
+///
+/// Samples are stored as `(timestamp, value)` pairs and must be written with non-decreasing
+/// timestamps, which lets the time window queries use binary searches. The underlying buffer can
+/// be accessed through `Deref`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::TimedHistoryBuffer;
+///
+/// // Timestamps in milliseconds
+/// let mut samples: TimedHistoryBuffer<u32, i16, 8> = TimedHistoryBuffer::new();
+/// for (t, value) in [(0, 10), (1000, 11), (2000, 12), (3000, 13), (4000, 14)] {
+///     samples.write(t, value).unwrap();
+/// }
+/// // Timestamps can't go back in time
+/// assert_eq!(samples.write(3500, 0), Err((3500, 0)));
+///
+/// // The samples of the last 2 seconds
+/// assert!(samples.range_since(4000 - 2000).map(|(_, v)| v).eq(&[12, 13, 14]));
+/// // The samples of the second second
+/// assert!(samples.between(1000, 2000).eq(&[(1000, 11)]));
+///
+/// assert_eq!(samples.len(), 5);
+/// assert_eq!(samples.recent(), Some(&(4000, 14)));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "copy", derive(Copy))]
+pub struct TimedHistoryBuffer<Ts, T, const N: usize> {
+    buffer: HistoryBuffer<(Ts, T), N>,
+}
+
+impl<Ts, T, const N: usize> TimedHistoryBuffer<Ts, T, N> {
+    /// Constructs a new timestamped history buffer.
+    pub const fn new() -> Self {
+        Self {
+            buffer: HistoryBuffer::new(),
+        }
+    }
+
+    /// Clears the buffer.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns the underlying buffer of `(timestamp, value)` pairs.
+    pub fn as_history_buffer(&self) -> &HistoryBuffer<(Ts, T), N> {
+        &self.buffer
+    }
+}
+
+impl<Ts: Ord, T, const N: usize> TimedHistoryBuffer<Ts, T, N> {
+    /// Writes a sample to the buffer, overwriting the oldest one.
+    ///
+    /// Returns back the sample if `timestamp` is older than the most recent sample.
+    pub fn write(&mut self, timestamp: Ts, value: T) -> Result<(), (Ts, T)> {
+        match self.buffer.recent() {
+            Some((recent, _)) if timestamp < *recent => Err((timestamp, value)),
+            _ => {
+                self.buffer.write((timestamp, value));
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns an iterator over the samples with a timestamp greater or equal to `start`, from the
+    /// oldest to the newest.
+    pub fn range_since(&self, start: Ts) -> OldestOrderedView<'_, (Ts, T)> {
+        let start = self.partition_point(|t| *t < start);
+        self.buffer.ordered_range(start, self.buffer.len())
+    }
+
+    /// Returns an iterator over the samples with a timestamp greater or equal to `start` and
+    /// less than `end`, from the oldest to the newest.
+    pub fn between(&self, start: Ts, end: Ts) -> OldestOrderedView<'_, (Ts, T)> {
+        let start = self.partition_point(|t| *t < start);
+        let end = self.partition_point(|t| *t < end).max(start);
+        self.buffer.ordered_range(start, end)
+    }
+
+    /// Returns the number of samples, in chronological order, for which `pred` is true.
+    fn partition_point<P: FnMut(&Ts) -> bool>(&self, mut pred: P) -> usize {
+        // The samples are sorted by timestamp across both slices
+        let (old, new) = self.buffer.as_slices();
+        old.partition_point(|(t, _)| pred(t)) + new.partition_point(|(t, _)| pred(t))
+    }
+}
+
+impl<Ts, T, const N: usize> Default for TimedHistoryBuffer<Ts, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ts, T, const N: usize> Deref for TimedHistoryBuffer<Ts, T, N> {
+    type Target = HistoryBuffer<(Ts, T), N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::fmt::Debug;
+    use core::ptr;
     use core::sync::atomic::{AtomicUsize, Ordering};
 
     use static_assertions::assert_not_impl_any;
@@ -725,6 +1324,33 @@ mod tests {
         extend_then_assert(b"123456", (b"34", b"56"));
     }
 
+    /// Test whether .as_mut_slices() returns the same slices as .as_slices().
+    #[test]
+    fn as_mut_slices() {
+        let mut buffer: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(buffer.as_mut_slices(), (&mut [][..], &mut [][..]));
+
+        for n in 0..10 {
+            buffer.write(n);
+            let (head, tail) = buffer.as_slices();
+            let (head, tail) = (head.to_vec(), tail.to_vec());
+            let (head_mut, tail_mut) = buffer.as_mut_slices();
+            assert_eq!((&*head_mut, &*tail_mut), (&*head, &*tail));
+
+            // Mutations are visible in chronological order
+            for x in head_mut.iter_mut().chain(tail_mut) {
+                *x += 100;
+            }
+            assert_eq_iter(
+                buffer.oldest_ordered().map(|x| x - 100),
+                head.iter().chain(&tail).copied(),
+            );
+            for x in buffer.oldest_ordered_mut() {
+                *x -= 100;
+            }
+        }
+    }
+
     /// Test whether .as_slices() and .oldest_ordered() produce elements in the same order.
     #[test]
     fn as_slices_equals_ordered() {
@@ -793,6 +1419,265 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ordered_mut() {
+        for n in 0..20 {
+            const N: usize = 7;
+            let mut buffer: HistoryBuffer<u8, N> = HistoryBuffer::new();
+            buffer.extend(0..n);
+            let start = n.saturating_sub(N as u8);
+            assert_eq!(buffer.oldest_ordered_mut().len(), (n - start) as usize);
+            for (i, x) in buffer.oldest_ordered_mut().enumerate() {
+                assert_eq!(*x, start + i as u8);
+                *x = i as u8;
+            }
+            assert_eq_iter(buffer.oldest_ordered().copied(), 0..n - start);
+            for x in buffer.oldest_ordered_mut().rev().take(1) {
+                *x = 100;
+            }
+            assert_eq!(buffer.recent(), if n == 0 { None } else { Some(&100) });
+        }
+    }
+
+    #[test]
+    fn statistics() {
+        let mut buffer: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        assert_eq!(buffer.min(), None);
+        assert_eq!(buffer.max(), None);
+        assert_eq!(buffer.sum(), 0);
+        assert_eq!(buffer.mean(), None);
+        assert_eq!(buffer.variance(), None);
+
+        buffer.write(-3);
+        assert_eq!(buffer.min(), Some(&-3));
+        assert_eq!(buffer.max(), Some(&-3));
+        assert_eq!(buffer.mean(), Some(-3));
+        assert_eq!(buffer.variance(), Some(0));
+
+        // Only the last 4 values are in the window
+        buffer.extend([1000, -1000, 2, 4, -6, 8]);
+        assert_eq!(buffer.min(), Some(&-6));
+        assert_eq!(buffer.max(), Some(&8));
+        assert_eq!(buffer.sum(), 8);
+        assert_eq!(buffer.mean(), Some(2));
+        // Deviations are 0, 2, 8 and 6
+        assert_eq!(buffer.variance(), Some(26));
+
+        let mut buffer: HistoryBuffer<f64, 3> = HistoryBuffer::new();
+        buffer.extend([1e9, 1.0, 2.0, 6.0]);
+        assert_eq!(buffer.min(), Some(&1.0));
+        assert_eq!(buffer.max(), Some(&6.0));
+        assert_eq!(buffer.sum(), 9.0);
+        assert_eq!(buffer.mean(), Some(3.0));
+        assert_eq!(buffer.variance(), Some(14.0 / 3.0));
+
+        let mut buffer: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        buffer.extend([9, 1, 5]);
+        assert_eq!(buffer.mean(), Some(5));
+        assert_eq!(buffer.variance(), Some(10));
+    }
+
+    #[test]
+    fn min_max_ties() {
+        let mut buffer: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        // In memory: [7, 1, 9, 1], in write order: [9, 1, 7, 1]
+        buffer.extend([5, 1, 9, 1, 7, 1]);
+        assert!(ptr::eq(buffer.min().unwrap(), &buffer.as_slice()[3]));
+
+        // In memory: [4, 9, 2, 9], in write order: [2, 9, 4, 9]
+        buffer.clear();
+        buffer.extend([5, 9, 2, 9, 4, 9]);
+        assert!(ptr::eq(buffer.max().unwrap(), &buffer.as_slice()[3]));
+
+        let mut buffer: HistoryBuffer<f32, 3> = HistoryBuffer::new();
+        buffer.extend([2.0, f32::NAN, 0.5]);
+        assert_eq!(buffer.min(), Some(&0.5));
+        // The oldest element is `NaN`
+        buffer.write(1.0);
+        assert!(buffer.min().unwrap().is_nan());
+    }
+
+    #[test]
+    fn statistics_overflow() {
+        let mut buffer: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        buffer.extend([200; 4]);
+        assert_eq!(buffer.sum(), 800);
+        assert_eq!(buffer.mean(), Some(200));
+        assert_eq!(buffer.variance(), Some(0));
+        buffer.extend([0, 255]);
+        assert_eq!(buffer.mean(), Some(163));
+        // Deviations are 37, 37, 163 and 92
+        assert_eq!(buffer.variance(), Some(9442));
+
+        // The number of elements doesn't fit in `u8`
+        let mut buffer: HistoryBuffer<u8, 256> = HistoryBuffer::new();
+        buffer.extend([255; 256]);
+        assert_eq!(buffer.sum(), 65280);
+        assert_eq!(buffer.mean(), Some(255));
+        assert_eq!(buffer.variance(), Some(0));
+        buffer.extend([0; 128]);
+        assert_eq!(buffer.mean(), Some(127));
+        assert_eq!(buffer.variance(), Some(16256));
+
+        // 12-bit ADC samples
+        let mut buffer: HistoryBuffer<u16, 64> = HistoryBuffer::new();
+        buffer.extend((0..64).map(|i| if i & 1 == 0 { 0 } else { 4095 }));
+        assert_eq!(buffer.sum(), 32 * 4095);
+        assert_eq!(buffer.mean(), Some(2047));
+        assert_eq!(buffer.variance(), Some(4_192_256));
+        buffer.extend([u16::MAX; 64]);
+        assert_eq!(buffer.mean(), Some(u16::MAX));
+
+        let mut buffer: HistoryBuffer<u32, 4> = HistoryBuffer::new();
+        buffer.extend([u32::MAX, 0, u32::MAX, 0]);
+        let sum: u64 = buffer.sum();
+        assert_eq!(sum, 2 * u64::from(u32::MAX));
+        assert_eq!(buffer.mean(), Some(u32::MAX / 2));
+
+        let mut buffer: HistoryBuffer<i8, 256> = HistoryBuffer::new();
+        buffer.extend((0..256).map(|i| if i & 1 == 0 { -128 } else { 127 }));
+        assert_eq!(buffer.mean(), Some(0));
+        assert_eq!(buffer.variance(), Some(16256));
+    }
+
+    #[test]
+    fn write_slice() {
+        for n in 0..12 {
+            for chunk in 0..12 {
+                let mut expected: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+                expected.extend(0..n);
+                let mut buffer = expected.clone();
+
+                let items: Vec<u8> = (n..n + chunk).collect();
+                expected.extend_from_slice(&items);
+                buffer.write_slice(&items);
+
+                assert_eq!(buffer.len(), expected.len());
+                assert_eq!(buffer.is_full(), expected.is_full());
+                assert_eq!(buffer.recent_index(), expected.recent_index());
+                assert_eq!(buffer.as_slice(), expected.as_slice());
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_step() {
+        for n in 0..20 {
+            const N: usize = 7;
+            let mut buffer: HistoryBuffer<u8, N> = HistoryBuffer::new();
+            buffer.extend(0..n);
+            let start = n.saturating_sub(N as u8);
+            for step in 1..9 {
+                assert_eq_iter(
+                    buffer.oldest_ordered().step_by(step).copied(),
+                    (start..n).step_by(step),
+                );
+                assert_eq_iter(
+                    buffer.oldest_ordered().step_by(step).rev().copied(),
+                    (start..n).step_by(step).rev(),
+                );
+                assert_eq!(
+                    buffer.oldest_ordered().step_by(step).len(),
+                    (start..n).step_by(step).len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn view() {
+        use super::HistoryBufferView;
+
+        // Not monomorphized per capacity
+        fn smooth(window: &mut HistoryBufferView<i32>) -> Option<i32> {
+            let mean = window.mean()?;
+            *window.recent_mut()? = mean;
+            Some(mean)
+        }
+
+        let mut small: HistoryBuffer<i32, 2> = HistoryBuffer::new();
+        let mut large: HistoryBuffer<i32, 8> = HistoryBuffer::new();
+        assert_eq!(smooth(&mut small), None);
+        small.extend([1, 2, 3, 5]);
+        large.extend([3, 5]);
+        assert_eq!(small.as_view(), large.as_view());
+        assert_eq!(smooth(small.as_mut_view()), Some(4));
+        assert_eq!(smooth(&mut large), Some(4));
+        assert!(small.as_view().oldest_ordered().eq(&[3, 4]));
+        assert_eq!(small, large);
+        assert_eq!(format!("{:?}", large.as_view()), "[3, 4]");
+    }
+
+    #[test]
+    fn recent_oldest_mut() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.recent_mut(), None);
+        assert_eq!(x.oldest_mut(), None);
+
+        for i in 0..7 {
+            x.write(i);
+            *x.recent_mut().unwrap() += 10;
+            assert_eq!(x.recent(), Some(&(i + 10)));
+        }
+        *x.oldest_mut().unwrap() = 0;
+        assert!(x.oldest_ordered().eq(&[0, 15, 16]));
+    }
+
+    #[test]
+    fn recent_n() {
+        for n in 0..20 {
+            const N: usize = 7;
+            let mut buffer: HistoryBuffer<u8, N> = HistoryBuffer::new();
+            buffer.extend(0..n);
+            let start = n.saturating_sub(N as u8);
+            for count in 0..10 {
+                let expected = n.saturating_sub(count).max(start)..n;
+                assert_eq_iter(buffer.recent_n(count as usize).copied(), expected.clone());
+                assert_eq_iter(
+                    buffer.recent_n(count as usize).rev().copied(),
+                    expected.rev(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn timed() {
+        use super::TimedHistoryBuffer;
+
+        for n in 0..15u32 {
+            let mut buffer: TimedHistoryBuffer<u32, u32, 5> = TimedHistoryBuffer::new();
+            // Two samples per timestamp
+            for i in 0..n {
+                buffer.write(i / 2, i).unwrap();
+            }
+            let samples: Vec<(u32, u32)> = buffer.oldest_ordered().copied().collect();
+
+            for start in 0..10 {
+                assert_eq_iter(
+                    buffer.range_since(start).copied(),
+                    samples.iter().copied().filter(|(t, _)| *t >= start),
+                );
+                for end in 0..10 {
+                    assert_eq_iter(
+                        buffer.between(start, end).copied(),
+                        samples
+                            .iter()
+                            .copied()
+                            .filter(|(t, _)| *t >= start && *t < end),
+                    );
+                }
+            }
+
+            if let Some(&(recent, _)) = buffer.recent() {
+                assert_eq!(buffer.write(recent, 100), Ok(()));
+                if recent > 0 {
+                    assert_eq!(buffer.write(recent - 1, 100), Err((recent - 1, 100)));
+                }
+            }
+        }
+    }
+
     /// Compares two iterators item by item, making sure they stop at the same time.
     fn assert_eq_iter<I: Eq + Debug>(
         a: impl IntoIterator<Item = I>,
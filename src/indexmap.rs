@@ -1,13 +1,18 @@
 use core::{
     borrow::Borrow,
+    cmp::Ordering,
     fmt,
     hash::{BuildHasher, Hash},
-    mem,
-    num::NonZeroU32,
-    ops, slice,
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    num::{NonZeroU16, NonZeroU32},
+    ops::{self, Range, RangeBounds},
+    ptr::{self, NonNull},
+    slice,
 };
 
-use hash32::{BuildHasherDefault, FnvHasher};
+use hash32::{BuildHasherDefault, FnvHasher, Murmur3Hasher};
 
 use crate::Vec;
 
@@ -64,16 +69,67 @@ use crate::Vec;
 /// ```
 pub type FnvIndexMap<K, V, const N: usize> = IndexMap<K, V, BuildHasherDefault<FnvHasher>, N>;
 
+/// An [`IndexMap`] using the Murmur3 hasher.
+///
+/// Murmur3 hashes its input 4 bytes at a time, which makes it faster than FNV for keys longer
+/// than a few bytes.
+///
+/// A list of all Methods and Traits available for `Murmur3IndexMap` can be found in
+/// the [`IndexMap`] documentation.
+///
+/// # Examples
+/// ```
+/// use heapless::Murmur3IndexMap;
+///
+/// let mut routes = Murmur3IndexMap::<[u8; 6], u8, 16>::new();
+/// routes.insert([0x02, 0, 0, 0, 0, 0x01], 1).unwrap();
+/// assert_eq!(routes.get(&[0x02, 0, 0, 0, 0, 0x01]), Some(&1));
+/// ```
+pub type Murmur3IndexMap<K, V, const N: usize> =
+    IndexMap<K, V, BuildHasherDefault<Murmur3Hasher>, N>;
+
+/// An [`IndexMap`] using the default FNV hasher and [`CompactPos`] indices.
+///
+/// Halves the RAM used by the hash indices, at the cost of a capacity limited to 255 entries.
+///
+/// A list of all Methods and Traits available for `CompactFnvIndexMap` can be found in
+/// the [`IndexMap`] documentation.
+///
+/// # Examples
+/// ```
+/// use heapless::{CompactFnvIndexMap, FnvIndexMap};
+///
+/// let mut sensors = CompactFnvIndexMap::<u8, i16, 16>::new();
+/// sensors.insert(3, -12).unwrap();
+/// assert_eq!(sensors.get(&3), Some(&-12));
+///
+/// assert!(core::mem::size_of_val(&sensors) < core::mem::size_of::<FnvIndexMap<u8, i16, 16>>());
+/// ```
+pub type CompactFnvIndexMap<K, V, const N: usize> =
+    IndexMap<K, V, BuildHasherDefault<FnvHasher>, N, CompactPos>;
+
 #[derive(Clone, Copy, Eq, PartialEq)]
-struct HashValue(u16);
+pub(crate) struct HashValue(u16);
 
 impl HashValue {
-    fn desired_pos(&self, mask: usize) -> usize {
-        usize::from(self.0) & mask
+    #[inline]
+    fn desired_pos(&self, capacity: usize) -> usize {
+        // Reduce with a bitmask when possible, it's much cheaper than a division
+        if capacity.is_power_of_two() {
+            usize::from(self.0) & (capacity - 1)
+        } else {
+            usize::from(self.0) % capacity
+        }
     }
 
-    fn probe_distance(&self, mask: usize, current: usize) -> usize {
-        current.wrapping_sub(self.desired_pos(mask)) & mask
+    #[inline]
+    fn probe_distance(&self, capacity: usize, current: usize) -> usize {
+        let desired = self.desired_pos(capacity);
+        if current >= desired {
+            current - desired
+        } else {
+            current + capacity - desired
+        }
     }
 }
 
@@ -85,7 +141,28 @@ pub struct Bucket<K, V> {
     value: V,
 }
 
-#[doc(hidden)]
+/// The type of the hash indices of an [`IndexMap`] or [`IndexSet`](crate::IndexSet).
+///
+/// This trait is sealed: it is implemented by [`Pos`], the default, and by [`CompactPos`], which
+/// halves the size of the indices of maps and sets holding at most 255 entries.
+#[allow(private_bounds)]
+pub trait IndexPos: SealedIndexPos {}
+
+pub(crate) trait SealedIndexPos: Copy {
+    /// The largest capacity this position can index.
+    const MAX_CAPACITY: usize;
+
+    fn new(index: usize, hash: HashValue) -> Self;
+
+    fn hash(&self) -> HashValue;
+
+    fn index(&self) -> usize;
+
+    /// Truncates a full hash to the bits stored in this position.
+    fn hash_value(hash: u64) -> HashValue;
+}
+
+/// A hash index for maps and sets of up to 65535 entries, stored in 4 bytes.
 #[derive(Clone, Copy, PartialEq)]
 pub struct Pos {
     // compact representation of `{ hash_value: u16, index: u16 }`
@@ -95,7 +172,12 @@ pub struct Pos {
     nz: NonZeroU32,
 }
 
-impl Pos {
+impl IndexPos for Pos {}
+
+impl SealedIndexPos for Pos {
+    // `{ hash_value: 0xffff, index: 0xffff }` would wrap to zero
+    const MAX_CAPACITY: usize = (1 << 16) - 1;
+
     fn new(index: usize, hash: HashValue) -> Self {
         Pos {
             nz: unsafe {
@@ -113,6 +195,57 @@ impl Pos {
     fn index(&self) -> usize {
         self.nz.get().wrapping_sub(1) as u16 as usize
     }
+
+    fn hash_value(hash: u64) -> HashValue {
+        HashValue(hash as u16)
+    }
+}
+
+/// A hash index for maps and sets of up to 255 entries, stored in 2 bytes.
+///
+/// Only 8 bits of the hash are kept, so lookups compare more keys on collisions than with
+/// [`Pos`]; in exchange the indices of the map take half the RAM.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CompactPos {
+    // `{ hash_value: u8, index: u8 }`, stored minus 1 like `Pos`
+    nz: NonZeroU16,
+}
+
+impl IndexPos for CompactPos {}
+
+impl SealedIndexPos for CompactPos {
+    // `{ hash_value: 0xff, index: 0xff }` would wrap to zero
+    const MAX_CAPACITY: usize = (1 << 8) - 1;
+
+    fn new(index: usize, hash: HashValue) -> Self {
+        CompactPos {
+            nz: unsafe {
+                NonZeroU16::new_unchecked(((hash.0 << 8) + index as u16).wrapping_add(1))
+            },
+        }
+    }
+
+    fn hash(&self) -> HashValue {
+        HashValue(self.nz.get().wrapping_sub(1) >> 8)
+    }
+
+    fn index(&self) -> usize {
+        self.nz.get().wrapping_sub(1) as u8 as usize
+    }
+
+    fn hash_value(hash: u64) -> HashValue {
+        HashValue(u16::from(hash as u8))
+    }
+}
+
+/// Compile-time check that `P` can index `N` entries.
+struct CapacityCheck<P, const N: usize>(PhantomData<P>);
+
+impl<P: IndexPos, const N: usize> CapacityCheck<P, N> {
+    const OK: () = assert!(
+        N <= P::MAX_CAPACITY,
+        "capacity too large for the index type"
+    );
 }
 
 enum Insert<K, V> {
@@ -137,133 +270,125 @@ macro_rules! probe_loop {
     }
 }
 
-struct CoreMap<K, V, const N: usize> {
+// Invariants of `indices`, maintained by the Robin Hood insertion and the backward-shift deletion:
+// - there is exactly one `Pos` per entry, and no tombstones
+// - walking forward from a slot, the probe distance of the `Pos`s grows by at most one per slot,
+//   so that lookups can stop at the first `Pos` closer to its desired slot than the query
+struct CoreMap<K, V, const N: usize, P: IndexPos> {
     entries: Vec<Bucket<K, V>, N>,
-    indices: [Option<Pos>; N],
+    indices: [Option<P>; N],
 }
 
-impl<K, V, const N: usize> CoreMap<K, V, N> {
+impl<K, V, const N: usize, P: IndexPos> CoreMap<K, V, N, P> {
     const fn new() -> Self {
-        const INIT: Option<Pos> = None;
-
         CoreMap {
             entries: Vec::new(),
-            indices: [INIT; N],
+            indices: [None; N],
         }
     }
-}
 
-impl<K, V, const N: usize> CoreMap<K, V, N>
-where
-    K: Eq + Hash,
-{
     fn capacity() -> usize {
         N
     }
 
-    fn mask() -> usize {
-        Self::capacity() - 1
+    fn clear_indices(&mut self) {
+        for pos in self.indices.iter_mut() {
+            *pos = None;
+        }
     }
 
-    fn find<Q>(&self, hash: HashValue, query: &Q) -> Option<(usize, usize)>
-    where
-        K: Borrow<Q>,
-        Q: ?Sized + Eq,
-    {
-        let mut probe = hash.desired_pos(Self::mask());
+    /// Rebuilds the hash indices pointing to the entries, after the entries moved.
+    fn rebuild_indices(&mut self) {
+        self.rebuild_indices_up_to(self.entries.len());
+    }
+
+    /// Rebuilds the hash indices pointing to the first `len` entries only.
+    fn rebuild_indices_up_to(&mut self, len: usize) {
+        self.clear_indices();
+
+        for index in 0..len {
+            let hash = self.entries[index].hash;
+            self.insert_index(index, hash);
+        }
+    }
+
+    /// Inserts the index of an entry whose key is not in the indices yet.
+    fn insert_index(&mut self, index: usize, hash: HashValue) {
+        let mut probe = hash.desired_pos(Self::capacity());
         let mut dist = 0;
 
         probe_loop!(probe < self.indices.len(), {
-            if let Some(pos) = self.indices[probe] {
+            let pos = &mut self.indices[probe];
+
+            if let Some(pos) = *pos {
                 let entry_hash = pos.hash();
-                // NOTE(i) we use unchecked indexing below
-                let i = pos.index();
-                debug_assert!(i < self.entries.len());
 
-                if dist > entry_hash.probe_distance(Self::mask(), probe) {
-                    // give up when probe distance is too long
-                    return None;
-                } else if entry_hash == hash
-                    && unsafe { self.entries.get_unchecked(i).key.borrow() == query }
-                {
-                    return Some((probe, i));
+                // robin hood: steal the spot if it's better for us
+                let their_dist = entry_hash.probe_distance(Self::capacity(), probe);
+                if their_dist < dist {
+                    Self::insert_phase_2(&mut self.indices, probe, P::new(index, hash));
+                    return;
                 }
             } else {
-                return None;
+                *pos = Some(P::new(index, hash));
+                return;
             }
-
             dist += 1;
         });
     }
 
-    fn insert(&mut self, hash: HashValue, key: K, value: V) -> Insert<K, V> {
-        let mut probe = hash.desired_pos(Self::mask());
+    /// Inserts an entry whose key is known not to be in the map, returning its index.
+    fn insert_new(&mut self, hash: HashValue, key: K, value: V) -> Result<usize, (K, V)> {
+        if self.entries.is_full() {
+            return Err((key, value));
+        }
+
+        let index = self.entries.len();
+        unsafe { self.entries.push_unchecked(Bucket { hash, key, value }) };
+        self.insert_index(index, hash);
+        Ok(index)
+    }
+
+    /// Return probe (indices) and position (entries) of the entry matching `is_match`.
+    fn find_by<F>(&self, hash: HashValue, mut is_match: F) -> Option<(usize, usize)>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let mut probe = hash.desired_pos(Self::capacity());
         let mut dist = 0;
 
         probe_loop!(probe < self.indices.len(), {
-            let pos = &mut self.indices[probe];
-
-            if let Some(pos) = *pos {
+            if let Some(pos) = self.indices[probe] {
                 let entry_hash = pos.hash();
                 // NOTE(i) we use unchecked indexing below
                 let i = pos.index();
                 debug_assert!(i < self.entries.len());
 
-                let their_dist = entry_hash.probe_distance(Self::mask(), probe);
-
-                if their_dist < dist {
-                    if self.entries.is_full() {
-                        return Insert::Full((key, value));
-                    }
-                    // robin hood: steal the spot if it's better for us
-                    let index = self.entries.len();
-                    unsafe { self.entries.push_unchecked(Bucket { hash, key, value }) };
-                    Self::insert_phase_2(&mut self.indices, probe, Pos::new(index, hash));
-                    return Insert::Success(Inserted {
-                        index,
-                        old_value: None,
-                    });
-                } else if entry_hash == hash && unsafe { self.entries.get_unchecked(i).key == key }
+                if dist > entry_hash.probe_distance(Self::capacity(), probe) {
+                    // give up when probe distance is too long
+                    return None;
+                } else if entry_hash == hash
+                    && is_match(unsafe { &self.entries.get_unchecked(i).key })
                 {
-                    return Insert::Success(Inserted {
-                        index: i,
-                        old_value: Some(mem::replace(
-                            unsafe { &mut self.entries.get_unchecked_mut(i).value },
-                            value,
-                        )),
-                    });
+                    return Some((probe, i));
                 }
             } else {
-                if self.entries.is_full() {
-                    return Insert::Full((key, value));
-                }
-                // empty bucket, insert here
-                let index = self.entries.len();
-                *pos = Some(Pos::new(index, hash));
-                unsafe { self.entries.push_unchecked(Bucket { hash, key, value }) };
-                return Insert::Success(Inserted {
-                    index,
-                    old_value: None,
-                });
+                return None;
             }
+
             dist += 1;
         });
     }
 
-    // phase 2 is post-insert where we forward-shift `Pos` in the indices.
-    fn insert_phase_2(indices: &mut [Option<Pos>; N], mut probe: usize, mut old_pos: Pos) -> usize {
-        probe_loop!(probe < indices.len(), {
-            let pos = unsafe { indices.get_unchecked_mut(probe) };
-
-            let mut is_none = true; // work around lack of NLL
-            if let Some(pos) = pos.as_mut() {
-                old_pos = mem::replace(pos, old_pos);
-                is_none = false;
-            }
+    /// Return the probe (indices) of the entry at position `index` (entries).
+    fn probe_of(&self, index: usize) -> usize {
+        let mut probe = self.entries[index].hash.desired_pos(Self::capacity());
 
-            if is_none {
-                *pos = Some(old_pos);
-                return probe;
+        probe_loop!(probe < self.indices.len(), {
+            if let Some(pos) = self.indices[probe] {
+                if pos.index() == index {
+                    return probe;
+                }
             }
         });
     }
@@ -279,13 +404,13 @@ where
         if let Some(entry) = self.entries.get(found) {
             // was not last element
             // examine new element in `found` and find it in indices
-            let mut probe = entry.hash.desired_pos(Self::mask());
+            let mut probe = entry.hash.desired_pos(Self::capacity());
 
             probe_loop!(probe < self.indices.len(), {
                 if let Some(pos) = self.indices[probe] {
                     if pos.index() >= self.entries.len() {
                         // found it
-                        self.indices[probe] = Some(Pos::new(found, entry.hash));
+                        self.indices[probe] = Some(P::new(found, entry.hash));
                         break;
                     }
                 }
@@ -297,47 +422,34 @@ where
         (entry.key, entry.value)
     }
 
+    fn shift_remove_found(&mut self, probe: usize, found: usize) -> (K, V) {
+        // index `probe` and entry `found` is to be removed
+        // shift the following entries down, then update the indices that point to them
+        self.indices[probe] = None;
+        self.backward_shift_after_removal(probe);
+        let entry = self.entries.remove(found);
+
+        for pos in self.indices.iter_mut().flatten() {
+            let index = pos.index();
+            if index > found {
+                *pos = P::new(index - 1, pos.hash());
+            }
+        }
+
+        (entry.key, entry.value)
+    }
+
     fn retain_in_order<F>(&mut self, mut keep: F)
     where
         F: FnMut(&mut K, &mut V) -> bool,
     {
-        const INIT: Option<Pos> = None;
-
+        let len = self.entries.len();
         self.entries
             .retain_mut(|entry| keep(&mut entry.key, &mut entry.value));
 
-        if self.entries.len() < self.indices.len() {
-            for index in self.indices.iter_mut() {
-                *index = INIT;
-            }
-
-            for (index, entry) in self.entries.iter().enumerate() {
-                let mut probe = entry.hash.desired_pos(Self::mask());
-                let mut dist = 0;
-
-                probe_loop!(probe < self.indices.len(), {
-                    let pos = &mut self.indices[probe];
-
-                    if let Some(pos) = *pos {
-                        let entry_hash = pos.hash();
-
-                        // robin hood: steal the spot if it's better for us
-                        let their_dist = entry_hash.probe_distance(Self::mask(), probe);
-                        if their_dist < dist {
-                            Self::insert_phase_2(
-                                &mut self.indices,
-                                probe,
-                                Pos::new(index, entry.hash),
-                            );
-                            break;
-                        }
-                    } else {
-                        *pos = Some(Pos::new(index, entry.hash));
-                        break;
-                    }
-                    dist += 1;
-                });
-            }
+        // The remaining entries moved, so rebuild the hash indices pointing to them
+        if self.entries.len() < len {
+            self.rebuild_indices();
         }
     }
 
@@ -351,7 +463,7 @@ where
             if let Some(pos) = self.indices[probe] {
                 let entry_hash = pos.hash();
 
-                if entry_hash.probe_distance(Self::mask(), probe) > 0 {
+                if entry_hash.probe_distance(Self::capacity(), probe) > 0 {
                     unsafe { *self.indices.get_unchecked_mut(last_probe) = self.indices[probe] }
                     self.indices[probe] = None;
                 } else {
@@ -363,17 +475,102 @@ where
             last_probe = probe;
         });
     }
+
+    // phase 2 is post-insert where we forward-shift `Pos` in the indices.
+    fn insert_phase_2(indices: &mut [Option<P>; N], mut probe: usize, mut old_pos: P) -> usize {
+        probe_loop!(probe < indices.len(), {
+            let pos = unsafe { indices.get_unchecked_mut(probe) };
+
+            let mut is_none = true; // work around lack of NLL
+            if let Some(pos) = pos.as_mut() {
+                old_pos = mem::replace(pos, old_pos);
+                is_none = false;
+            }
+
+            if is_none {
+                *pos = Some(old_pos);
+                return probe;
+            }
+        });
+    }
+}
+
+impl<K, V, const N: usize, P: IndexPos> CoreMap<K, V, N, P>
+where
+    K: Eq + Hash,
+{
+    fn find<Q>(&self, hash: HashValue, query: &Q) -> Option<(usize, usize)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.find_by(hash, |key| key.borrow() == query)
+    }
+
+    fn insert(&mut self, hash: HashValue, key: K, value: V) -> Insert<K, V> {
+        let mut probe = hash.desired_pos(Self::capacity());
+        let mut dist = 0;
+
+        probe_loop!(probe < self.indices.len(), {
+            let pos = &mut self.indices[probe];
+
+            if let Some(pos) = *pos {
+                let entry_hash = pos.hash();
+                // NOTE(i) we use unchecked indexing below
+                let i = pos.index();
+                debug_assert!(i < self.entries.len());
+
+                let their_dist = entry_hash.probe_distance(Self::capacity(), probe);
+
+                if their_dist < dist {
+                    if self.entries.is_full() {
+                        return Insert::Full((key, value));
+                    }
+                    // robin hood: steal the spot if it's better for us
+                    let index = self.entries.len();
+                    unsafe { self.entries.push_unchecked(Bucket { hash, key, value }) };
+                    Self::insert_phase_2(&mut self.indices, probe, P::new(index, hash));
+                    return Insert::Success(Inserted {
+                        index,
+                        old_value: None,
+                    });
+                } else if entry_hash == hash && unsafe { self.entries.get_unchecked(i).key == key }
+                {
+                    return Insert::Success(Inserted {
+                        index: i,
+                        old_value: Some(mem::replace(
+                            unsafe { &mut self.entries.get_unchecked_mut(i).value },
+                            value,
+                        )),
+                    });
+                }
+            } else {
+                if self.entries.is_full() {
+                    return Insert::Full((key, value));
+                }
+                // empty bucket, insert here
+                let index = self.entries.len();
+                *pos = Some(P::new(index, hash));
+                unsafe { self.entries.push_unchecked(Bucket { hash, key, value }) };
+                return Insert::Success(Inserted {
+                    index,
+                    old_value: None,
+                });
+            }
+            dist += 1;
+        });
+    }
 }
 
 #[cfg(feature="copy")]
-impl<K, V, const N: usize> Copy for CoreMap<K, V, N>
+impl<K, V, const N: usize, P: IndexPos> Copy for CoreMap<K, V, N, P>
 where
     K: Copy,
     V: Copy,
 {
 }
 
-impl<K, V, const N: usize> Clone for CoreMap<K, V, N>
+impl<K, V, const N: usize, P: IndexPos> Clone for CoreMap<K, V, N, P>
 where
     K: Clone,
     V: Clone,
@@ -387,20 +584,22 @@ where
 }
 
 /// A view into an entry in the map
-pub enum Entry<'a, K, V, const N: usize> {
+pub enum Entry<'a, K, V, const N: usize, P: IndexPos = Pos> {
     /// The entry corresponding to the key `K` exists in the map
-    Occupied(OccupiedEntry<'a, K, V, N>),
+    Occupied(OccupiedEntry<'a, K, V, N, P>),
     /// The entry corresponding to the key `K` does not exist in the map
-    Vacant(VacantEntry<'a, K, V, N>),
+    Vacant(VacantEntry<'a, K, V, N, P>),
 }
 
-impl<'a, K, V, const N: usize> Entry<'a, K, V, N>
+impl<'a, K, V, const N: usize, P: IndexPos> Entry<'a, K, V, N, P>
 where
     K: Eq + Hash,
 {
     /// Ensures a value is in the entry by inserting the default if empty, and
     /// returns a mutable reference to the value in the entry.
     ///
+    /// If the entry is vacant and the map is full, the default is returned back instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -429,6 +628,9 @@ where
     /// function if empty, and returns a mutable reference to the value in the
     /// entry.
     ///
+    /// If the entry is vacant and the map is full, the result of the default
+    /// function is returned back instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -462,6 +664,9 @@ where
     /// The reference to the moved key is provided so that cloning or copying
     /// the key is unnecessary, unlike with `.or_insert_with(|| ... )`.
     ///
+    /// If the entry is vacant and the map is full, the result of the default
+    /// function is returned back instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -526,6 +731,27 @@ where
     ///     .or_insert("Very enjoyable.");
     /// assert_eq!(book_reviews["Grimms' Fairy Tales"], "Very enjoyable.");
     /// ```
+    ///
+    /// Combined with [`or_insert`](Self::or_insert), this updates existing entries
+    /// even when the map is full, and reports when a new entry doesn't fit:
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut routes = FnvIndexMap::<u8, u32, 2>::new();
+    /// routes.insert(1, 100).unwrap();
+    /// routes.insert(2, 200).unwrap();
+    ///
+    /// let mut update = |dest, cost| {
+    ///     routes
+    ///         .entry(dest)
+    ///         .and_modify(|c| *c = cost)
+    ///         .or_insert(cost)
+    ///         .map(|c| *c)
+    /// };
+    /// assert_eq!(update(2, 150), Ok(150));
+    /// assert_eq!(update(3, 300), Err(300));
+    /// ```
     pub fn and_modify<F>(self, f: F) -> Self
     where
         F: FnOnce(&mut V),
@@ -540,7 +766,7 @@ where
     }
 }
 
-impl<'a, K, V, const N: usize> Entry<'a, K, V, N>
+impl<'a, K, V, const N: usize, P: IndexPos> Entry<'a, K, V, N, P>
 where
     K: Eq + Hash,
     V: Default,
@@ -548,6 +774,8 @@ where
     /// Ensures a value is in the entry by inserting the default value if empty,
     /// and returns a mutable reference to the value in the entry.
     ///
+    /// If the entry is vacant and the map is full, the default value is returned back instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -571,14 +799,14 @@ where
 }
 
 /// An occupied entry which can be manipulated
-pub struct OccupiedEntry<'a, K, V, const N: usize> {
+pub struct OccupiedEntry<'a, K, V, const N: usize, P: IndexPos = Pos> {
     key: K,
     probe: usize,
     pos: usize,
-    core: &'a mut CoreMap<K, V, N>,
+    core: &'a mut CoreMap<K, V, N, P>,
 }
 
-impl<'a, K, V, const N: usize> OccupiedEntry<'a, K, V, N>
+impl<'a, K, V, const N: usize, P: IndexPos> OccupiedEntry<'a, K, V, N, P>
 where
     K: Eq + Hash,
 {
@@ -588,10 +816,28 @@ where
     }
 
     /// Removes this entry from the map and yields its corresponding key and value
+    ///
+    /// Same as [`swap_remove_entry`](Self::swap_remove_entry)
     pub fn remove_entry(self) -> (K, V) {
+        self.swap_remove_entry()
+    }
+
+    /// Removes this entry from the map by swapping it with the last entry, and yields its
+    /// corresponding key and value
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove_entry(self) -> (K, V) {
         self.core.remove_found(self.probe, self.pos)
     }
 
+    /// Removes this entry from the map by shifting all the following entries, and yields its
+    /// corresponding key and value
+    ///
+    /// Computes in *O*(n) time.
+    pub fn shift_remove_entry(self) -> (K, V) {
+        self.core.shift_remove_found(self.probe, self.pos)
+    }
+
     /// Gets a reference to the value associated with this entry
     pub fn get(&self) -> &V {
         // SAFETY: Already checked existence at instantiation and the only mutable reference
@@ -626,18 +872,35 @@ where
     }
 
     /// Removes this entry from the map and yields its value
+    ///
+    /// Same as [`swap_remove`](Self::swap_remove)
     pub fn remove(self) -> V {
         self.remove_entry().1
     }
+
+    /// Removes this entry from the map by swapping it with the last entry, and yields its value
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove(self) -> V {
+        self.swap_remove_entry().1
+    }
+
+    /// Removes this entry from the map by shifting all the following entries, and yields its
+    /// value
+    ///
+    /// Computes in *O*(n) time.
+    pub fn shift_remove(self) -> V {
+        self.shift_remove_entry().1
+    }
 }
 
 /// A view into an empty slot in the underlying map
-pub struct VacantEntry<'a, K, V, const N: usize> {
+pub struct VacantEntry<'a, K, V, const N: usize, P: IndexPos = Pos> {
     key: K,
     hash_val: HashValue,
-    core: &'a mut CoreMap<K, V, N>,
+    core: &'a mut CoreMap<K, V, N, P>,
 }
-impl<'a, K, V, const N: usize> VacantEntry<'a, K, V, N>
+impl<'a, K, V, const N: usize, P: IndexPos> VacantEntry<'a, K, V, N, P>
 where
     K: Eq + Hash,
 {
@@ -646,38 +909,395 @@ where
         &self.key
     }
 
-    /// Consumes this entry to yield to key associated with it
-    pub fn into_key(self) -> K {
-        self.key
-    }
+    /// Consumes this entry to yield to key associated with it
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts this entry into to underlying map, yields a mutable reference to the inserted value.
+    /// If the map is at capacity the value is returned instead.
+    pub fn insert(self, value: V) -> Result<&'a mut V, V> {
+        if self.core.entries.is_full() {
+            Err(value)
+        } else {
+            match self.core.insert(self.hash_val, self.key, value) {
+                Insert::Success(inserted) => {
+                    unsafe {
+                        // SAFETY: Already checked existence at instantiation and the only mutable reference
+                        // to the map is internally held.
+                        Ok(&mut (*self.core.entries.as_mut_ptr().add(inserted.index)).value)
+                    }
+                }
+                Insert::Full((_, v)) => Err(v),
+            }
+        }
+    }
+}
+
+/// A builder for computing where in an [`IndexMap`] a key-value pair would be stored
+///
+/// Created by [`IndexMap::raw_entry`].
+pub struct RawEntryBuilder<'a, K, V, S, const N: usize, P: IndexPos = Pos> {
+    map: &'a IndexMap<K, V, S, N, P>,
+}
+
+impl<'a, K, V, S, const N: usize, P: IndexPos> RawEntryBuilder<'a, K, V, S, N, P>
+where
+    S: BuildHasher,
+{
+    /// Access an entry by key
+    pub fn from_key<Q>(self, key: &Q) -> Option<(&'a K, &'a V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let hash = self.map.build_hasher.hash_one(key);
+        self.from_key_hashed_nocheck(hash, key)
+    }
+
+    /// Access an entry by key and its precomputed hash
+    ///
+    /// The hash must be the one computed by the map's [hasher](IndexMap::hasher) for the key,
+    /// otherwise the entry won't be found.
+    pub fn from_key_hashed_nocheck<Q>(self, hash: u64, key: &Q) -> Option<(&'a K, &'a V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.from_hash(hash, |k| k.borrow() == key)
+    }
+
+    /// Access an entry by its precomputed hash, using `is_match` to compare the keys
+    ///
+    /// `is_match` is called on the keys with a matching hash, until it returns `true`.
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> Option<(&'a K, &'a V)>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let core = &self.map.core;
+        core.find_by(P::hash_value(hash), is_match)
+            .map(|(_, found)| {
+                let bucket = &core.entries[found];
+                (&bucket.key, &bucket.value)
+            })
+    }
+}
+
+/// A builder for computing where in an [`IndexMap`] a key-value pair would be stored
+///
+/// Created by [`IndexMap::raw_entry_mut`].
+pub struct RawEntryBuilderMut<'a, K, V, S, const N: usize, P: IndexPos = Pos> {
+    map: &'a mut IndexMap<K, V, S, N, P>,
+}
+
+impl<'a, K, V, S, const N: usize, P: IndexPos> RawEntryBuilderMut<'a, K, V, S, N, P>
+where
+    S: BuildHasher,
+{
+    /// Create a [`RawEntryMut`] from the given key
+    pub fn from_key<Q>(self, key: &Q) -> RawEntryMut<'a, K, V, S, N, P>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let hash = self.map.build_hasher.hash_one(key);
+        self.from_key_hashed_nocheck(hash, key)
+    }
+
+    /// Create a [`RawEntryMut`] from the given key and its precomputed hash
+    ///
+    /// The hash must be the one computed by the map's [hasher](IndexMap::hasher) for the key,
+    /// otherwise the entry won't be found.
+    pub fn from_key_hashed_nocheck<Q>(self, hash: u64, key: &Q) -> RawEntryMut<'a, K, V, S, N, P>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.from_hash(hash, |k| k.borrow() == key)
+    }
+
+    /// Create a [`RawEntryMut`] from the given precomputed hash, using `is_match` to compare the
+    /// keys
+    ///
+    /// `is_match` is called on the keys with a matching hash, until it returns `true`.
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> RawEntryMut<'a, K, V, S, N, P>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        match self.map.core.find_by(P::hash_value(hash), is_match) {
+            Some((probe, pos)) => RawEntryMut::Occupied(RawOccupiedEntryMut {
+                probe,
+                pos,
+                core: &mut self.map.core,
+            }),
+            None => RawEntryMut::Vacant(RawVacantEntryMut {
+                build_hasher: &self.map.build_hasher,
+                core: &mut self.map.core,
+            }),
+        }
+    }
+}
+
+/// A view into a single entry in an [`IndexMap`], found with a precomputed hash
+///
+/// Created by [`RawEntryBuilderMut`].
+pub enum RawEntryMut<'a, K, V, S, const N: usize, P: IndexPos = Pos> {
+    /// The entry exists in the map
+    Occupied(RawOccupiedEntryMut<'a, K, V, N, P>),
+    /// The entry does not exist in the map
+    Vacant(RawVacantEntryMut<'a, K, V, S, N, P>),
+}
+
+impl<'a, K, V, S, const N: usize, P: IndexPos> RawEntryMut<'a, K, V, S, N, P>
+where
+    K: Hash,
+    S: BuildHasher,
+{
+    /// Ensures a value is in the entry by inserting the result of `default` if empty, and returns
+    /// mutable references to the key and value in the entry.
+    ///
+    /// If the entry is vacant and the map is full, the key and value are returned instead.
+    pub fn or_insert_with<F>(self, default: F) -> Result<(&'a mut K, &'a mut V), (K, V)>
+    where
+        F: FnOnce() -> (K, V),
+    {
+        match self {
+            Self::Occupied(entry) => Ok(entry.into_key_value()),
+            Self::Vacant(entry) => {
+                let (key, value) = default();
+                entry.insert(key, value)
+            }
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into
+    /// the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut K, &mut V),
+    {
+        match self {
+            Self::Occupied(mut entry) => {
+                let (key, value) = entry.get_key_value_mut();
+                f(key, value);
+                Self::Occupied(entry)
+            }
+            Self::Vacant(entry) => Self::Vacant(entry),
+        }
+    }
+}
+
+/// An occupied entry which can be manipulated, found with a precomputed hash
+///
+/// Changing the key with [`key_mut`](Self::key_mut) must not change its hash or its equality with
+/// the other keys, otherwise the entry won't be found anymore.
+pub struct RawOccupiedEntryMut<'a, K, V, const N: usize, P: IndexPos = Pos> {
+    probe: usize,
+    pos: usize,
+    core: &'a mut CoreMap<K, V, N, P>,
+}
+
+impl<'a, K, V, const N: usize, P: IndexPos> RawOccupiedEntryMut<'a, K, V, N, P> {
+    /// Gets the position of this entry in insertion order
+    pub fn index(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets a reference to the key in the entry
+    pub fn key(&self) -> &K {
+        &self.core.entries[self.pos].key
+    }
+
+    /// Gets a mutable reference to the key in the entry
+    pub fn key_mut(&mut self) -> &mut K {
+        &mut self.core.entries[self.pos].key
+    }
+
+    /// Gets a reference to the value in the entry
+    pub fn get(&self) -> &V {
+        &self.core.entries[self.pos].value
+    }
+
+    /// Gets a mutable reference to the value in the entry
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.core.entries[self.pos].value
+    }
+
+    /// Gets mutable references to the key and value in the entry
+    pub fn get_key_value_mut(&mut self) -> (&mut K, &mut V) {
+        let bucket = &mut self.core.entries[self.pos];
+        (&mut bucket.key, &mut bucket.value)
+    }
+
+    /// Consumes this entry and yields a reference to the underlying value
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.core.entries[self.pos].value
+    }
+
+    /// Consumes this entry and yields mutable references to the underlying key and value
+    pub fn into_key_value(self) -> (&'a mut K, &'a mut V) {
+        let bucket = &mut self.core.entries[self.pos];
+        (&mut bucket.key, &mut bucket.value)
+    }
+
+    /// Overwrites the underlying map's value with this entry's value
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes this entry from the map by swapping it with the last entry, and yields its
+    /// corresponding key and value
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove_entry(self) -> (K, V) {
+        self.core.remove_found(self.probe, self.pos)
+    }
+
+    /// Removes this entry from the map by shifting all the following entries, and yields its
+    /// corresponding key and value
+    ///
+    /// Computes in *O*(n) time.
+    pub fn shift_remove_entry(self) -> (K, V) {
+        self.core.shift_remove_found(self.probe, self.pos)
+    }
+}
+
+/// A view into an empty slot in the underlying map, found with a precomputed hash
+pub struct RawVacantEntryMut<'a, K, V, S, const N: usize, P: IndexPos = Pos> {
+    build_hasher: &'a S,
+    core: &'a mut CoreMap<K, V, N, P>,
+}
 
-    /// Inserts this entry into to underlying map, yields a mutable reference to the inserted value.
-    /// If the map is at capacity the value is returned instead.
-    pub fn insert(self, value: V) -> Result<&'a mut V, V> {
-        if self.core.entries.is_full() {
-            Err(value)
-        } else {
-            match self.core.insert(self.hash_val, self.key, value) {
-                Insert::Success(inserted) => {
-                    unsafe {
-                        // SAFETY: Already checked existence at instantiation and the only mutable reference
-                        // to the map is internally held.
-                        Ok(&mut (*self.core.entries.as_mut_ptr().add(inserted.index)).value)
-                    }
-                }
-                Insert::Full((_, v)) => Err(v),
-            }
-        }
+impl<'a, K, V, S, const N: usize, P: IndexPos> RawVacantEntryMut<'a, K, V, S, N, P>
+where
+    S: BuildHasher,
+{
+    /// Inserts the given key and value into the map, and yields mutable references to them.
+    /// If the map is at capacity the key and value are returned instead.
+    pub fn insert(self, key: K, value: V) -> Result<(&'a mut K, &'a mut V), (K, V)>
+    where
+        K: Hash,
+    {
+        let hash = self.build_hasher.hash_one(&key);
+        self.insert_hashed_nocheck(hash, key, value)
+    }
+
+    /// Inserts the given key and value into the map with the precomputed hash of the key, and
+    /// yields mutable references to them. If the map is at capacity the key and value are returned
+    /// instead.
+    ///
+    /// The hash must be the one computed by the map's [hasher](IndexMap::hasher) for the key,
+    /// otherwise the entry won't be found with a regular lookup.
+    pub fn insert_hashed_nocheck(
+        self,
+        hash: u64,
+        key: K,
+        value: V,
+    ) -> Result<(&'a mut K, &'a mut V), (K, V)> {
+        let index = self.core.insert_new(P::hash_value(hash), key, value)?;
+        let bucket = &mut self.core.entries[index];
+        Ok((&mut bucket.key, &mut bucket.value))
     }
 }
 
 /// Fixed capacity [`IndexMap`](https://docs.rs/indexmap/2/indexmap/map/struct.IndexMap.html)
 ///
 /// Note that you cannot use `IndexMap` directly, since it is generic around the hashing algorithm
-/// in use. Pick a concrete instantiation like [`FnvIndexMap`] instead
+/// in use. Pick a concrete instantiation like [`FnvIndexMap`] or [`Murmur3IndexMap`] instead
 /// or create your own.
 ///
-/// Note that the capacity of the `IndexMap` must be a power of 2.
+/// The capacity of the `IndexMap` can be any number greater than 1, but a power of 2 makes
+/// lookups a bit faster, since the hashes can then be reduced to a slot with a bitmask instead of
+/// a division.
+///
+/// The entries are stored densely in insertion order, next to a hash index that uses Robin Hood
+/// probing with backward-shift deletion: removals leave no tombstones behind, and probe lengths
+/// stay short even when the map is full.
+///
+/// # Hashers
+///
+/// Any [`BuildHasher`] can be used: either a [`BuildHasherDefault`](hash32::BuildHasherDefault),
+/// with which the map can be created with [`new`](Self::new), or any other one passed to
+/// [`with_hasher`](Self::with_hasher).
+///
+/// ```
+/// use core::hash::{BuildHasher, Hasher};
+/// use heapless::IndexMap;
+///
+/// // A multiplicative hasher, fast for integer keys
+/// #[derive(Default)]
+/// struct MulHasher(u64);
+///
+/// impl Hasher for MulHasher {
+///     fn write(&mut self, bytes: &[u8]) {
+///         for &b in bytes {
+///             self.write_u8(b);
+///         }
+///     }
+///
+///     fn write_u8(&mut self, i: u8) {
+///         self.write_u64(i.into());
+///     }
+///
+///     fn write_u64(&mut self, i: u64) {
+///         self.0 = (self.0.rotate_left(5) ^ i).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+///     }
+///
+///     fn finish(&self) -> u64 {
+///         // The map only uses the low bits of the hash
+///         self.0 ^ (self.0 >> 32)
+///     }
+/// }
+///
+/// struct BuildMulHasher;
+///
+/// impl BuildHasher for BuildMulHasher {
+///     type Hasher = MulHasher;
+///
+///     fn build_hasher(&self) -> MulHasher {
+///         MulHasher::default()
+///     }
+/// }
+///
+/// static mut SENSORS: IndexMap<u64, i16, BuildMulHasher, 8> = IndexMap::with_hasher(BuildMulHasher);
+///
+/// let mut sensors = IndexMap::<u64, i16, _, 8>::with_hasher(BuildMulHasher);
+/// sensors.insert(0x28ff_6402_0000_00a1, 215).unwrap();
+/// assert_eq!(sensors[&0x28ff_6402_0000_00a1], 215);
+/// ```
+///
+/// # Index size
+///
+/// The hash index holds one [`IndexPos`] per slot. The default [`Pos`] takes 4 bytes and supports
+/// up to 65535 entries; [`CompactPos`] takes 2 bytes and supports up to 255 entries. These limits
+/// are checked at compile time. [`CompactFnvIndexMap`] uses `CompactPos` with the FNV hasher.
+///
+/// ```
+/// use heapless::{CompactPos, FnvIndexMap, IndexMap};
+/// use hash32::{BuildHasherDefault, FnvHasher};
+///
+/// type SmallMap = IndexMap<u8, u8, BuildHasherDefault<FnvHasher>, 64, CompactPos>;
+///
+/// // 2 bytes saved per slot
+/// assert_eq!(
+///     core::mem::size_of::<FnvIndexMap<u8, u8, 64>>() - core::mem::size_of::<SmallMap>(),
+///     64 * 2
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use heapless::CompactFnvIndexMap;
+///
+/// // `CompactPos` can't index 256 entries
+/// let map = CompactFnvIndexMap::<u8, u8, 256>::new();
+/// ```
+///
+/// ```compile_fail
+/// use heapless::FnvIndexMap;
+///
+/// // `Pos` can't index 65536 entries
+/// let map = FnvIndexMap::<u8, u8, 65536>::new();
+/// ```
 ///
 /// # Examples
 ///
@@ -729,31 +1349,95 @@ where
 ///     println!("{}: \"{}\"", book, review);
 /// }
 /// ```
-pub struct IndexMap<K, V, S, const N: usize> {
-    core: CoreMap<K, V, N>,
+pub struct IndexMap<K, V, S, const N: usize, P: IndexPos = Pos> {
+    core: CoreMap<K, V, N, P>,
     build_hasher: S,
 }
 
-impl<K, V, S, const N: usize> IndexMap<K, V, BuildHasherDefault<S>, N> {
+impl<K, V, S, const N: usize, P: IndexPos> IndexMap<K, V, BuildHasherDefault<S>, N, P> {
     /// Creates an empty `IndexMap`.
     pub const fn new() -> Self {
+        Self::with_hasher(BuildHasherDefault::new())
+    }
+}
+
+impl<K, V, S, const N: usize, P: IndexPos> IndexMap<K, V, S, N, P> {
+    /// Creates an empty `IndexMap` that uses `build_hasher` to hash the keys.
+    pub const fn with_hasher(build_hasher: S) -> Self {
         // Const assert
         crate::sealed::greater_than_1::<N>();
-        crate::sealed::power_of_two::<N>();
+        let () = CapacityCheck::<P, N>::OK;
 
         IndexMap {
-            build_hasher: BuildHasherDefault::new(),
+            build_hasher,
             core: CoreMap::new(),
         }
     }
-}
 
-impl<K, V, S, const N: usize> IndexMap<K, V, S, N> {
     /// Returns the number of elements the map can hold
     pub fn capacity(&self) -> usize {
         N
     }
 
+    /// Returns a reference to the map's [`BuildHasher`]
+    pub fn hasher(&self) -> &S {
+        &self.build_hasher
+    }
+
+    /// Creates a raw immutable entry builder for the map
+    ///
+    /// Raw entries allow looking up a key-value pair by a precomputed hash and a custom equality,
+    /// for instance to avoid hashing a key twice, or to look up a compound key by a borrowed form
+    /// that can't implement [`Borrow`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::hash::BuildHasher;
+    /// use heapless::{FnvIndexMap, String};
+    ///
+    /// let mut map = FnvIndexMap::<(String<8>, u8), u32, 8>::new();
+    /// map.insert((String::try_from("eth").unwrap(), 0), 1500).unwrap();
+    ///
+    /// // `(String<8>, u8)` can't be borrowed as `(&str, u8)`, but both hash the same way
+    /// let hash = map.hasher().hash_one(("eth", 0u8));
+    /// let entry = map
+    ///     .raw_entry()
+    ///     .from_hash(hash, |(name, port)| name == "eth" && *port == 0);
+    /// assert_eq!(entry.map(|(_, mtu)| *mtu), Some(1500));
+    /// ```
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, S, N, P> {
+        RawEntryBuilder { map: self }
+    }
+
+    /// Creates a raw entry builder for the map, allowing to insert or modify key-value pairs
+    ///
+    /// See [`raw_entry`](Self::raw_entry) for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::hash::BuildHasher;
+    /// use heapless::{FnvIndexMap, RawEntryMut};
+    ///
+    /// let mut counts = FnvIndexMap::<u32, u32, 8>::new();
+    /// for id in [7, 3, 7] {
+    ///     // Hash the key once, for both the lookup and the insertion
+    ///     let hash = counts.hasher().hash_one(id);
+    ///     match counts.raw_entry_mut().from_key_hashed_nocheck(hash, &id) {
+    ///         RawEntryMut::Occupied(mut entry) => *entry.get_mut() += 1,
+    ///         RawEntryMut::Vacant(entry) => {
+    ///             entry.insert_hashed_nocheck(hash, id, 1).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(counts[&7], 2);
+    /// assert_eq!(counts[&3], 1);
+    /// ```
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, K, V, S, N, P> {
+        RawEntryBuilderMut { map: self }
+    }
+
     /// Return an iterator over the keys of the map, in insertion order
     ///
     /// ```
@@ -902,6 +1586,186 @@ impl<K, V, S, const N: usize> IndexMap<K, V, S, N> {
             .map(|bucket| (&bucket.key, &mut bucket.value))
     }
 
+    /// Get the key-value pair at position `index` in insertion order, or `None` if `index` is out
+    /// of bounds
+    ///
+    /// Note that removing entries with [`swap_remove`](Self::swap_remove) moves the last entry to
+    /// the position of the removed one.
+    ///
+    /// Computes in *O*(1) time
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.core
+            .entries
+            .get(index)
+            .map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    /// Get the key-value pair at position `index` in insertion order, with mutable access to the
+    /// value, or `None` if `index` is out of bounds
+    ///
+    /// Computes in *O*(1) time
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// if let Some((_, v)) = map.get_index_mut(0) {
+    ///     *v = 10;
+    /// }
+    /// assert_eq!(map["a"], 10);
+    /// ```
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.core
+            .entries
+            .get_mut(index)
+            .map(|bucket| (&bucket.key, &mut bucket.value))
+    }
+
+    /// Remove the key-value pair at position `index` by swapping it with the last pair, and
+    /// return it, or `None` if `index` is out of bounds.
+    ///
+    /// Like `Vec::swap_remove`, this perturbs the position of what used to be the last pair.
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index >= self.len() {
+            return None;
+        }
+        let probe = self.core.probe_of(index);
+        Some(self.core.remove_found(probe, index))
+    }
+
+    /// Remove the key-value pair at position `index` by shifting all the pairs that follow it,
+    /// and return it, or `None` if `index` is out of bounds.
+    ///
+    /// Like `Vec::remove`, this preserves the order of the remaining pairs.
+    ///
+    /// Computes in *O*(n) time.
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index >= self.len() {
+            return None;
+        }
+        let probe = self.core.probe_of(index);
+        Some(self.core.shift_remove_found(probe, index))
+    }
+
+    /// Remove the last key-value pair, i.e. the most recently inserted one, and return it.
+    ///
+    /// Computes in *O*(1) time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// assert_eq!(map.pop(), Some(("b", 2)));
+    /// assert_eq!(map.pop(), Some(("a", 1)));
+    /// assert_eq!(map.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.swap_remove_index(self.len().wrapping_sub(1))
+    }
+
+    /// Remove the first key-value pair, i.e. the least recently inserted one, and return it,
+    /// preserving the order of the remaining pairs.
+    ///
+    /// Computes in *O*(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// // A bounded FIFO of requests, keyed by their ID
+    /// let mut pending = FnvIndexMap::<u16, &str, 4>::new();
+    /// pending.insert(7, "read").unwrap();
+    /// pending.insert(3, "write").unwrap();
+    /// pending.insert(9, "erase").unwrap();
+    ///
+    /// // Requests can be looked up by ID, and served in order
+    /// assert_eq!(pending.get(&3), Some(&"write"));
+    /// assert_eq!(pending.shift_pop_front(), Some((7, "read")));
+    /// assert!(pending.keys().eq(&[3, 9]));
+    /// ```
+    pub fn shift_pop_front(&mut self) -> Option<(K, V)> {
+        self.shift_remove_index(0)
+    }
+
+    /// Sort the map's key-value pairs by the default ordering of the keys.
+    ///
+    /// Since the keys of a map are unique, this is equivalent to a stable sort, but it does not
+    /// allocate.
+    ///
+    /// Computes in *O*(n log n + c) time, where *c* is the capacity of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert(3, "c").unwrap();
+    /// map.insert(1, "a").unwrap();
+    /// map.insert(2, "b").unwrap();
+    /// map.sort_keys();
+    ///
+    /// assert!(map.keys().eq(&[1, 2, 3]));
+    /// assert_eq!(map.get_index_of(&3), Some(2));
+    /// ```
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.sort_unstable_by(|k1, _, k2, _| k1.cmp(k2));
+    }
+
+    /// Sort the map's key-value pairs in place using the comparison function `cmp`.
+    ///
+    /// The comparison function receives two key and value pairs to compare. The sort is not stable:
+    /// pairs that compare equal may be reordered.
+    ///
+    /// Computes in *O*(n log n + c) time, where *c* is the capacity of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 3).unwrap();
+    /// map.insert("b", 1).unwrap();
+    /// map.insert("c", 2).unwrap();
+    ///
+    /// // Sort by value, in descending order
+    /// map.sort_unstable_by(|_, v1, _, v2| v2.cmp(v1));
+    /// assert!(map.keys().eq(&["a", "c", "b"]));
+    /// assert_eq!(map["b"], 1);
+    /// ```
+    pub fn sort_unstable_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        self.core
+            .entries
+            .sort_unstable_by(|a, b| cmp(&a.key, &a.value, &b.key, &b.value));
+        // The entries moved, so rebuild the hash indices pointing to them
+        self.core.rebuild_indices();
+    }
+
     /// Return the number of key-value pairs in the map.
     ///
     /// Computes in *O*(1) time.
@@ -967,13 +1831,105 @@ impl<K, V, S, const N: usize> IndexMap<K, V, S, N> {
     /// ```
     pub fn clear(&mut self) {
         self.core.entries.clear();
-        for pos in self.core.indices.iter_mut() {
-            *pos = None;
+        self.core.clear_indices();
+    }
+
+    /// Removes the specified range of entries from the map in bulk, returning all removed
+    /// key-value pairs as an iterator, in insertion order. If the iterator is dropped before being
+    /// fully consumed, it drops the remaining removed pairs.
+    ///
+    /// The order of the remaining pairs is preserved.
+    ///
+    /// If the returned iterator is leaked, the map is left with only the pairs before the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// map.insert("c", 3).unwrap();
+    ///
+    /// assert!(map.drain(..2).eq([("a", 1), ("b", 2)]));
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map.get("c"), Some(&3));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, K, V, N, P>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = crate::slice::range(range, ..self.len());
+        let mut core = NonNull::from(&mut self.core);
+        // NOTE(unsafe) the `Drain` holds the only access to the map until it is dropped, and
+        // `iter` only accesses the entries while `core` is only used after dropping `iter`. Only
+        // the entries before the range are indexed first, as they are the ones left in the map if
+        // the `Drain` is leaked
+        unsafe {
+            core.as_mut().rebuild_indices_up_to(start);
+            let iter = core.as_mut().entries.drain(start..end);
+            Drain {
+                iter: ManuallyDrop::new(iter),
+                core,
+            }
+        }
+    }
+
+    /// Creates an iterator which uses a closure to determine if a key-value pair should be
+    /// removed, and yields the removed pairs in insertion order.
+    ///
+    /// If the closure returns `true`, the pair is removed from the map and yielded. If the closure
+    /// returns `false`, the pair remains in the map and will not be yielded by the iterator. The
+    /// order of the remaining pairs is preserved.
+    ///
+    /// If the returned `ExtractIf` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining pairs are retained. Use
+    /// [`retain`](Self::retain) with a negated predicate if you do not need the returned iterator.
+    ///
+    /// If the returned iterator is leaked, the map is left without lookup indices: it must be
+    /// cleared before being used again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{FnvIndexMap, Vec};
+    ///
+    /// let mut sessions = FnvIndexMap::<u32, u32, 8>::new();
+    /// for (id, age) in [(1, 5), (2, 50), (3, 10), (4, 70)] {
+    ///     sessions.insert(id, age).unwrap();
+    /// }
+    ///
+    /// let expired: Vec<_, 8> = sessions.extract_if(|_, &mut age| age > 30).collect();
+    /// assert_eq!(expired, [(2, 50), (4, 70)]);
+    /// assert!(sessions.keys().eq(&[1, 3]));
+    /// assert_eq!(sessions.get(&3), Some(&10));
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, N, P>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let old_len = self.core.entries.len();
+        self.core.clear_indices();
+        // NOTE(unsafe) hide the entries, so that leaking the `ExtractIf` doesn't expose entries
+        // that were already moved out
+        unsafe { self.core.entries.set_len(0) };
+        ExtractIf {
+            core: &mut self.core,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred,
         }
     }
 }
 
-impl<K, V, S, const N: usize> IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash,
     S: BuildHasher,
@@ -994,8 +1950,8 @@ where
     /// // Prints 2
     /// println!("val: {}", *map.get("a").unwrap());
     /// ```
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
-        let hash_val = hash_with(&key, &self.build_hasher);
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N, P> {
+        let hash_val = hash_with::<_, _, P>(&key, &self.build_hasher);
         if let Some((probe, pos)) = self.core.find(hash_val, &key) {
             Entry::Occupied(OccupiedEntry {
                 key,
@@ -1012,28 +1968,56 @@ where
         }
     }
 
-    /// Returns a reference to the value corresponding to the key.
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but `Hash` and `Eq` on the borrowed
+    /// form *must* match those for the key type.
+    ///
+    /// Computes in *O*(1) time (average).
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 16>::new();
+    /// map.insert(1, "a").unwrap();
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key)
+            .map(|(_, found)| unsafe { &self.core.entries.get_unchecked(found).value })
+    }
+
+    /// Returns the position in insertion order of the key-value pair corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type, but `Hash` and `Eq` on the borrowed
     /// form *must* match those for the key type.
     ///
     /// Computes in *O*(1) time (average).
     ///
+    /// # Examples
+    ///
     /// ```
     /// use heapless::FnvIndexMap;
     ///
-    /// let mut map = FnvIndexMap::<_, _, 16>::new();
-    /// map.insert(1, "a").unwrap();
-    /// assert_eq!(map.get(&1), Some(&"a"));
-    /// assert_eq!(map.get(&2), None);
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// let handle = map.get_index_of("b").unwrap();
+    /// assert_eq!(handle, 1);
+    /// assert_eq!(map.get_index(handle), Some((&"b", &2)));
+    /// assert_eq!(map.get_index_of("c"), None);
     /// ```
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
     where
         K: Borrow<Q>,
         Q: ?Sized + Hash + Eq,
     {
-        self.find(key)
-            .map(|(_, found)| unsafe { &self.core.entries.get_unchecked(found).value })
+        self.find(key).map(|(_, found)| found)
     }
 
     /// Returns true if the map contains a value for the specified key.
@@ -1092,6 +2076,53 @@ where
         }
     }
 
+    /// Returns mutable references to the values corresponding to `M` keys at once.
+    ///
+    /// Returns `None` if any of the keys is missing, or if two of the keys are equal, as the
+    /// references would then alias.
+    ///
+    /// Computes in *O*(M²) time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut sent = FnvIndexMap::<_, u32, 8>::new();
+    /// sent.insert("uplink", 1200).unwrap();
+    /// sent.insert("downlink", 300).unwrap();
+    ///
+    /// // move 100 bytes from one link to the other
+    /// if let Some([up, down]) = sent.get_many_mut(["uplink", "downlink"]) {
+    ///     *up -= 100;
+    ///     *down += 100;
+    /// }
+    /// assert_eq!(sent["uplink"], 1100);
+    /// assert_eq!(sent["downlink"], 400);
+    ///
+    /// assert_eq!(sent.get_many_mut(["uplink", "uplink"]), None);
+    /// assert_eq!(sent.get_many_mut(["uplink", "sidelink"]), None);
+    /// ```
+    pub fn get_many_mut<Q, const M: usize>(&mut self, keys: [&Q; M]) -> Option<[&mut V; M]>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let mut indices = [0; M];
+        for (i, key) in keys.into_iter().enumerate() {
+            let (_, found) = self.find(key)?;
+            if indices[..i].contains(&found) {
+                return None;
+            }
+            indices[i] = found;
+        }
+
+        let entries = self.core.entries.as_mut_ptr();
+        // NOTE(unsafe) the indices are in bounds and pairwise distinct, so the references don't
+        // alias
+        Some(indices.map(|found| unsafe { &mut (*entries.add(found)).value }))
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If an equivalent key already exists in the map: the key remains and retains in its place in
@@ -1120,7 +2151,7 @@ where
     /// assert_eq!(map[&37], "c");
     /// ```
     pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
-        let hash = hash_with(&key, &self.build_hasher);
+        let hash = hash_with::<_, _, P>(&key, &self.build_hasher);
         match self.core.insert(hash, key, value) {
             Insert::Success(inserted) => Ok(inserted.old_value),
             Insert::Full((k, v)) => Err((k, v)),
@@ -1129,6 +2160,9 @@ where
 
     /// Same as [`swap_remove`](Self::swap_remove)
     ///
+    /// This does not preserve the insertion order of the remaining pairs; use
+    /// [`shift_remove`](Self::shift_remove) when the order matters.
+    ///
     /// Computes in *O*(1) time (average).
     ///
     /// # Examples
@@ -1166,9 +2200,62 @@ where
             .map(|(probe, found)| self.core.remove_found(probe, found).1)
     }
 
+    /// Remove the key-value pair equivalent to `key` and return its value.
+    ///
+    /// Like `Vec::remove`, the pair is removed by shifting all of the elements that follow it,
+    /// preserving their relative order. **This perturbs the index of all of those elements!**
+    ///
+    /// Return `None` if `key` is not in map.
+    ///
+    /// Computes in *O*(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert(1, "a").unwrap();
+    /// map.insert(2, "b").unwrap();
+    /// map.insert(3, "c").unwrap();
+    /// assert_eq!(map.shift_remove(&1), Some("a"));
+    /// assert!(map.keys().eq(&[2, 3]));
+    ///
+    /// // `swap_remove` moves the last pair into the removed one's position
+    /// map.insert(4, "d").unwrap();
+    /// assert_eq!(map.swap_remove(&2), Some("b"));
+    /// assert!(map.keys().eq(&[4, 3]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key)
+            .map(|(probe, found)| self.core.shift_remove_found(probe, found).1)
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all pairs `(k, v)` for which `f(&k, &mut v)` returns `false`.
+    /// The order of the remaining pairs is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// // Expire the sessions that haven't been seen recently
+    /// let mut last_seen = FnvIndexMap::<u32, u32, 8>::new();
+    /// for (session, time) in [(1, 100), (2, 50), (3, 120), (4, 10)] {
+    ///     last_seen.insert(session, time).unwrap();
+    /// }
+    /// last_seen.retain(|_, &mut time| time >= 100);
+    ///
+    /// assert!(last_seen.keys().eq(&[1, 3]));
+    /// assert_eq!(last_seen.get(&3), Some(&120));
+    /// assert_eq!(last_seen.get(&2), None);
+    /// ```
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&K, &mut V) -> bool,
@@ -1186,12 +2273,12 @@ where
         if self.is_empty() {
             return None;
         }
-        let h = hash_with(key, &self.build_hasher);
+        let h = hash_with::<_, _, P>(key, &self.build_hasher);
         self.core.find(h, key)
     }
 }
 
-impl<K, Q, V, S, const N: usize> ops::Index<&Q> for IndexMap<K, V, S, N>
+impl<K, Q, V, S, const N: usize, P: IndexPos> ops::Index<&Q> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash + Borrow<Q>,
     Q: ?Sized + Eq + Hash,
@@ -1204,7 +2291,7 @@ where
     }
 }
 
-impl<K, Q, V, S, const N: usize> ops::IndexMut<&Q> for IndexMap<K, V, S, N>
+impl<K, Q, V, S, const N: usize, P: IndexPos> ops::IndexMut<&Q> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash + Borrow<Q>,
     Q: ?Sized + Eq + Hash,
@@ -1216,7 +2303,7 @@ where
 }
 
 #[cfg(feature="copy")]
-impl<K, V, S, const N: usize> Copy for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Copy for IndexMap<K, V, S, N, P>
 where
     K: Copy,
     V: Copy,
@@ -1224,7 +2311,7 @@ where
 {
 }
 
-impl<K, V, S, const N: usize> Clone for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Clone for IndexMap<K, V, S, N, P>
 where
     K: Clone,
     V: Clone,
@@ -1238,7 +2325,7 @@ where
     }
 }
 
-impl<K, V, S, const N: usize> fmt::Debug for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> fmt::Debug for IndexMap<K, V, S, N, P>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -1248,31 +2335,24 @@ where
     }
 }
 
-impl<K, V, S, const N: usize> Default for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Default for IndexMap<K, V, S, N, P>
 where
     S: Default,
 {
     fn default() -> Self {
-        // Const assert
-        crate::sealed::greater_than_1::<N>();
-        crate::sealed::power_of_two::<N>();
-
-        IndexMap {
-            build_hasher: <_>::default(),
-            core: CoreMap::new(),
-        }
+        Self::with_hasher(S::default())
     }
 }
 
-impl<K, V, S, S2, const N: usize, const N2: usize> PartialEq<IndexMap<K, V, S2, N2>>
-    for IndexMap<K, V, S, N>
+impl<K, V, S, S2, const N: usize, P: IndexPos, const N2: usize, P2: IndexPos>
+    PartialEq<IndexMap<K, V, S2, N2, P2>> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash,
     V: Eq,
     S: BuildHasher,
     S2: BuildHasher,
 {
-    fn eq(&self, other: &IndexMap<K, V, S2, N2>) -> bool {
+    fn eq(&self, other: &IndexMap<K, V, S2, N2, P2>) -> bool {
         self.len() == other.len()
             && self
                 .iter()
@@ -1280,7 +2360,7 @@ where
     }
 }
 
-impl<K, V, S, const N: usize> Eq for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Eq for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash,
     V: Eq,
@@ -1288,7 +2368,7 @@ where
 {
 }
 
-impl<K, V, S, const N: usize> Extend<(K, V)> for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Extend<(K, V)> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash,
     S: BuildHasher,
@@ -1303,7 +2383,7 @@ where
     }
 }
 
-impl<'a, K, V, S, const N: usize> Extend<(&'a K, &'a V)> for IndexMap<K, V, S, N>
+impl<'a, K, V, S, const N: usize, P: IndexPos> Extend<(&'a K, &'a V)> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash + Copy,
     V: Copy,
@@ -1317,7 +2397,7 @@ where
     }
 }
 
-impl<K, V, S, const N: usize> FromIterator<(K, V)> for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> FromIterator<(K, V)> for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash,
     S: BuildHasher + Default,
@@ -1345,7 +2425,7 @@ impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
     }
 }
 
-impl<K, V, S, const N: usize> IntoIterator for IndexMap<K, V, S, N> {
+impl<K, V, S, const N: usize, P: IndexPos> IntoIterator for IndexMap<K, V, S, N, P> {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V, N>;
 
@@ -1356,7 +2436,7 @@ impl<K, V, S, const N: usize> IntoIterator for IndexMap<K, V, S, N> {
     }
 }
 
-impl<'a, K, V, S, const N: usize> IntoIterator for &'a IndexMap<K, V, S, N> {
+impl<'a, K, V, S, const N: usize, P: IndexPos> IntoIterator for &'a IndexMap<K, V, S, N, P> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1365,7 +2445,7 @@ impl<'a, K, V, S, const N: usize> IntoIterator for &'a IndexMap<K, V, S, N> {
     }
 }
 
-impl<'a, K, V, S, const N: usize> IntoIterator for &'a mut IndexMap<K, V, S, N> {
+impl<'a, K, V, S, const N: usize, P: IndexPos> IntoIterator for &'a mut IndexMap<K, V, S, N, P> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1464,21 +2544,136 @@ impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
     }
 }
 
-fn hash_with<K, S>(key: &K, build_hasher: &S) -> HashValue
+/// A draining iterator over the items of a [`IndexMap`].
+///
+/// This `struct` is created by the [`drain`](IndexMap::drain) method on [`IndexMap`]. See its
+/// documentation for more.
+pub struct Drain<'a, K, V, const N: usize, P: IndexPos = Pos> {
+    iter: ManuallyDrop<crate::vec::Drain<'a, Bucket<K, V>>>,
+    core: NonNull<CoreMap<K, V, N, P>>,
+}
+
+impl<K, V, const N: usize, P: IndexPos> Iterator for Drain<'_, K, V, N, P> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|bucket| (bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V, const N: usize, P: IndexPos> DoubleEndedIterator for Drain<'_, K, V, N, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|bucket| (bucket.key, bucket.value))
+    }
+}
+
+impl<K, V, const N: usize, P: IndexPos> ExactSizeIterator for Drain<'_, K, V, N, P> {}
+impl<K, V, const N: usize, P: IndexPos> FusedIterator for Drain<'_, K, V, N, P> {}
+
+unsafe impl<K: Sync, V: Sync, const N: usize, P: IndexPos> Sync for Drain<'_, K, V, N, P> {}
+unsafe impl<K: Send, V: Send, const N: usize, P: IndexPos> Send for Drain<'_, K, V, N, P> {}
+
+impl<K, V, const N: usize, P: IndexPos> Drop for Drain<'_, K, V, N, P> {
+    fn drop(&mut self) {
+        // NOTE(unsafe) `iter` is not used after being dropped, and it doesn't access the map
+        // afterwards
+        unsafe {
+            ManuallyDrop::drop(&mut self.iter);
+            self.core.as_mut().rebuild_indices();
+        }
+    }
+}
+
+/// An iterator which uses a closure to determine if a key-value pair of a [`IndexMap`] should be
+/// removed.
+///
+/// This `struct` is created by the [`extract_if`](IndexMap::extract_if) method on [`IndexMap`].
+/// See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, K, V, F, const N: usize, P: IndexPos = Pos> {
+    core: &'a mut CoreMap<K, V, N, P>,
+    /// The index of the entry that will be inspected by the next call to `next`.
+    idx: usize,
+    /// The number of entries that have been removed thus far.
+    del: usize,
+    /// The number of entries prior to extracting.
+    old_len: usize,
+    pred: F,
+}
+
+impl<K, V, F, const N: usize, P: IndexPos> Iterator for ExtractIf<'_, K, V, F, N, P>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // NOTE(unsafe) the entries before `idx - del` and from `idx` to `old_len` are initialized
+        unsafe {
+            let entries = self.core.entries.as_mut_ptr();
+            while self.idx < self.old_len {
+                let i = self.idx;
+                let bucket = &mut *entries.add(i);
+                let extracted = (self.pred)(&bucket.key, &mut bucket.value);
+                // Update the index *after* the predicate is called, so that the entry isn't
+                // leaked if the predicate panics.
+                self.idx += 1;
+                if extracted {
+                    self.del += 1;
+                    let bucket = ptr::read(entries.add(i));
+                    return Some((bucket.key, bucket.value));
+                } else if self.del > 0 {
+                    ptr::copy_nonoverlapping(entries.add(i), entries.add(i - self.del), 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<K, V, F, const N: usize, P: IndexPos> Drop for ExtractIf<'_, K, V, F, N, P> {
+    fn drop(&mut self) {
+        // NOTE(unsafe) close the gap left by the removed entries, then restore the length
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let src = self.core.entries.as_mut_ptr().add(self.idx);
+                ptr::copy(src, src.sub(self.del), self.old_len - self.idx);
+            }
+            self.core.entries.set_len(self.old_len - self.del);
+        }
+        self.core.rebuild_indices();
+    }
+}
+
+fn hash_with<K, S, P>(key: &K, build_hasher: &S) -> HashValue
 where
     K: ?Sized + Hash,
     S: BuildHasher,
+    P: IndexPos,
 {
-    HashValue(build_hasher.hash_one(key) as u16)
+    P::hash_value(build_hasher.hash_one(key))
 }
 
 #[cfg(test)]
 mod tests {
-    use core::mem;
+    use core::{hash::BuildHasher, mem};
 
     use static_assertions::assert_not_impl_any;
 
-    use super::{BuildHasherDefault, Entry, FnvIndexMap, IndexMap};
+    use super::{
+        BuildHasherDefault, CompactFnvIndexMap, Entry, FnvIndexMap, IndexMap, IndexPos,
+        Murmur3IndexMap, RawEntryMut, SealedIndexPos,
+    };
 
     // Ensure a `IndexMap` containing `!Send` keys stays `!Send` itself.
     assert_not_impl_any!(IndexMap<*const (), (), BuildHasherDefault<()>, 4>: Send);
@@ -1594,6 +2789,26 @@ mod tests {
         assert_eq!(a["k2"], "default v2");
     }
 
+    #[test]
+    fn entry_and_modify_full() {
+        let mut a: FnvIndexMap<_, _, 2> = FnvIndexMap::new();
+        a.insert("k1", 1).unwrap();
+        a.insert("k2", 2).unwrap();
+
+        let v = a.entry("k1").and_modify(|e| *e += 10).or_insert(0).unwrap();
+        assert_eq!(*v, 11);
+
+        let mut called = false;
+        let result = a
+            .entry("k3")
+            .and_modify(|_| called = true)
+            .or_insert_with(|| 3);
+        assert_eq!(result, Err(3));
+        assert!(!called);
+        assert_eq!(a.entry("k3").or_default(), Err(0));
+        assert_eq!(a.len(), 2);
+    }
+
     #[test]
     fn entry_or_default() {
         let mut a: FnvIndexMap<&str, Option<u32>, 2> = FnvIndexMap::new();
@@ -1776,6 +2991,427 @@ mod tests {
         assert_eq!(odd.len(), (MAP_SLOTS / 2) + 1);
     }
 
+    fn check_robin_hood<K, V, S, const N: usize, P: IndexPos>(map: &IndexMap<K, V, S, N, P>) {
+        let indices = &map.core.indices;
+        assert_eq!(indices.iter().flatten().count(), map.len());
+        for probe in 0..N {
+            if let Some(pos) = indices[probe] {
+                let dist = pos.hash().probe_distance(N, probe);
+                if dist > 0 {
+                    let prev = indices[(probe + N - 1) % N].unwrap();
+                    assert!(prev.hash().probe_distance(N, (probe + N - 1) % N) + 1 >= dist);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn robin_hood_churn() {
+        let mut map = FnvIndexMap::<u32, u32, 64>::new();
+        // Keep the map between 80% and 100% full, replacing the entries many times over
+        let mut seed = 1u32;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+        for _ in 0..2000 {
+            if map.len() < 52 || (map.len() < 64 && next() & 1 == 0) {
+                let k = next();
+                map.insert(k, k).unwrap();
+            } else {
+                let index = next() as usize % map.len();
+                let k = *map.get_index(index).unwrap().0;
+                if k & 1 == 0 {
+                    assert_eq!(map.swap_remove(&k), Some(k));
+                } else {
+                    assert_eq!(map.shift_remove(&k), Some(k));
+                }
+            }
+            check_robin_hood(&map);
+        }
+        for (k, v) in map.iter() {
+            assert_eq!(map.get(k), Some(v));
+        }
+    }
+
+    #[test]
+    fn get_many_mut() {
+        let mut map = FnvIndexMap::<u8, u32, 8>::new();
+        for i in 0..5 {
+            map.insert(i, u32::from(i) * 10).unwrap();
+        }
+
+        let [a, b, c] = map.get_many_mut([&4, &0, &2]).unwrap();
+        mem::swap(a, b);
+        *c += 1;
+        assert!(map.values().eq(&[40, 10, 21, 30, 0]));
+
+        assert_eq!(map.get_many_mut([&1, &5]), None);
+        assert_eq!(map.get_many_mut([&3, &1, &3]), None);
+        assert_eq!(map.get_many_mut::<u8, 0>([]), Some([]));
+    }
+
+    // The largest capacities are accepted
+    const _: () = super::CapacityCheck::<super::Pos, 65535>::OK;
+    const _: () = super::CapacityCheck::<super::CompactPos, 255>::OK;
+
+    #[test]
+    fn pos_limits() {
+        assert_eq!(mem::size_of::<Option<super::Pos>>(), 4);
+        for index in [0, 1, 0xfffe] {
+            for hash in [0, 1, 0xffff] {
+                let pos = super::Pos::new(index, super::HashValue(hash));
+                assert_eq!(pos.index(), index);
+                assert_eq!(pos.hash().0, hash);
+            }
+        }
+    }
+
+    #[test]
+    fn compact_pos() {
+        assert_eq!(mem::size_of::<Option<super::CompactPos>>(), 2);
+        assert!(
+            mem::size_of::<CompactFnvIndexMap<u8, u8, 64>>()
+                < mem::size_of::<FnvIndexMap<u8, u8, 64>>()
+        );
+
+        // Every index and every truncated hash round-trips
+        for index in 0..255 {
+            for hash in [0, 1, 0x7f, 0xff] {
+                let pos = super::CompactPos::new(index, super::HashValue(hash));
+                assert_eq!(pos.index(), index);
+                assert_eq!(pos.hash().0, hash);
+            }
+        }
+
+        let mut map = CompactFnvIndexMap::<u32, u32, 255>::new();
+        for i in 0..255 {
+            assert_eq!(map.insert(i * 3, i), Ok(None));
+        }
+        assert_eq!(map.insert(1, 1), Err((1, 1)));
+        check_robin_hood(&map);
+        for i in (0..255).step_by(2) {
+            assert_eq!(map.swap_remove(&(i * 3)), Some(i));
+        }
+        for i in (1..255).step_by(4) {
+            assert_eq!(map.shift_remove(&(i * 3)), Some(i));
+        }
+        check_robin_hood(&map);
+        for i in 0..255 {
+            let expected = if i % 4 == 3 { Some(&i) } else { None };
+            assert_eq!(map.get(&(i * 3)), expected);
+        }
+
+        let mut small = CompactFnvIndexMap::<&str, u8, 3>::new();
+        small.insert("a", 1).unwrap();
+        small.insert("b", 2).unwrap();
+        small.insert("c", 3).unwrap();
+        assert_eq!(small.insert("d", 4), Err(("d", 4)));
+        assert_eq!(small.get("b"), Some(&2));
+    }
+
+    #[test]
+    fn non_power_of_two() {
+        let mut map = FnvIndexMap::<u32, u32, 100>::new();
+        for i in 0..100 {
+            assert_eq!(map.insert(i * 7, i), Ok(None));
+        }
+        assert_eq!(map.insert(1, 1), Err((1, 1)));
+        for i in 0..100 {
+            assert_eq!(map.get(&(i * 7)), Some(&i));
+        }
+
+        for i in (0..100).step_by(3) {
+            assert_eq!(map.swap_remove(&(i * 7)), Some(i));
+        }
+        for i in (1..100).step_by(3) {
+            assert_eq!(map.shift_remove(&(i * 7)), Some(i));
+        }
+        for i in 0..100 {
+            let expected = if i % 3 == 2 { Some(&i) } else { None };
+            assert_eq!(map.get(&(i * 7)), expected);
+        }
+
+        // Fill the map again
+        for i in 0..67 {
+            assert_eq!(map.insert(i * 7 + 1, i), Ok(None));
+        }
+        assert_eq!(map.len(), 100);
+        for i in 0..67 {
+            assert_eq!(map.get(&(i * 7 + 1)), Some(&i));
+        }
+    }
+
+    #[test]
+    fn murmur3() {
+        let mut map = Murmur3IndexMap::<u64, usize, 16>::new();
+        for i in 0..16 {
+            map.insert(u64::MAX - i as u64, i).unwrap();
+        }
+        for i in 0..16 {
+            assert_eq!(map.get(&(u64::MAX - i as u64)), Some(&i));
+            assert_eq!(map.get_index_of(&(u64::MAX - i as u64)), Some(i));
+        }
+        assert_eq!(map.get(&0), None);
+    }
+
+    #[test]
+    fn raw_entry() {
+        let mut map = FnvIndexMap::<usize, usize, 16>::new();
+        for i in 0..15 {
+            let hash = map.hasher().hash_one(i);
+            match map.raw_entry_mut().from_key_hashed_nocheck(hash, &i) {
+                RawEntryMut::Occupied(_) => panic!(),
+                RawEntryMut::Vacant(v) => {
+                    assert_eq!(v.insert(i, i * 2), Ok((&mut { i }, &mut (i * 2))))
+                }
+            }
+        }
+        for i in 0..15 {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+            assert_eq!(map.raw_entry().from_key(&i), Some((&i, &(i * 2))));
+        }
+        assert_eq!(map.raw_entry().from_key(&15), None);
+
+        // Insert the last entry through `or_insert_with`
+        let (k, v) = map
+            .raw_entry_mut()
+            .from_key(&15)
+            .and_modify(|_, _| panic!())
+            .or_insert_with(|| (15, 30))
+            .unwrap();
+        assert_eq!((*k, *v), (15, 30));
+        match map.raw_entry_mut().from_key(&16) {
+            RawEntryMut::Occupied(_) => panic!(),
+            RawEntryMut::Vacant(v) => assert_eq!(v.insert(16, 0), Err((16, 0))),
+        }
+
+        let hash = map.hasher().hash_one(3usize);
+        match map.raw_entry_mut().from_hash(hash, |k| *k == 3) {
+            RawEntryMut::Occupied(mut o) => {
+                assert_eq!(o.index(), 3);
+                assert_eq!(o.insert(0), 6);
+                assert_eq!(o.shift_remove_entry(), (3, 0));
+            }
+            RawEntryMut::Vacant(_) => panic!(),
+        }
+        assert_eq!(map.get(&3), None);
+        assert_eq!(map.get_index_of(&4), Some(3));
+    }
+
+    #[test]
+    fn sort() {
+        let mut map = almost_filled_map();
+        map.sort_unstable_by(|k1, _, k2, _| k2.cmp(k1));
+        for (index, (k, v)) in map.iter().enumerate() {
+            assert_eq!(*k, MAP_SLOTS - 1 - index);
+            assert_eq!(k, v);
+            assert_eq!(map.get_index_of(k), Some(index));
+        }
+
+        map.sort_keys();
+        for (index, (k, v)) in map.iter().enumerate() {
+            assert_eq!(*k, index + 1);
+            assert_eq!(map.get(k), Some(v));
+        }
+        assert_eq!(map.insert(0, 0), Ok(None));
+        assert_eq!(map.get_index_of(&0), Some(MAP_SLOTS - 1));
+    }
+
+    #[test]
+    fn shift_remove() {
+        let mut map = almost_filled_map();
+        for i in (1..MAP_SLOTS).step_by(2) {
+            assert_eq!(map.shift_remove(&i), Some(i));
+        }
+        assert_eq!(map.shift_remove(&1), None);
+        assert_eq!(map.len(), (MAP_SLOTS - 1) / 2);
+        for (index, (k, v)) in map.iter().enumerate() {
+            assert_eq!(*k, 2 * (index + 1));
+            assert_eq!(k, v);
+            assert_eq!(map.get_index_of(k), Some(index));
+            assert_eq!(map.get(k), Some(v));
+        }
+
+        match map.entry(2) {
+            Entry::Occupied(o) => assert_eq!(o.shift_remove_entry(), (2, 2)),
+            Entry::Vacant(_) => panic!(),
+        }
+        assert_eq!(map.first(), Some((&4, &4)));
+        assert_eq!(map.get_index_of(&6), Some(1));
+    }
+
+    #[test]
+    fn remove_index() {
+        let mut map = FnvIndexMap::<usize, usize, 16>::new();
+        for i in 0..16 {
+            map.insert(i, i).unwrap();
+        }
+        assert_eq!(map.swap_remove_index(16), None);
+        assert_eq!(map.shift_remove_index(16), None);
+
+        assert_eq!(map.swap_remove_index(1), Some((1, 1)));
+        assert_eq!(map.shift_remove_index(2), Some((2, 2)));
+        assert_eq!(map.pop(), Some((14, 14)));
+        assert_eq!(map.shift_pop_front(), Some((0, 0)));
+        assert!(map.keys().eq(&[15, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]));
+        for (index, (k, v)) in map.iter().enumerate() {
+            assert_eq!(map.get_index_of(k), Some(index));
+            assert_eq!(map.get(k), Some(v));
+        }
+
+        while map.shift_pop_front().is_some() {}
+        assert!(map.is_empty());
+        assert_eq!(map.pop(), None);
+        assert_eq!(map.shift_pop_front(), None);
+        assert_eq!(map.insert(0, 0), Ok(None));
+    }
+
+    #[test]
+    fn get_index() {
+        let mut map = almost_filled_map();
+        for i in 0..MAP_SLOTS - 1 {
+            assert_eq!(map.get_index(i), Some((&(i + 1), &(i + 1))));
+            assert_eq!(map.get_index_of(&(i + 1)), Some(i));
+        }
+        assert_eq!(map.get_index(MAP_SLOTS - 1), None);
+        assert_eq!(map.get_index_mut(MAP_SLOTS - 1), None);
+        assert_eq!(map.get_index_of(&0), None);
+
+        // The last entry takes the place of a removed one
+        map.swap_remove(&1);
+        assert_eq!(map.get_index_of(&(MAP_SLOTS - 1)), Some(0));
+        *map.get_index_mut(0).unwrap().1 = 0;
+        assert_eq!(map[&(MAP_SLOTS - 1)], 0);
+    }
+
+    #[test]
+    fn drain() {
+        const SLOTS: usize = 16;
+        for start in 0..SLOTS {
+            for end in start..SLOTS {
+                let mut map: FnvIndexMap<usize, usize, SLOTS> =
+                    (1..SLOTS).map(|k| (k, k)).collect();
+                let drained: std::vec::Vec<_> = map.drain(start..end).collect();
+                let expected: std::vec::Vec<_> = (start + 1..end + 1).map(|k| (k, k)).collect();
+                assert_eq!(drained, expected);
+                assert_eq!(map.len(), SLOTS - 1 - (end - start));
+                for k in 1..SLOTS {
+                    let drained = k > start && k <= end;
+                    assert_eq!(map.get(&k), if drained { None } else { Some(&k) });
+                }
+                assert_eq!(map.insert(0, 0), Ok(None));
+                assert_eq!(map.get(&0), Some(&0));
+            }
+        }
+
+        // Dropping the iterator early still removes the whole range
+        let mut map = almost_filled_map();
+        let mut drain = map.drain(2..6);
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next(), Some((3, 3)));
+        assert_eq!(drain.next_back(), Some((6, 6)));
+        mem::drop(drain);
+        assert!(map.keys().copied().eq((1..3).chain(7..MAP_SLOTS)));
+        assert_eq!(map.get(&7), Some(&7));
+
+        // A leaked iterator leaves the pairs before the range in a usable map
+        let mut map = almost_filled_map();
+        mem::forget(map.drain(3..6));
+        assert!(map.keys().copied().eq(1..4));
+        for k in 1..MAP_SLOTS {
+            assert_eq!(map.get(&k), if k < 4 { Some(&k) } else { None });
+        }
+        assert_eq!(map.insert(2, 20), Ok(Some(2)));
+        assert_eq!(map.insert(5, 5), Ok(None));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.swap_remove(&1), Some(1));
+        assert!(map.keys().copied().eq([5, 2, 3]));
+
+        let mut map = almost_filled_map();
+        mem::forget(map.drain(..));
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.insert(1, 1), Ok(None));
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut map = almost_filled_map();
+        let odd: std::vec::Vec<_> = map.extract_if(|&k, _| k % 2 == 1).collect();
+        assert!(odd.iter().map(|&(k, _)| k).eq((1..MAP_SLOTS).step_by(2)));
+        assert!(map.keys().copied().eq((2..MAP_SLOTS).step_by(2)));
+        for k in 1..MAP_SLOTS {
+            assert_eq!(map.get(&k), if k % 2 == 0 { Some(&k) } else { None });
+        }
+
+        // Dropping the iterator early keeps the unvisited pairs
+        let mut map = almost_filled_map();
+        let mut iter = map.extract_if(|_, v| {
+            *v += 100;
+            *v % 3 == 0
+        });
+        assert_eq!(iter.next(), Some((2, 102)));
+        mem::drop(iter);
+        assert_eq!(map.len(), MAP_SLOTS - 2);
+        assert_eq!(map.get(&1), Some(&101));
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.get(&3), Some(&3));
+        assert!(map.keys().copied().eq((1..2).chain(3..MAP_SLOTS)));
+
+        let mut map = almost_filled_map();
+        assert_eq!(map.extract_if(|_, _| false).count(), 0);
+        assert_eq!(map.len(), MAP_SLOTS - 1);
+        assert_eq!(map.extract_if(|_, _| true).count(), MAP_SLOTS - 1);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn drain_extract_if_drop() {
+        droppable!();
+
+        let mut map = FnvIndexMap::<_, _, 8>::new();
+        for i in 0..6 {
+            map.insert(i, Droppable::new()).ok().unwrap();
+        }
+        assert_eq!(Droppable::count(), 6);
+
+        mem::drop(map.extract_if(|k, _| k % 2 == 0).next());
+        assert_eq!(Droppable::count(), 5);
+        map.extract_if(|k, _| k % 2 == 0).for_each(mem::drop);
+        assert_eq!(Droppable::count(), 3);
+
+        let mut drain = map.drain(1..);
+        mem::drop(drain.next());
+        assert_eq!(Droppable::count(), 2);
+        mem::drop(drain);
+        assert_eq!(Droppable::count(), 1);
+        assert!(map.keys().eq(&[1]));
+        mem::drop(map);
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn retain_lookups() {
+        let mut map = almost_filled_map();
+        map.retain(|&k, _| k % 3 != 0);
+        let kept: std::vec::Vec<_> = map.keys().copied().collect();
+        assert!(kept.windows(2).all(|w| w[0] < w[1]));
+        for k in 1..MAP_SLOTS {
+            assert_eq!(map.get(&k), if k % 3 != 0 { Some(&k) } else { None });
+        }
+
+        // Removing entries after a retain keeps the indices consistent
+        for &k in &kept {
+            assert_eq!(map.remove(&k), Some(k));
+            assert_eq!(map.get(&k), None);
+        }
+        assert!(map.is_empty());
+    }
+
     #[test]
     fn entry_roll_through_all() {
         let mut src: FnvIndexMap<usize, usize, MAP_SLOTS> = FnvIndexMap::new();
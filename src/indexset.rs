@@ -1,12 +1,16 @@
 use core::{
     borrow::Borrow,
+    cmp::Ordering,
     fmt,
     hash::{BuildHasher, Hash},
+    iter::FusedIterator,
+    mem,
+    ops::{self, RangeBounds},
 };
 
-use hash32::{BuildHasherDefault, FnvHasher};
+use hash32::{BuildHasherDefault, FnvHasher, Murmur3Hasher};
 
-use crate::indexmap::{self, IndexMap};
+use crate::indexmap::{self, CompactPos, IndexMap, IndexPos, Pos, RawEntryMut};
 
 /// An [`IndexSet`] using the default FNV hasher.
 ///
@@ -44,13 +48,51 @@ use crate::indexmap::{self, IndexMap};
 /// ```
 pub type FnvIndexSet<T, const N: usize> = IndexSet<T, BuildHasherDefault<FnvHasher>, N>;
 
+/// An [`IndexSet`] using the Murmur3 hasher.
+///
+/// Murmur3 hashes its input 4 bytes at a time, which makes it faster than FNV for values longer
+/// than a few bytes.
+///
+/// A list of all Methods and Traits available for `Murmur3IndexSet` can be found in
+/// the [`IndexSet`] documentation.
+///
+/// # Examples
+/// ```
+/// use heapless::Murmur3IndexSet;
+///
+/// let mut peers = Murmur3IndexSet::<[u8; 16], 16>::new();
+/// peers.insert([0xfe; 16]).unwrap();
+/// assert!(peers.contains(&[0xfe; 16]));
+/// ```
+pub type Murmur3IndexSet<T, const N: usize> = IndexSet<T, BuildHasherDefault<Murmur3Hasher>, N>;
+
+/// An [`IndexSet`] using the default FNV hasher and [`CompactPos`] indices.
+///
+/// Halves the RAM used by the hash indices, at the cost of a capacity limited to 255 values.
+///
+/// A list of all Methods and Traits available for `CompactFnvIndexSet` can be found in
+/// the [`IndexSet`] documentation.
+///
+/// # Examples
+/// ```
+/// use heapless::CompactFnvIndexSet;
+///
+/// let mut seen = CompactFnvIndexSet::<u8, 32>::new();
+/// assert!(seen.insert(7).unwrap());
+/// assert!(!seen.insert(7).unwrap());
+/// ```
+pub type CompactFnvIndexSet<T, const N: usize> =
+    IndexSet<T, BuildHasherDefault<FnvHasher>, N, CompactPos>;
+
 /// Fixed capacity [`IndexSet`](https://docs.rs/indexmap/2/indexmap/set/struct.IndexSet.html).
 ///
 /// Note that you cannot use `IndexSet` directly, since it is generic around the hashing algorithm
-/// in use. Pick a concrete instantiation like [`FnvIndexSet`] instead
-/// or create your own.
+/// in use. Pick a concrete instantiation like [`FnvIndexSet`] or [`Murmur3IndexSet`] instead
+/// or create your own, see [`IndexMap`](crate::IndexMap#hashers).
 ///
-/// Note that the capacity of the `IndexSet` must be a power of 2.
+/// The capacity of the `IndexSet` can be any number greater than 1, but a power of 2 makes
+/// lookups a bit faster. Sets of up to 255 values can use [`CompactPos`] indices, as in
+/// [`CompactFnvIndexSet`], to save 2 bytes per slot; see [`IndexMap`](crate::IndexMap#index-size).
 ///
 /// # Examples
 /// Since `IndexSet` cannot be used directly, we're using its `FnvIndexSet` instantiation
@@ -84,11 +126,11 @@ pub type FnvIndexSet<T, const N: usize> = IndexSet<T, BuildHasherDefault<FnvHash
 ///     println!("{}", book);
 /// }
 /// ```
-pub struct IndexSet<T, S, const N: usize> {
-    map: IndexMap<T, (), S, N>,
+pub struct IndexSet<T, S, const N: usize, P: IndexPos = Pos> {
+    map: IndexMap<T, (), S, N, P>,
 }
 
-impl<T, S, const N: usize> IndexSet<T, BuildHasherDefault<S>, N> {
+impl<T, S, const N: usize, P: IndexPos> IndexSet<T, BuildHasherDefault<S>, N, P> {
     /// Creates an empty `IndexSet`
     pub const fn new() -> Self {
         IndexSet {
@@ -97,7 +139,19 @@ impl<T, S, const N: usize> IndexSet<T, BuildHasherDefault<S>, N> {
     }
 }
 
-impl<T, S, const N: usize> IndexSet<T, S, N> {
+impl<T, S, const N: usize, P: IndexPos> IndexSet<T, S, N, P> {
+    /// Creates an empty `IndexSet` that uses `build_hasher` to hash the values.
+    pub const fn with_hasher(build_hasher: S) -> Self {
+        IndexSet {
+            map: IndexMap::with_hasher(build_hasher),
+        }
+    }
+
+    /// Returns a reference to the set's [`BuildHasher`]
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
     /// Returns the number of elements the set can hold
     ///
     /// # Examples
@@ -148,6 +202,110 @@ impl<T, S, const N: usize> IndexSet<T, S, N> {
         self.map.last().map(|(k, _v)| k)
     }
 
+    /// Get the value at position `index` in insertion order, or `None` if `index` is out of
+    /// bounds
+    ///
+    /// Computes in *O*(1) time
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let set: FnvIndexSet<_, 16> = ["a", "b"].into_iter().collect();
+    /// assert_eq!(set.get_index(1), Some(&"b"));
+    /// assert_eq!(set.get_index(2), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.map.get_index(index).map(|(k, _v)| k)
+    }
+
+    /// Remove the value at position `index` by swapping it with the last value, and return it, or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<T> {
+        self.map.swap_remove_index(index).map(|(k, _v)| k)
+    }
+
+    /// Remove the value at position `index` by shifting all the values that follow it, and return
+    /// it, or `None` if `index` is out of bounds.
+    ///
+    /// Computes in *O*(n) time.
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<T> {
+        self.map.shift_remove_index(index).map(|(k, _v)| k)
+    }
+
+    /// Remove the last value, i.e. the most recently inserted one, and return it.
+    ///
+    /// Computes in *O*(1) time (average).
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2].iter().cloned().collect();
+    /// assert_eq!(set.pop(), Some(2));
+    /// assert!(set.iter().eq(&[1]));
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.map.pop().map(|(k, _v)| k)
+    }
+
+    /// Remove the first value, i.e. the least recently inserted one, and return it, preserving
+    /// the order of the remaining values.
+    ///
+    /// Computes in *O*(n) time.
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.shift_pop_front(), Some(1));
+    /// assert!(set.iter().eq(&[2, 3]));
+    /// ```
+    pub fn shift_pop_front(&mut self) -> Option<T> {
+        self.map.shift_pop_front().map(|(k, _v)| k)
+    }
+
+    /// Sort the set's values by their default ordering.
+    ///
+    /// Since the values of a set are unique, this is equivalent to a stable sort, but it does not
+    /// allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [3, 1, 2].iter().cloned().collect();
+    /// set.sort();
+    /// assert!(set.iter().eq(&[1, 2, 3]));
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.map.sort_keys();
+    }
+
+    /// Sort the set's values in place using the comparison function `cmp`.
+    ///
+    /// The sort is not stable: values that compare equal may be reordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [3, 1, 2].iter().cloned().collect();
+    /// set.sort_unstable_by(|a, b| b.cmp(a));
+    /// assert!(set.iter().eq(&[3, 2, 1]));
+    /// ```
+    pub fn sort_unstable_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.map.sort_unstable_by(|a, _, b, _| cmp(a, b));
+    }
+
     /// Returns the number of elements in the set.
     ///
     /// # Examples
@@ -214,9 +372,43 @@ impl<T, S, const N: usize> IndexSet<T, S, N> {
     pub fn clear(&mut self) {
         self.map.clear()
     }
+
+    /// Removes the specified range of values from the set in bulk, returning all removed values
+    /// as an iterator, in insertion order. If the iterator is dropped before being fully consumed,
+    /// it drops the remaining removed values.
+    ///
+    /// The order of the remaining values is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// // Identifiers in the order they were seen, oldest first
+    /// let mut seen: FnvIndexSet<u32, 8> = [10, 11, 12, 13].iter().cloned().collect();
+    ///
+    /// // Expire the two oldest ones
+    /// assert!(seen.drain(..2).eq([10, 11]));
+    /// assert!(seen.iter().eq(&[12, 13]));
+    /// assert!(!seen.contains(&10));
+    /// assert!(seen.contains(&13));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N, P>
+    where
+        R: RangeBounds<usize>,
+    {
+        Drain {
+            iter: self.map.drain(range),
+        }
+    }
 }
 
-impl<T, S, const N: usize> IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> IndexSet<T, S, N, P>
 where
     T: Eq + Hash,
     S: BuildHasher,
@@ -245,10 +437,10 @@ where
     /// let diff: FnvIndexSet<_, 16> = b.difference(&a).collect();
     /// assert_eq!(diff, [4].iter().collect::<FnvIndexSet<_, 16>>());
     /// ```
-    pub fn difference<'a, S2, const N2: usize>(
+    pub fn difference<'a, S2, const N2: usize, P2: IndexPos>(
         &'a self,
-        other: &'a IndexSet<T, S2, N2>,
-    ) -> Difference<'a, T, S2, N2>
+        other: &'a IndexSet<T, S2, N2, P2>,
+    ) -> Difference<'a, T, S2, N2, P2>
     where
         S2: BuildHasher,
     {
@@ -280,9 +472,9 @@ where
     /// assert_eq!(diff1, diff2);
     /// assert_eq!(diff1, [1, 4].iter().collect::<FnvIndexSet<_, 16>>());
     /// ```
-    pub fn symmetric_difference<'a, S2, const N2: usize>(
+    pub fn symmetric_difference<'a, S2, const N2: usize, P2: IndexPos>(
         &'a self,
-        other: &'a IndexSet<T, S2, N2>,
+        other: &'a IndexSet<T, S2, N2, P2>,
     ) -> impl Iterator<Item = &'a T>
     where
         S2: BuildHasher,
@@ -309,10 +501,10 @@ where
     /// let intersection: FnvIndexSet<_, 16> = a.intersection(&b).collect();
     /// assert_eq!(intersection, [2, 3].iter().collect::<FnvIndexSet<_, 16>>());
     /// ```
-    pub fn intersection<'a, S2, const N2: usize>(
+    pub fn intersection<'a, S2, const N2: usize, P2: IndexPos>(
         &'a self,
-        other: &'a IndexSet<T, S2, N2>,
-    ) -> Intersection<'a, T, S2, N2>
+        other: &'a IndexSet<T, S2, N2, P2>,
+    ) -> Intersection<'a, T, S2, N2, P2>
     where
         S2: BuildHasher,
     {
@@ -341,9 +533,9 @@ where
     /// let union: FnvIndexSet<_, 16> = a.union(&b).collect();
     /// assert_eq!(union, [1, 2, 3, 4].iter().collect::<FnvIndexSet<_, 16>>());
     /// ```
-    pub fn union<'a, S2, const N2: usize>(
+    pub fn union<'a, S2, const N2: usize, P2: IndexPos>(
         &'a self,
-        other: &'a IndexSet<T, S2, N2>,
+        other: &'a IndexSet<T, S2, N2, P2>,
     ) -> impl Iterator<Item = &'a T>
     where
         S2: BuildHasher,
@@ -351,6 +543,28 @@ where
         self.iter().chain(other.difference(self))
     }
 
+    /// Returns the position in insertion order of a value.
+    ///
+    /// The value may be any borrowed form of the set's value type, but `Hash` and `Eq` on the
+    /// borrowed form must match those for the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.get_index_of(&2), Some(1));
+    /// assert_eq!(set.get_index_of(&4), None);
+    /// ```
+    pub fn get_index_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.get_index_of(value)
+    }
+
     /// Returns `true` if the set contains a value.
     ///
     /// The value may be any borrowed form of the set's value type, but `Hash` and `Eq` on the
@@ -390,7 +604,10 @@ where
     /// b.insert(1).unwrap();
     /// assert_eq!(a.is_disjoint(&b), false);
     /// ```
-    pub fn is_disjoint<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
+    pub fn is_disjoint<S2, const N2: usize, P2: IndexPos>(
+        &self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) -> bool
     where
         S2: BuildHasher,
     {
@@ -414,7 +631,10 @@ where
     /// set.insert(4).unwrap();
     /// assert_eq!(set.is_subset(&sup), false);
     /// ```
-    pub fn is_subset<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
+    pub fn is_subset<S2, const N2: usize, P2: IndexPos>(
+        &self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) -> bool
     where
         S2: BuildHasher,
     {
@@ -441,7 +661,10 @@ where
     /// set.insert(2).unwrap();
     /// assert_eq!(set.is_superset(&sub), true);
     /// ```
-    pub fn is_superset<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
+    pub fn is_superset<S2, const N2: usize, P2: IndexPos>(
+        &self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) -> bool
     where
         S2: BuildHasher,
     {
@@ -472,11 +695,46 @@ where
             .map_err(|(k, _)| k)
     }
 
+    /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
+    /// one, without changing its position. Returns the replaced value.
+    ///
+    /// If the set is full and doesn't contain an equal value, the value is returned as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set = FnvIndexSet::<_, 16>::new();
+    /// set.insert(Vec::<i32>::new()).unwrap();
+    ///
+    /// assert_eq!(set.get_index(0).unwrap().capacity(), 0);
+    /// assert_eq!(set.replace(Vec::with_capacity(10)), Ok(Some(Vec::new())));
+    /// assert_eq!(set.get_index(0).unwrap().capacity(), 10);
+    /// ```
+    pub fn replace(&mut self, value: T) -> Result<Option<T>, T> {
+        let hash = self.map.hasher().hash_one(&value);
+        match self
+            .map
+            .raw_entry_mut()
+            .from_key_hashed_nocheck(hash, &value)
+        {
+            RawEntryMut::Occupied(mut entry) => Ok(Some(mem::replace(entry.key_mut(), value))),
+            RawEntryMut::Vacant(entry) => entry
+                .insert_hashed_nocheck(hash, value, ())
+                .map(|_| None)
+                .map_err(|(k, _)| k),
+        }
+    }
+
     /// Removes a value from the set. Returns `true` if the value was present in the set.
     ///
     /// The value may be any borrowed form of the set's value type, but `Hash` and `Eq` on the
     /// borrowed form must match those for the value type.
     ///
+    /// Same as [`swap_remove`](Self::swap_remove). This does not preserve the insertion order of
+    /// the remaining values; use [`shift_remove`](Self::shift_remove) when the order matters.
+    ///
     /// # Examples
     ///
     /// ```
@@ -496,26 +754,206 @@ where
         self.map.remove(value).is_some()
     }
 
+    /// Removes a value from the set by swapping it with the last value. Returns `true` if the
+    /// value was present in the set.
+    ///
+    /// Like `Vec::swap_remove`, this perturbs the position of what used to be the last value.
+    ///
+    /// Computes in *O*(1) time (average).
+    pub fn swap_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.swap_remove(value).is_some()
+    }
+
+    /// Removes a value from the set by shifting all the values that follow it. Returns `true` if
+    /// the value was present in the set.
+    ///
+    /// Like `Vec::remove`, this preserves the order of the remaining values.
+    ///
+    /// Computes in *O*(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3, 4].iter().cloned().collect();
+    /// assert_eq!(set.shift_remove(&2), true);
+    /// assert!(set.iter().eq(&[1, 3, 4]));
+    /// assert_eq!(set.swap_remove(&1), true);
+    /// assert!(set.iter().eq(&[4, 3]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.shift_remove(value).is_some()
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// The order of the remaining elements is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set = FnvIndexSet::<_, 8>::new();
+    /// set.extend([1, 2, 3, 4, 5, 6]);
+    /// set.retain(|&x| x % 2 == 0);
+    ///
+    /// assert!(set.iter().eq(&[2, 4, 6]));
+    /// assert!(set.contains(&4));
+    /// assert!(!set.contains(&3));
+    /// ```
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
     {
         self.map.retain(move |k, _| f(k));
     }
+
+    /// Retains only the values that are also in `other`, preserving their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut a: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 16> = [4, 3, 2].iter().cloned().collect();
+    /// a.intersect_with(&b);
+    /// assert!(a.iter().eq(&[2, 3]));
+    /// ```
+    pub fn intersect_with<S2, const N2: usize, P2: IndexPos>(
+        &mut self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) where
+        S2: BuildHasher,
+    {
+        self.retain(|value| other.contains(value));
+    }
+
+    /// Removes the values that are in `other`, preserving the order of the remaining ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut a: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 16> = [4, 2].iter().cloned().collect();
+    /// a.difference_with(&b);
+    /// assert!(a.iter().eq(&[1, 3]));
+    /// ```
+    pub fn difference_with<S2, const N2: usize, P2: IndexPos>(
+        &mut self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) where
+        S2: BuildHasher,
+    {
+        self.retain(|value| !other.contains(value));
+    }
+
+    /// Inserts the values of `other` that are not in `self` yet, in their order in `other`.
+    ///
+    /// Returns `Err(())` and leaves `self` unchanged if the union doesn't fit in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut a: FnvIndexSet<_, 4> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 4> = [3, 4].iter().cloned().collect();
+    /// a.union_with(&b).unwrap();
+    /// assert!(a.iter().eq(&[1, 2, 3, 4]));
+    ///
+    /// let c: FnvIndexSet<_, 4> = [5].iter().cloned().collect();
+    /// assert_eq!(a.union_with(&c), Err(()));
+    /// assert_eq!(a.len(), 4);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn union_with<S2, const N2: usize, P2: IndexPos>(
+        &mut self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) -> Result<(), ()>
+    where
+        T: Clone,
+        S2: BuildHasher,
+    {
+        if self.len() + other.difference(self).count() > N {
+            return Err(());
+        }
+
+        for value in other {
+            if !self.contains(value) {
+                // NOTE(unwrap) the capacity was checked above
+                self.insert(value.clone()).ok().unwrap();
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the values that are in `other`, and inserts the values of `other` that were not in
+    /// `self`, in their order in `other`.
+    ///
+    /// Returns `Err(())` and leaves `self` unchanged if the symmetric difference doesn't fit in the
+    /// set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut a: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 16> = [4, 2, 3, 5].iter().cloned().collect();
+    /// a.symmetric_difference_with(&b).unwrap();
+    /// assert!(a.iter().eq(&[1, 4, 5]));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn symmetric_difference_with<S2, const N2: usize, P2: IndexPos>(
+        &mut self,
+        other: &IndexSet<T, S2, N2, P2>,
+    ) -> Result<(), ()>
+    where
+        T: Clone,
+        S2: BuildHasher,
+    {
+        // Which values of `other` are also in `self`, by position in `other`
+        let mut in_both = [false; N2];
+        for (flag, value) in in_both.iter_mut().zip(other) {
+            *flag = self.contains(value);
+        }
+        let common = in_both.iter().filter(|&&flag| flag).count();
+        if self.len() - common + (other.len() - common) > N {
+            return Err(());
+        }
+
+        self.difference_with(other);
+        for (value, _) in other.iter().zip(&in_both).filter(|(_, &flag)| !flag) {
+            // NOTE(unwrap) the capacity was checked above
+            self.insert(value.clone()).ok().unwrap();
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature="copy")]
-impl<T, S, const N: usize> Copy for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> Copy for IndexSet<T, S, N, P>
 where
     T: Copy,
     S: Copy,
 {
 }
 
-impl<T, S, const N: usize> Clone for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> Clone for IndexSet<T, S, N, P>
 where
     T: Clone,
     S: Clone,
@@ -527,7 +965,7 @@ where
     }
 }
 
-impl<T, S, const N: usize> fmt::Debug for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> fmt::Debug for IndexSet<T, S, N, P>
 where
     T: fmt::Debug,
 {
@@ -536,7 +974,7 @@ where
     }
 }
 
-impl<T, S, const N: usize> Default for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> Default for IndexSet<T, S, N, P>
 where
     S: Default,
 {
@@ -547,19 +985,19 @@ where
     }
 }
 
-impl<T, S1, S2, const N1: usize, const N2: usize> PartialEq<IndexSet<T, S2, N2>>
-    for IndexSet<T, S1, N1>
+impl<T, S1, S2, const N1: usize, P1: IndexPos, const N2: usize, P2: IndexPos>
+    PartialEq<IndexSet<T, S2, N2, P2>> for IndexSet<T, S1, N1, P1>
 where
     T: Eq + Hash,
     S1: BuildHasher,
     S2: BuildHasher,
 {
-    fn eq(&self, other: &IndexSet<T, S2, N2>) -> bool {
+    fn eq(&self, other: &IndexSet<T, S2, N2, P2>) -> bool {
         self.len() == other.len() && self.is_subset(other)
     }
 }
 
-impl<T, S, const N: usize> Extend<T> for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> Extend<T> for IndexSet<T, S, N, P>
 where
     T: Eq + Hash,
     S: BuildHasher,
@@ -572,7 +1010,7 @@ where
     }
 }
 
-impl<'a, T, S, const N: usize> Extend<&'a T> for IndexSet<T, S, N>
+impl<'a, T, S, const N: usize, P: IndexPos> Extend<&'a T> for IndexSet<T, S, N, P>
 where
     T: 'a + Eq + Hash + Copy,
     S: BuildHasher,
@@ -585,7 +1023,7 @@ where
     }
 }
 
-impl<T, S, const N: usize> FromIterator<T> for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> FromIterator<T> for IndexSet<T, S, N, P>
 where
     T: Eq + Hash,
     S: BuildHasher + Default,
@@ -600,7 +1038,7 @@ where
     }
 }
 
-impl<'a, T, S, const N: usize> IntoIterator for &'a IndexSet<T, S, N>
+impl<'a, T, S, const N: usize, P: IndexPos> IntoIterator for &'a IndexSet<T, S, N, P>
 where
     T: Eq + Hash,
     S: BuildHasher,
@@ -637,16 +1075,45 @@ impl<T> Clone for Iter<'_, T> {
     }
 }
 
-pub struct Difference<'a, T, S, const N: usize>
+/// A draining iterator over the items of a [`IndexSet`].
+///
+/// This `struct` is created by the [`drain`](IndexSet::drain) method on [`IndexSet`]. See its
+/// documentation for more.
+pub struct Drain<'a, T, const N: usize, P: IndexPos = Pos> {
+    iter: indexmap::Drain<'a, T, (), N, P>,
+}
+
+impl<T, const N: usize, P: IndexPos> Iterator for Drain<'_, T, N, P> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, const N: usize, P: IndexPos> DoubleEndedIterator for Drain<'_, T, N, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<T, const N: usize, P: IndexPos> ExactSizeIterator for Drain<'_, T, N, P> {}
+impl<T, const N: usize, P: IndexPos> FusedIterator for Drain<'_, T, N, P> {}
+
+pub struct Difference<'a, T, S, const N: usize, P: IndexPos = Pos>
 where
     S: BuildHasher,
     T: Eq + Hash,
 {
     iter: Iter<'a, T>,
-    other: &'a IndexSet<T, S, N>,
+    other: &'a IndexSet<T, S, N, P>,
 }
 
-impl<'a, T, S, const N: usize> Iterator for Difference<'a, T, S, N>
+impl<'a, T, S, const N: usize, P: IndexPos> Iterator for Difference<'a, T, S, N, P>
 where
     S: BuildHasher,
     T: Eq + Hash,
@@ -663,16 +1130,16 @@ where
     }
 }
 
-pub struct Intersection<'a, T, S, const N: usize>
+pub struct Intersection<'a, T, S, const N: usize, P: IndexPos = Pos>
 where
     S: BuildHasher,
     T: Eq + Hash,
 {
     iter: Iter<'a, T>,
-    other: &'a IndexSet<T, S, N>,
+    other: &'a IndexSet<T, S, N, P>,
 }
 
-impl<'a, T, S, const N: usize> Iterator for Intersection<'a, T, S, N>
+impl<'a, T, S, const N: usize, P: IndexPos> Iterator for Intersection<'a, T, S, N, P>
 where
     S: BuildHasher,
     T: Eq + Hash,
@@ -689,12 +1156,221 @@ where
     }
 }
 
+impl<T, S1, S2, const N1: usize, P1: IndexPos, const N2: usize, P2: IndexPos>
+    ops::BitAnd<&IndexSet<T, S2, N2, P2>> for &IndexSet<T, S1, N1, P1>
+where
+    T: Eq + Hash + Clone,
+    S1: BuildHasher + Default,
+    S2: BuildHasher,
+{
+    type Output = IndexSet<T, S1, N1, P1>;
+
+    /// Returns the intersection of `self` and `rhs` as a new set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let a: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 16> = [2, 3, 4].iter().cloned().collect();
+    /// assert!((&a & &b).iter().eq(&[2, 3]));
+    /// ```
+    fn bitand(self, rhs: &IndexSet<T, S2, N2, P2>) -> Self::Output {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T, S1, S2, const N1: usize, P1: IndexPos, const N2: usize, P2: IndexPos>
+    ops::Sub<&IndexSet<T, S2, N2, P2>> for &IndexSet<T, S1, N1, P1>
+where
+    T: Eq + Hash + Clone,
+    S1: BuildHasher + Default,
+    S2: BuildHasher,
+{
+    type Output = IndexSet<T, S1, N1, P1>;
+
+    /// Returns the difference of `self` and `rhs` as a new set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let a: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 16> = [2, 3, 4].iter().cloned().collect();
+    /// assert!((&a - &b).iter().eq(&[1]));
+    /// ```
+    fn sub(self, rhs: &IndexSet<T, S2, N2, P2>) -> Self::Output {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, S1, S2, const N1: usize, P1: IndexPos, const N2: usize, P2: IndexPos>
+    ops::BitOr<&IndexSet<T, S2, N2, P2>> for &IndexSet<T, S1, N1, P1>
+where
+    T: Eq + Hash + Clone,
+    S1: BuildHasher + Default,
+    S2: BuildHasher,
+{
+    type Output = Result<IndexSet<T, S1, N1, P1>, ()>;
+
+    /// Returns the union of `self` and `rhs` as a new set, or `Err(())` if it doesn't fit in the
+    /// capacity of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let a: FnvIndexSet<_, 4> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 4> = [2, 3, 4].iter().cloned().collect();
+    /// assert!((&a | &b).unwrap().iter().eq(&[1, 2, 3, 4]));
+    ///
+    /// let c: FnvIndexSet<_, 4> = [5, 6].iter().cloned().collect();
+    /// assert!((&a | &c).is_err());
+    /// ```
+    fn bitor(self, rhs: &IndexSet<T, S2, N2, P2>) -> Self::Output {
+        let mut set: IndexSet<T, S1, N1, P1> = self.iter().cloned().collect();
+        set.union_with(rhs)?;
+        Ok(set)
+    }
+}
+
+impl<T, S1, S2, const N1: usize, P1: IndexPos, const N2: usize, P2: IndexPos>
+    ops::BitXor<&IndexSet<T, S2, N2, P2>> for &IndexSet<T, S1, N1, P1>
+where
+    T: Eq + Hash + Clone,
+    S1: BuildHasher + Default,
+    S2: BuildHasher,
+{
+    type Output = Result<IndexSet<T, S1, N1, P1>, ()>;
+
+    /// Returns the symmetric difference of `self` and `rhs` as a new set, or `Err(())` if it
+    /// doesn't fit in the capacity of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let a: FnvIndexSet<_, 4> = [1, 2, 3].iter().cloned().collect();
+    /// let b: FnvIndexSet<_, 4> = [2, 3, 4].iter().cloned().collect();
+    /// assert!((&a ^ &b).unwrap().iter().eq(&[1, 4]));
+    /// ```
+    fn bitxor(self, rhs: &IndexSet<T, S2, N2, P2>) -> Self::Output {
+        let mut set: IndexSet<T, S1, N1, P1> = self.iter().cloned().collect();
+        set.symmetric_difference_with(rhs)?;
+        Ok(set)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use static_assertions::assert_not_impl_any;
 
     use super::{BuildHasherDefault, IndexSet};
+    use crate::{CompactFnvIndexSet, FnvIndexSet};
 
     // Ensure a `IndexSet` containing `!Send` values stays `!Send` itself.
     assert_not_impl_any!(IndexSet<*const (), BuildHasherDefault<()>, 4>: Send);
+
+    #[test]
+    fn drain() {
+        let mut set: FnvIndexSet<u8, 8> = (0..8).collect();
+        let mut drain = set.drain(2..6);
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next_back(), Some(5));
+        drop(drain);
+        assert!(set.iter().eq(&[0, 1, 6, 7]));
+        for value in [0, 1, 6, 7] {
+            assert!(set.contains(&value));
+        }
+        for value in 2..6 {
+            assert!(!set.contains(&value));
+        }
+
+        // The set can be filled again
+        set.extend(2..6);
+        assert!(set.is_full());
+        assert!(set.drain(..).eq([0, 1, 6, 7, 2, 3, 4, 5]));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn compact() {
+        let mut compact: CompactFnvIndexSet<u16, 200> = (0..200).map(|i| i * 5).collect();
+        assert!(compact.is_full());
+        for i in 0..200 {
+            assert!(compact.contains(&(i * 5)));
+        }
+
+        // Sets with different index types interoperate
+        let mut regular: FnvIndexSet<u16, 256> = (0..100).map(|i| i * 10).collect();
+        assert!(regular.is_subset(&compact));
+        assert_eq!(
+            regular,
+            compact
+                .iter()
+                .copied()
+                .filter(|v| v % 10 == 0)
+                .collect::<FnvIndexSet<_, 256>>()
+        );
+        compact.difference_with(&regular);
+        assert_eq!(compact.len(), 100);
+        assert!(compact.is_disjoint(&regular));
+        regular.union_with(&compact).unwrap();
+        assert_eq!(regular.len(), 200);
+    }
+
+    #[test]
+    fn in_place_set_algebra() {
+        let a: FnvIndexSet<u8, 8> = [1, 2, 3, 4, 5].iter().cloned().collect();
+        let b: FnvIndexSet<u8, 4> = [6, 4, 7, 2].iter().cloned().collect();
+
+        let mut set = a.clone();
+        set.intersect_with(&b);
+        assert!(set.iter().eq(a.intersection(&b)));
+
+        let mut set = a.clone();
+        set.difference_with(&b);
+        assert!(set.iter().eq(a.difference(&b)));
+
+        let mut set = a.clone();
+        set.union_with(&b).unwrap();
+        assert!(set.iter().eq(a.union(&b)));
+        assert_eq!(set.union_with(&b), Ok(()));
+
+        let mut set = a.clone();
+        set.symmetric_difference_with(&b).unwrap();
+        assert!(set.iter().eq(a.symmetric_difference(&b)));
+        for value in &set {
+            assert!(set.contains(value));
+        }
+    }
+
+    #[test]
+    fn set_algebra_capacity() {
+        // The union doesn't fit, but the symmetric difference does
+        let a: FnvIndexSet<u8, 4> = [1, 2, 3, 4].iter().cloned().collect();
+        let b: FnvIndexSet<u8, 4> = [5, 1, 2].iter().cloned().collect();
+
+        let mut set = a.clone();
+        assert_eq!(set.union_with(&b), Err(()));
+        assert_eq!(set, a);
+        assert!((&a | &b).is_err());
+
+        set.symmetric_difference_with(&b).unwrap();
+        assert!(set.iter().eq(&[3, 4, 5]));
+        assert_eq!(&a ^ &b, Ok(set));
+
+        let c: FnvIndexSet<u8, 4> = [5, 6, 7].iter().cloned().collect();
+        let mut set = a.clone();
+        assert_eq!(set.symmetric_difference_with(&c), Err(()));
+        assert_eq!(set, a);
+
+        assert!((&a & &c).is_empty());
+        assert_eq!(&a - &c, a);
+    }
 }
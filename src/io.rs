@@ -0,0 +1,62 @@
+//! `std::io` implementations for heapless types
+
+use std::io;
+
+use crate::{storage::Storage, vec::VecInner};
+
+/// Appends as many bytes as fit in the remaining capacity of the vector.
+///
+/// Once the vector is full, `write` returns `Ok(0)`, so [`io::Write::write_all`] fails with
+/// [`io::ErrorKind::WriteZero`].
+impl<S: Storage> io::Write for VecInner<u8, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.storage_capacity() - self.len());
+        // NOTE(unwrap) `n` bytes fit in the vector
+        self.extend_from_copy_slice(&buf[..n]).unwrap();
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, ErrorKind, Write};
+
+    use crate::{Vec, VecView};
+
+    #[test]
+    fn write_full() {
+        let mut v = Vec::<u8, 4>::from_slice(b"abc").unwrap();
+        assert_eq!(v.write(b"de").unwrap(), 1);
+        // Unlike `embedded_io`, a full vector isn't an error for `write`
+        assert_eq!(v.write(b"e").unwrap(), 0);
+        assert_eq!(v, b"abcd");
+    }
+
+    #[test]
+    fn write_all_write_zero() {
+        let mut v = Vec::<u8, 4>::new();
+        let view: &mut VecView<u8> = &mut v;
+        let err = view.write_all(b"hello").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        assert_eq!(v, b"hell");
+
+        let mut v = Vec::<u8, 4>::new();
+        let err = write!(v, "{}", 123456).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn copy() {
+        let mut v = Vec::<u8, 8>::new();
+        assert_eq!(io::copy(&mut &b"hello"[..], &mut v).unwrap(), 5);
+        assert_eq!(v, b"hello");
+
+        let err = io::copy(&mut &b" world"[..], &mut v).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        assert_eq!(v, b"hello wo");
+    }
+}
@@ -0,0 +1,46 @@
+//! Fallible conversions from iterators into fixed capacity containers.
+//!
+//! [`FromIterator`] implementations in this crate panic when the iterator yields more items
+//! than the container can hold. [`TryFromIterator`] is their fallible counterpart, and
+//! [`TryCollect::try_collect`] is the fallible counterpart of [`Iterator::collect`].
+//!
+//! # Examples
+//!
+//! ```
+//! use heapless::{iter::TryCollect, Vec};
+//!
+//! let v: Vec<u8, 4> = (0..4).try_collect().unwrap();
+//! assert_eq!(v, [0, 1, 2, 3]);
+//!
+//! // The first item that didn't fit is returned
+//! assert_eq!((0..8).try_collect::<Vec<u8, 4>>(), Err(4));
+//! ```
+
+/// Conversion from an [`Iterator`] that fails if the container cannot hold all the items.
+pub trait TryFromIterator<A>: Sized {
+    /// The error returned when the container cannot hold all the items.
+    type Error;
+
+    /// Creates a value from an iterator.
+    ///
+    /// Returns an error if the container cannot hold all the items of the iterator.
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = A>;
+}
+
+/// Extension trait for [`Iterator`] adding [`try_collect`](TryCollect::try_collect).
+pub trait TryCollect: Iterator + Sized {
+    /// Transforms an iterator into a collection, failing if the collection cannot hold all
+    /// the items of the iterator.
+    ///
+    /// This is the fallible counterpart of [`Iterator::collect`].
+    fn try_collect<B>(self) -> Result<B, B::Error>
+    where
+        B: TryFromIterator<Self::Item>,
+    {
+        B::try_from_iter(self)
+    }
+}
+
+impl<I: Iterator> TryCollect for I {}
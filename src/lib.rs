@@ -111,6 +111,7 @@
     doc = "- [Object](pool::object::Object) -- objects managed by an object pool"
 )]
 //! - [BinaryHeap] -- priority queue
+//! - [CString] -- nul-terminated C string
 //! - [Deque] -- double-ended queue
 //! - [HistoryBuffer] -- similar to a write-only ring buffer
 //! - [IndexMap] -- hash table
@@ -142,19 +143,28 @@
 )]
 
 pub use binary_heap::BinaryHeap;
+pub use c_string::CString;
 pub use deque::Deque;
 pub use histbuf::{HistoryBuffer, OldestOrdered};
 pub use indexmap::{
-    Bucket, Entry, FnvIndexMap, IndexMap, Iter as IndexMapIter, IterMut as IndexMapIterMut,
-    Keys as IndexMapKeys, OccupiedEntry, Pos, VacantEntry, Values as IndexMapValues,
-    ValuesMut as IndexMapValuesMut,
+    Bucket, CompactFnvIndexMap, CompactPos, Drain as IndexMapDrain, Entry,
+    ExtractIf as IndexMapExtractIf, FnvIndexMap, IndexMap, IndexPos, Iter as IndexMapIter,
+    IterMut as IndexMapIterMut, Keys as IndexMapKeys, Murmur3IndexMap, OccupiedEntry, Pos,
+    RawEntryBuilder, RawEntryBuilderMut, RawEntryMut, RawOccupiedEntryMut, RawVacantEntryMut,
+    VacantEntry, Values as IndexMapValues, ValuesMut as IndexMapValuesMut,
+};
+pub use indexset::{
+    CompactFnvIndexSet, Drain as IndexSetDrain, FnvIndexSet, IndexSet, Iter as IndexSetIter,
+    Murmur3IndexSet,
 };
-pub use indexset::{FnvIndexSet, IndexSet, Iter as IndexSetIter};
 pub use linear_map::LinearMap;
 pub use string::String;
 
 pub use vec::{Vec, VecView};
 
+#[cfg(feature = "serde")]
+pub use de::{DeserializePolicy, OnDuplicate, OnOverflow};
+
 #[macro_use]
 #[cfg(test)]
 mod test_helpers;
@@ -163,6 +173,7 @@ pub mod deque;
 pub mod histbuf;
 mod indexmap;
 mod indexset;
+pub mod iter;
 pub mod linear_map;
 mod slice;
 pub mod storage;
@@ -175,8 +186,16 @@ mod de;
 mod ser;
 
 pub mod binary_heap;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+pub mod c_string;
+#[cfg(feature = "codec")]
+pub mod codec;
 #[cfg(feature = "defmt-03")]
 mod defmt;
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+mod fmt_int;
 #[cfg(any(
     // assume we have all atomics available if we're using portable-atomic
     feature = "portable-atomic",
@@ -200,6 +219,8 @@ pub mod mpmc;
     )
 ))]
 pub mod pool;
+#[cfg(feature = "ryu")]
+mod ryu;
 pub mod sorted_linked_list;
 #[cfg(any(
     // assume we have all atomics available if we're using portable-atomic
@@ -215,11 +236,18 @@ pub mod spsc;
 #[cfg(feature = "ufmt")]
 mod ufmt;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "std")]
+mod io;
+
 mod sealed;
 
 /// Implementation details for macros.
 /// Do not use. Used for macros only. Not covered by semver guarantees.
 #[doc(hidden)]
 pub mod _export {
-    pub use crate::string::format;
+    pub use crate::string::{format, str_literal};
 }
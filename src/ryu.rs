@@ -0,0 +1,347 @@
+//! Float formatting for heapless types using `ryu`
+
+use ryu::{Buffer, Float};
+
+use crate::{
+    storage::Storage,
+    string::{String, StringInner},
+    vec::VecInner,
+};
+
+/// Maximum number of significant digits of the shortest representation of a `f64`, plus one for
+/// a carry when rounding.
+const MAX_DIGITS: usize = 18;
+
+/// The decimal digits of a finite float, such that its absolute value is
+/// `0.{digits} * 10^point`.
+struct Decimal {
+    negative: bool,
+    digits: [u8; MAX_DIGITS],
+    len: usize,
+    point: isize,
+}
+
+impl Decimal {
+    /// Parses the shortest representation written by `ryu`, like `-1.25`, `0.001` or `1e16`.
+    fn parse(s: &str) -> Self {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (mantissa, exp) = match s.split_once('e') {
+            // NOTE(unwrap) `ryu` writes the exponent as a decimal integer
+            Some((mantissa, exp)) => (mantissa, exp.parse::<isize>().unwrap()),
+            None => (s, 0),
+        };
+
+        let mut decimal = Decimal {
+            negative,
+            digits: [0; MAX_DIGITS],
+            len: 0,
+            point: mantissa.find('.').unwrap_or(mantissa.len()) as isize + exp,
+        };
+        let mut digits = mantissa.bytes().filter(u8::is_ascii_digit).peekable();
+        // Skip the leading zeros of values below 1, like `0.00123`, so that only the significant
+        // digits are stored
+        while digits.next_if_eq(&b'0').is_some() {
+            decimal.point -= 1;
+        }
+        for b in digits {
+            decimal.digits[decimal.len] = b - b'0';
+            decimal.len += 1;
+        }
+        decimal
+    }
+
+    fn digit(&self, i: isize) -> u8 {
+        if i < 0 || i >= self.len as isize {
+            0
+        } else {
+            self.digits[i as usize]
+        }
+    }
+
+    /// Rounds half away from zero to `precision` digits after the decimal point.
+    fn round(&mut self, precision: usize) {
+        let keep = self.point + precision as isize;
+        if keep < 0 || keep >= self.len as isize {
+            // Either the value rounds to zero, or all the digits are kept
+            self.len = self.len.min(keep.max(0) as usize);
+            return;
+        }
+
+        let keep = keep as usize;
+        let round_up = self.digits[keep] >= 5;
+        self.len = keep;
+        if !round_up {
+            return;
+        }
+
+        let mut i = keep;
+        loop {
+            if i == 0 {
+                // All the kept digits were 9s, e.g. 0.999 rounded to 1.00
+                self.digits.copy_within(0..keep, 1);
+                self.digits[0] = 1;
+                self.len = keep + 1;
+                self.point += 1;
+                return;
+            }
+            i -= 1;
+            if self.digits[i] == 9 {
+                self.digits[i] = 0;
+            } else {
+                self.digits[i] += 1;
+                return;
+            }
+        }
+    }
+}
+
+/// Appends `value` to `vec`, leaving it unchanged if it doesn't fit.
+fn write_float<S: Storage, F: Float>(
+    vec: &mut VecInner<u8, S>,
+    value: F,
+    precision: Option<usize>,
+) -> Result<(), ()> {
+    let mut buffer = Buffer::new();
+    let shortest = buffer.format(value);
+
+    let precision = match precision {
+        // Non-finite values are written as `NaN`, `inf` or `-inf`
+        Some(precision) if shortest.bytes().any(|b| b.is_ascii_digit()) => precision,
+        _ => return vec.extend_from_copy_slice(shortest.as_bytes()),
+    };
+
+    let mut decimal = Decimal::parse(shortest);
+    decimal.round(precision);
+
+    let start = vec.len();
+    let res = (|| {
+        if decimal.negative {
+            vec.push(b'-').map_err(drop)?;
+        }
+        if decimal.point <= 0 {
+            vec.push(b'0').map_err(drop)?;
+        } else {
+            for i in 0..decimal.point {
+                vec.push(b'0' + decimal.digit(i)).map_err(drop)?;
+            }
+        }
+        if precision > 0 {
+            vec.push(b'.').map_err(drop)?;
+            for i in 0..precision as isize {
+                vec.push(b'0' + decimal.digit(decimal.point + i))
+                    .map_err(drop)?;
+            }
+        }
+        Ok(())
+    })();
+
+    if res.is_err() {
+        vec.truncate(start);
+    }
+    res
+}
+
+impl<S: Storage> VecInner<u8, S> {
+    /// Appends the decimal representation of `value` to the vector.
+    ///
+    /// With a `precision` of `None`, the shortest representation that roundtrips is written,
+    /// like with [`String::from_f64`]. Otherwise exactly `precision` digits are written after the
+    /// decimal point, rounding half away from zero based on the shortest representation. Digits
+    /// beyond the shortest representation are written as zeros.
+    ///
+    /// Non-finite values are written as `NaN`, `inf` and `-inf`.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold the representation.
+    ///
+    /// This method is only available with the `ryu` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 16> = Vec::new();
+    /// v.write_float(1.5f32, None).unwrap();
+    /// v.push(b' ').unwrap();
+    /// v.write_float(2.0f64 / 3.0, Some(3)).unwrap();
+    /// assert_eq!(v, *b"1.5 0.667");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn write_float<F: Float>(&mut self, value: F, precision: Option<usize>) -> Result<(), ()> {
+        write_float(self, value, precision)
+    }
+}
+
+impl<S: Storage> StringInner<S> {
+    /// Appends the decimal representation of `value` to the string.
+    ///
+    /// With a `precision` of `None`, the shortest representation that roundtrips is written,
+    /// like with [`String::from_f64`]. Otherwise exactly `precision` digits are written after the
+    /// decimal point, rounding half away from zero based on the shortest representation. Digits
+    /// beyond the shortest representation are written as zeros.
+    ///
+    /// Non-finite values are written as `NaN`, `inf` and `-inf`.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the representation.
+    ///
+    /// This method is only available with the `ryu` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("t=").unwrap();
+    /// s.write_float(21.456f32, Some(1)).unwrap();
+    /// s.push_str("C").unwrap();
+    /// assert_eq!(s, "t=21.5C");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn write_float<F: Float>(&mut self, value: F, precision: Option<usize>) -> Result<(), ()> {
+        // NOTE(unsafe) only ASCII is written
+        write_float(unsafe { self.as_mut_vec() }, value, precision)
+    }
+}
+
+impl<const N: usize> String<N> {
+    /// Constructs a `String` holding the shortest decimal representation of `value` that
+    /// roundtrips.
+    ///
+    /// Returns `Err(())` if the `String` cannot hold the representation.
+    ///
+    /// This method is only available with the `ryu` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::from_f32(0.3).unwrap();
+    /// assert_eq!(s, "0.3");
+    /// assert!(String::<4>::from_f32(1.2345).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn from_f32(value: f32) -> Result<Self, ()> {
+        let mut s = Self::new();
+        s.write_float(value, None)?;
+        Ok(s)
+    }
+
+    /// Constructs a `String` holding the shortest decimal representation of `value` that
+    /// roundtrips.
+    ///
+    /// Returns `Err(())` if the `String` cannot hold the representation.
+    ///
+    /// This method is only available with the `ryu` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<24> = String::from_f64(-1.0 / 3.0).unwrap();
+    /// assert_eq!(s, "-0.3333333333333333");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn from_f64(value: f64) -> Result<Self, ()> {
+        let mut s = Self::new();
+        s.write_float(value, None)?;
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use crate::{String, Vec};
+
+    fn fixed(value: f64, precision: usize) -> String<64> {
+        let mut s = String::new();
+        s.write_float(value, Some(precision)).unwrap();
+        s
+    }
+
+    #[test]
+    fn shortest() {
+        assert_eq!(String::<32>::from_f64(0.0).unwrap(), "0.0");
+        assert_eq!(String::<32>::from_f64(-0.0).unwrap(), "-0.0");
+        assert_eq!(String::<32>::from_f64(1e16).unwrap(), "1e16");
+        assert_eq!(String::<32>::from_f32(0.1).unwrap(), "0.1");
+        assert_eq!(String::<32>::from_f32(f32::NAN).unwrap(), "NaN");
+        assert_eq!(String::<32>::from_f64(f64::NEG_INFINITY).unwrap(), "-inf");
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(fixed(0.0, 0), "0");
+        assert_eq!(fixed(0.0, 2), "0.00");
+        assert_eq!(fixed(1.25, 1), "1.3");
+        assert_eq!(fixed(-1.25, 1), "-1.3");
+        assert_eq!(fixed(0.5, 0), "1");
+        assert_eq!(fixed(0.4, 0), "0");
+        assert_eq!(fixed(9.999, 2), "10.00");
+        assert_eq!(fixed(999.96, 1), "1000.0");
+        assert_eq!(fixed(0.001, 2), "0.00");
+        assert_eq!(fixed(0.005, 2), "0.01");
+        assert_eq!(fixed(1.2345e-7, 9), "0.000000123");
+        assert_eq!(fixed(1e16, 1), "10000000000000000.0");
+        // Digits beyond the shortest representation are zeros, unlike with `core::fmt`
+        assert_eq!(fixed(6.02e23, 0), "602000000000000000000000");
+        assert_eq!(fixed(123.456, 5), "123.45600");
+        assert_eq!(fixed(f64::INFINITY, 2), "inf");
+    }
+
+    #[test]
+    fn precision_matches_core() {
+        for &value in &[2.71, 2.0 / 3.0, 100.0, 1234.5678, 1e-5, 6.02e13] {
+            for precision in 0..8 {
+                assert_eq!(
+                    fixed(value, precision),
+                    format!("{value:.precision$}").as_str()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn small_values_with_many_digits() {
+        let mut value = 0.12345678901234567;
+        for _ in 1..=7 {
+            for precision in [0, 3, 10] {
+                assert_eq!(
+                    fixed(value, precision),
+                    format!("{value:.precision$}").as_str()
+                );
+            }
+            value /= 10.0;
+        }
+        assert_eq!(fixed(0.012345678901234567, 3), "0.012");
+        assert_eq!(fixed(1.2345678901234567e-5, 3), "0.000");
+        assert_eq!(fixed(-0.0, 2), "-0.00");
+
+        let mut v: Vec<u8, 64> = Vec::new();
+        v.write_float(1.2345678901234567e-7, Some(12)).unwrap();
+        assert_eq!(v, b"0.000000123457");
+    }
+
+    #[test]
+    fn f32_precision() {
+        let mut s: String<16> = String::new();
+        s.write_float(0.1f32, Some(10)).unwrap();
+        assert_eq!(s, "0.1000000000");
+    }
+
+    #[test]
+    fn overflow() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(b"x").unwrap();
+        assert!(v.write_float(1.25, Some(2)).is_err());
+        assert!(v.write_float(-1.0, None).is_err());
+        assert_eq!(v, *b"x");
+        v.write_float(1.0, Some(1)).unwrap();
+        assert_eq!(v, *b"x1.0");
+    }
+}
@@ -8,6 +8,11 @@ pub(crate) const fn greater_than_eq<const N: usize, const MIN: usize>() {
     Assert::<N, MIN>::GREATER_EQ;
 }
 
+#[allow(dead_code, path_statements, clippy::no_effect)]
+pub(crate) const fn greater_than_eq_sum<const N: usize, const A: usize, const B: usize>() {
+    AssertSum::<N, A, B>::GREATER_EQ;
+}
+
 #[allow(dead_code, path_statements, clippy::no_effect)]
 pub(crate) const fn greater_than_eq_0<const N: usize>() {
     Assert::<N, 0>::GREATER_EQ;
@@ -57,3 +62,13 @@ impl<const L: usize, const R: usize> Assert<L, R> {
     /// Const assert hack
     pub const POWER_OF_TWO: usize = 0 - (L & (L - 1));
 }
+
+#[allow(dead_code)]
+/// Const assert hack
+pub struct AssertSum<const L: usize, const A: usize, const B: usize>;
+
+#[allow(dead_code)]
+impl<const L: usize, const A: usize, const B: usize> AssertSum<L, A, B> {
+    /// Const assert hack
+    pub const GREATER_EQ: usize = L - (A + B);
+}
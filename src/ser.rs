@@ -8,7 +8,7 @@ use crate::{
     storage::Storage,
     string::StringInner,
     vec::VecInner,
-    IndexMap, IndexSet,
+    IndexMap, IndexPos, IndexSet,
 };
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
@@ -32,7 +32,7 @@ where
     }
 }
 
-impl<T, S, const N: usize> Serialize for IndexSet<T, S, N>
+impl<T, S, const N: usize, P: IndexPos> Serialize for IndexSet<T, S, N, P>
 where
     T: Eq + Hash + Serialize,
     S: BuildHasher,
@@ -99,7 +99,7 @@ where
 
 // Dictionaries
 
-impl<K, V, S, const N: usize> Serialize for IndexMap<K, V, S, N>
+impl<K, V, S, const N: usize, P: IndexPos> Serialize for IndexMap<K, V, S, N, P>
 where
     K: Eq + Hash + Serialize,
     S: BuildHasher,
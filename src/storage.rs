@@ -1,6 +1,10 @@
 //! `Storage` trait defining how data is stored in a container.
 
-use core::borrow::{Borrow, BorrowMut};
+use core::{
+    borrow::{Borrow, BorrowMut},
+    convert::Infallible,
+    marker::PhantomData,
+};
 
 pub(crate) trait SealedStorage {
     type Buffer<T>: ?Sized + Borrow<[T]> + BorrowMut<[T]>;
@@ -14,10 +18,11 @@ pub(crate) trait SealedStorage {
 
 /// Trait defining how data for a container is stored.
 ///
-/// There's two implementations available:
+/// There's three implementations available:
 ///
 /// - [`OwnedStorage`]: stores the data in an array `[T; N]` whose size is known at compile time.
 /// - [`ViewStorage`]: stores the data in an unsized `[T]`.
+/// - [`AlignedStorage`]: stores the data in an array `[T; N]` aligned to an [`Alignment`].
 ///
 /// This allows containers to be generic over either sized or unsized storage. For example,
 /// the [`vec`](crate::vec) module contains a [`VecInner`](crate::vec::VecInner) struct
@@ -62,3 +67,82 @@ impl SealedStorage for ViewStorage {
         this as _
     }
 }
+
+/// Implementation of [`Storage`] that stores the data in an array `[T; N]` whose address is a
+/// multiple of the alignment `A`, regardless of the alignment of `T`.
+///
+/// This is used by [`AlignedVec`](crate::vec::AlignedVec).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AlignedStorage<const N: usize, A: Alignment>(Infallible, PhantomData<A>);
+impl<const N: usize, A: Alignment> Storage for AlignedStorage<N, A> {}
+impl<const N: usize, A: Alignment> SealedStorage for AlignedStorage<N, A> {
+    type Buffer<T> = Aligned<A, [T; N]>;
+    fn len<T>(_: *const Self::Buffer<T>) -> usize {
+        N
+    }
+    fn as_ptr<T>(this: *mut Self::Buffer<T>) -> *mut T {
+        this as _
+    }
+}
+
+/// Buffer `B` whose address is a multiple of the alignment `A`.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub(crate) struct Aligned<A, B: ?Sized> {
+    pub(crate) _align: [A; 0],
+    pub(crate) buffer: B,
+}
+
+impl<A, T, const N: usize> Borrow<[T]> for Aligned<A, [T; N]> {
+    fn borrow(&self) -> &[T] {
+        &self.buffer
+    }
+}
+
+impl<A, T, const N: usize> BorrowMut<[T]> for Aligned<A, [T; N]> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        &mut self.buffer
+    }
+}
+
+pub(crate) trait SealedAlignment {}
+
+/// Trait implemented by the alignment marker types used by [`AlignedStorage`].
+///
+/// This trait is sealed, so you cannot implement it for your own types. You can only use
+/// the implementations provided by this crate.
+#[allow(private_bounds)]
+pub trait Alignment: SealedAlignment + Copy {
+    /// The alignment in bytes.
+    const ALIGN: usize;
+}
+
+macro_rules! alignments {
+    ($($name:ident => $align:literal,)+) => {
+        $(
+            #[doc = concat!("Alignment marker for ", stringify!($align), "-byte alignment.")]
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+            #[repr(align($align))]
+            pub struct $name;
+            impl SealedAlignment for $name {}
+            impl Alignment for $name {
+                const ALIGN: usize = $align;
+            }
+        )+
+    };
+}
+
+alignments! {
+    Align2 => 2,
+    Align4 => 4,
+    Align8 => 8,
+    Align16 => 16,
+    Align32 => 32,
+    Align64 => 64,
+    Align128 => 128,
+    Align256 => 256,
+    Align512 => 512,
+    Align1024 => 1024,
+    Align2048 => 2048,
+    Align4096 => 4096,
+}
@@ -131,4 +131,26 @@ mod tests {
         drop(it);
         assert_eq!(s, "abc");
     }
+
+    #[test]
+    fn drain_unconsumed() {
+        let mut s = String::<16>::try_from("αβγδ").unwrap();
+        s.drain(2..6);
+        assert_eq!(s, "αδ");
+    }
+
+    #[test]
+    fn drain_rev() {
+        let mut s = String::<16>::try_from("αβγδ").unwrap();
+        let t: String<16> = s.drain(2..).rev().collect();
+        assert_eq!(t, "δγβ");
+        assert_eq!(s, "α");
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_not_char_boundary() {
+        let mut s = String::<16>::try_from("αβ").unwrap();
+        s.drain(1..);
+    }
 }
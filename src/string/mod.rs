@@ -12,6 +12,7 @@ use core::{
 };
 
 use crate::{
+    iter::TryFromIterator,
     storage::{OwnedStorage, Storage, ViewStorage},
     vec::VecInner,
     Vec,
@@ -20,6 +21,9 @@ use crate::{
 mod drain;
 pub use drain::Drain;
 
+mod utf8_writer;
+pub use utf8_writer::{Utf8WriteError, Utf8Writer};
+
 /// A possible error value when converting a [`String`] from a UTF-16 byte slice.
 ///
 /// This type is the error type for the [`from_utf16`] method on [`String`].
@@ -42,6 +46,21 @@ impl fmt::Display for FromUtf16Error {
     }
 }
 
+/// The error type for the [`TryFromIterator`] implementations of [`String`].
+///
+/// It is returned when the `String` cannot hold all the items of the iterator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromIterError {
+    /// The number of items consumed from the iterator, including the one that didn't fit.
+    pub consumed: usize,
+}
+
+impl fmt::Display for TryFromIterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "insufficient capacity for item {}", self.consumed)
+    }
+}
+
 /// Base struct for [`String`] and [`StringView`], generic over the [`Storage`].
 ///
 /// In most cases you should use [`String`] or [`StringView`] directly. Only use this
@@ -54,6 +73,30 @@ pub struct StringInner<S: Storage> {
 pub type String<const N: usize> = StringInner<OwnedStorage<N>>;
 
 /// A dynamic capacity [`String`](https://doc.rust-lang.org/std/string/struct.String.html).
+///
+/// All the methods of [`StringInner`] that don't depend on the capacity being known at compile
+/// time are available on `StringView`, so code can accept a `&mut StringView` and work with a
+/// `String` of any capacity.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::string::{String, StringView};
+///
+/// fn log_line(buf: &mut StringView, msg: &str) -> Result<(), ()> {
+///     buf.truncate(0);
+///     buf.push_str("> ")?;
+///     buf.push_str(msg)?;
+///     buf.retain(|c| !c.is_control());
+///     buf.push('\n')
+/// }
+///
+/// let mut small: String<8> = String::new();
+/// let mut large: String<32> = String::new();
+/// assert!(log_line(&mut small, "hello\tworld").is_err());
+/// log_line(&mut large, "hello\tworld").unwrap();
+/// assert_eq!(large, "> helloworld\n");
+/// ```
 pub type StringView = StringInner<ViewStorage>;
 
 impl StringView {
@@ -179,6 +222,39 @@ impl<const N: usize> String<N> {
         Ok(s)
     }
 
+    /// Decodes a UTF-16–encoded slice `v` into a `String`, replacing
+    /// invalid data with [the replacement character (`U+FFFD`)][U+FFFD].
+    ///
+    /// Returns `Err(())` if the decoded string doesn't fit in the `String`.
+    ///
+    /// [U+FFFD]: core::char::REPLACEMENT_CHARACTER
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// // 𝄞mus<invalid>ic<invalid>
+    /// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075, 0x0073, 0xDD1E, 0x0069, 0x0063, 0xD834];
+    /// let s: String<16> = String::from_utf16_lossy(v).unwrap();
+    /// assert_eq!(s, "𝄞mus\u{FFFD}ic\u{FFFD}");
+    ///
+    /// assert!(String::<8>::from_utf16_lossy(v).is_err());
+    /// ```
+    #[inline]
+    #[allow(clippy::result_unit_err)]
+    pub fn from_utf16_lossy(v: &[u16]) -> Result<Self, ()> {
+        let mut s = Self::new();
+
+        for c in char::decode_utf16(v.iter().cloned()) {
+            s.push(c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+
+        Ok(s)
+    }
+
     /// Convert UTF-8 bytes into a `String`.
     ///
     /// # Examples
@@ -347,6 +423,121 @@ impl<const N: usize> String<N> {
     {
         self.as_mut_view().drain(range)
     }
+
+    /// Returns a copy of this string where each character is mapped to its
+    /// ASCII upper case equivalent.
+    ///
+    /// ASCII letters 'a' to 'z' are mapped to 'A' to 'Z',
+    /// but non-ASCII letters are unchanged.
+    ///
+    /// To uppercase the value in-place, use [`str::make_ascii_uppercase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<32> = String::try_from("Grüße, Jürgen ❤").unwrap();
+    /// assert_eq!(s.to_ascii_uppercase(), "GRüßE, JüRGEN ❤");
+    /// ```
+    pub fn to_ascii_uppercase(&self) -> Self {
+        let mut s = Self::new();
+        // NOTE(unwrap) `s` has the same capacity as `self`
+        s.push_str(self).unwrap();
+        s.make_ascii_uppercase();
+        s
+    }
+
+    /// Returns a copy of this string where each character is mapped to its
+    /// ASCII lower case equivalent.
+    ///
+    /// ASCII letters 'A' to 'Z' are mapped to 'a' to 'z',
+    /// but non-ASCII letters are unchanged.
+    ///
+    /// To lowercase the value in-place, use [`str::make_ascii_lowercase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::try_from("AT+CGMI").unwrap();
+    /// assert_eq!(s.to_ascii_lowercase(), "at+cgmi");
+    /// ```
+    pub fn to_ascii_lowercase(&self) -> Self {
+        let mut s = Self::new();
+        // NOTE(unwrap) `s` has the same capacity as `self`
+        s.push_str(self).unwrap();
+        s.make_ascii_lowercase();
+        s
+    }
+
+    /// Concatenates two strings into a new `String`.
+    ///
+    /// If the capacity `N` is less than `A + B`, a compile-time error is produced, so the result
+    /// can never be truncated. Use [`try_concat`](Self::try_concat) to check the actual lengths
+    /// at runtime instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let prefix: String<8> = String::try_from("sensors/").unwrap();
+    /// let id: String<4> = String::try_from("42").unwrap();
+    /// let topic: String<12> = String::concat(&prefix, &id);
+    /// assert_eq!(topic, "sensors/42");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use heapless::String;
+    ///
+    /// let a: String<8> = String::new();
+    /// let b: String<4> = String::new();
+    /// let ab: String<11> = String::concat(&a, &b);
+    /// ```
+    pub fn concat<const A: usize, const B: usize>(a: &String<A>, b: &String<B>) -> Self {
+        // Const assert N >= A + B
+        crate::sealed::greater_than_eq_sum::<N, A, B>();
+
+        let mut s = Self::new();
+        // NOTE(unwrap) `N >= A + B` was asserted above
+        s.vec.extend_from_copy_slice(a.as_bytes()).unwrap();
+        s.vec.extend_from_copy_slice(b.as_bytes()).unwrap();
+        s
+    }
+
+    /// Concatenates string slices into a new `String`.
+    ///
+    /// Returns `Err(())` if the `String` cannot hold the result, instead of truncating it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let topic: String<16> = String::try_concat(&["sensors", "/", "42"]).unwrap();
+    /// assert_eq!(topic, "sensors/42");
+    ///
+    /// assert!(String::<8>::try_concat(&["sensors", "/", "42"]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn try_concat(parts: &[&str]) -> Result<Self, ()> {
+        let len = parts
+            .iter()
+            .try_fold(0usize, |len, part| len.checked_add(part.len()));
+        match len {
+            Some(len) if len <= N => {}
+            _ => return Err(()),
+        }
+
+        let mut s = Self::new();
+        for part in parts {
+            // NOTE(unwrap) the capacity was checked above
+            s.push_str(part).unwrap();
+        }
+        Ok(s)
+    }
 }
 
 impl<S: Storage> StringInner<S> {
@@ -442,7 +633,42 @@ impl<S: Storage> StringInner<S> {
     #[inline]
     #[allow(clippy::result_unit_err)]
     pub fn push_str(&mut self, string: &str) -> Result<(), ()> {
-        self.vec.extend_from_slice(string.as_bytes())
+        self.vec.extend_from_copy_slice(string.as_bytes())
+    }
+
+    /// Appends as much of the given string slice as fits onto the end of this `String`, cutting
+    /// it on a [`char`] boundary.
+    ///
+    /// Returns the number of bytes that were appended.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::try_from("foo")?;
+    ///
+    /// assert_eq!(s.push_str_lossy("bar"), 3);
+    /// assert_eq!("foobar", s);
+    ///
+    /// // 'é' takes two bytes and doesn't fit anymore
+    /// assert_eq!(s.push_str_lossy("dé"), 1);
+    /// assert_eq!("foobard", s);
+    /// # Ok::<(), ()>(())
+    /// ```
+    pub fn push_str_lossy(&mut self, string: &str) -> usize {
+        let mut n = string.len().min(self.capacity() - self.len());
+        while !string.is_char_boundary(n) {
+            n -= 1;
+        }
+
+        // NOTE(unwrap) `n` bytes fit in the string
+        self.vec
+            .extend_from_copy_slice(&string.as_bytes()[..n])
+            .unwrap();
+        n
     }
 
     /// Returns the maximum number of elements the String can hold.
@@ -489,7 +715,34 @@ impl<S: Storage> StringInner<S> {
             1 => self.vec.push(c as u8).map_err(|_| {}),
             _ => self
                 .vec
-                .extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                .extend_from_copy_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
+    /// Appends the given [`char`] to the end of this `String` if it fits.
+    ///
+    /// Returns the number of bytes that were appended, which is either `0` or the length of the
+    /// UTF-8 encoding of `c`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<4> = String::try_from("ab")?;
+    ///
+    /// assert_eq!(s.push_lossy('é'), 2);
+    /// assert_eq!(s.push_lossy('c'), 0);
+    /// assert_eq!("abé", s);
+    /// # Ok::<(), ()>(())
+    /// ```
+    #[inline]
+    pub fn push_lossy(&mut self, c: char) -> usize {
+        match self.push(c) {
+            Ok(()) => c.len_utf8(),
+            Err(()) => 0,
         }
     }
 
@@ -527,6 +780,88 @@ impl<S: Storage> StringInner<S> {
         }
     }
 
+    /// Shortens this `String` to at most `max_len` bytes, cutting it on the closest [`char`]
+    /// boundary at or before `max_len`.
+    ///
+    /// If `max_len` is greater than the string's current length, this has no effect. Unlike
+    /// [`truncate`](Self::truncate), this never panics.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("❤️🧡💛")?;
+    ///
+    /// // '❤' and its variation selector take 6 bytes
+    /// s.truncate_to_boundary(8);
+    ///
+    /// assert_eq!("❤️", s);
+    /// # Ok::<(), ()>(())
+    /// ```
+    #[inline]
+    pub fn truncate_to_boundary(&mut self, max_len: usize) {
+        let new_len = self.floor_char_boundary(max_len);
+        self.vec.truncate(new_len)
+    }
+
+    /// Finds the closest `x` not exceeding `index` where `is_char_boundary(x)` is `true`.
+    ///
+    /// If `index` is greater than the length of the string, this returns the length of the
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::try_from("❤️🧡💛")?;
+    /// assert_eq!(s.floor_char_boundary(13), 10);
+    /// assert_eq!(s.floor_char_boundary(20), 14);
+    /// # Ok::<(), ()>(())
+    /// ```
+    #[inline]
+    pub fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            self.len()
+        } else {
+            // A char is at most 4 bytes long
+            (index.saturating_sub(3)..=index)
+                .rev()
+                .find(|&i| self.is_char_boundary(i))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Finds the closest `x` not below `index` where `is_char_boundary(x)` is `true`.
+    ///
+    /// If `index` is greater than the length of the string, this returns the length of the
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::try_from("❤️🧡💛")?;
+    /// assert_eq!(s.ceil_char_boundary(7), 10);
+    /// assert_eq!(s.ceil_char_boundary(20), 14);
+    /// # Ok::<(), ()>(())
+    /// ```
+    #[inline]
+    pub fn ceil_char_boundary(&self, index: usize) -> usize {
+        if index > self.len() {
+            self.len()
+        } else {
+            // A char is at most 4 bytes long
+            (index..=(index + 3).min(self.len()))
+                .find(|&i| self.is_char_boundary(i))
+                .unwrap_or(self.len())
+        }
+    }
+
     /// Removes the last character from the string buffer and returns it.
     ///
     /// Returns [`None`] if this `String` is empty.
@@ -560,6 +895,139 @@ impl<S: Storage> StringInner<S> {
         Some(ch)
     }
 
+    /// Inserts a character into this `String` at a byte position.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the
+    /// buffer.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold the character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<4> = String::new();
+    ///
+    /// s.insert(0, 'f').unwrap();
+    /// s.insert(1, 'o').unwrap();
+    /// s.insert(2, 'o').unwrap();
+    ///
+    /// assert_eq!(s, "foo");
+    /// assert!(s.insert(0, 'ö').is_err());
+    /// ```
+    #[inline]
+    #[allow(clippy::result_unit_err)]
+    pub fn insert(&mut self, idx: usize, ch: char) -> Result<(), ()> {
+        self.insert_str(idx, ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Inserts a string slice into this `String` at a byte position.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the
+    /// buffer.
+    ///
+    /// Returns `Err(())` without modifying the string if it cannot hold `string`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::try_from("bar").unwrap();
+    ///
+    /// s.insert_str(0, "foo").unwrap();
+    ///
+    /// assert_eq!(s, "foobar");
+    /// assert!(s.insert_str(3, "baz").is_err());
+    /// ```
+    #[inline]
+    #[allow(clippy::result_unit_err)]
+    pub fn insert_str(&mut self, idx: usize, string: &str) -> Result<(), ()> {
+        assert!(self.is_char_boundary(idx), "index must be a char boundary");
+
+        self.vec.insert_from_slice(idx, string.as_bytes())
+    }
+
+    /// Splits the string into two at the given byte index.
+    ///
+    /// Returns a new `String` containing bytes `[at, len)`. After the call, the original string
+    /// will be left containing bytes `[0, at)`.
+    ///
+    /// Returns `Err(())`, leaving the original string unmodified, if the returned string cannot
+    /// hold `len - at` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is not on a UTF-8 code point boundary, or if it is beyond the last
+    /// code point of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut hello: String<16> = String::try_from("Hello, World!").unwrap();
+    /// let world: String<8> = hello.split_off(7).unwrap();
+    /// assert_eq!(hello, "Hello, ");
+    /// assert_eq!(world, "World!");
+    ///
+    /// // The tail doesn't fit in a `String` of capacity 4
+    /// assert!(hello.split_off::<4>(0).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<String<M>, ()> {
+        assert!(self.is_char_boundary(at));
+
+        Ok(String {
+            vec: self.vec.split_off(at)?,
+        })
+    }
+
+    /// Removes the specified range in the string, and replaces it with the given string.
+    /// The given string doesn't need to be the same length as the range.
+    ///
+    /// Returns `Err(())` without modifying the string if the result wouldn't fit in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point or end point do not lie on a [`char`]
+    /// boundary, or if they're out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<32> = String::try_from("Hello, {name}!").unwrap();
+    /// let start = s.find('{').unwrap();
+    /// let end = s.find('}').unwrap() + 1;
+    ///
+    /// s.replace_range(start..end, "world").unwrap();
+    /// assert_eq!(s, "Hello, world!");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &str) -> Result<(), ()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = crate::slice::range(range, ..self.len());
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        self.vec.replace_range(start..end, replace_with.as_bytes())
+    }
+
     /// Removes a [`char`] from this `String` at a byte position and returns it.
     ///
     /// Note: Because this shifts over the remaining elements, it has a
@@ -600,9 +1068,155 @@ impl<S: Storage> StringInner<S> {
         ch
     }
 
-    /// Truncates this `String`, removing all contents.
+    /// Removes all matches of a pattern from the string, in place.
     ///
-    /// While this means the `String` will have a length of zero, it does not
+    /// Each byte is moved at most once, however many matches there are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<64> = String::try_from("Trees are not green, the sky is not blue.").unwrap();
+    /// s.remove_matches("not ");
+    /// assert_eq!("Trees are green, the sky is blue.", s);
+    ///
+    /// // Use `char::encode_utf8` to remove a `char`
+    /// let mut s: String<32> = String::try_from("a,b,c").unwrap();
+    /// s.remove_matches(','.encode_utf8(&mut [0; 4]));
+    /// assert_eq!("abc", s);
+    /// ```
+    pub fn remove_matches(&mut self, pat: &str) {
+        if pat.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        let ptr = self.vec.as_mut_ptr();
+        let mut read = 0;
+        let mut write = 0;
+        loop {
+            // SAFETY: `read` is on a char boundary, and the bytes from `read` on have not been
+            // overwritten yet, as `write <= read`.
+            let rest = unsafe {
+                str::from_utf8_unchecked(core::slice::from_raw_parts(ptr.add(read), len - read))
+            };
+            let (keep, next) = match rest.find(pat) {
+                Some(i) => (i, read + i + pat.len()),
+                None => (rest.len(), len),
+            };
+            // SAFETY: `keep` bytes starting at `read` are valid UTF-8 and `write + keep <= len`.
+            unsafe { core::ptr::copy(ptr.add(read), ptr.add(write), keep) };
+            write += keep;
+            read = next;
+            if read == len {
+                break;
+            }
+        }
+
+        // SAFETY: the bytes before `write` are the concatenation of the non matching parts of
+        // the string, so valid UTF-8.
+        unsafe { self.vec.set_len(write) };
+    }
+
+    /// Retains only the characters specified by the predicate.
+    ///
+    /// In other words, remove all characters `c` such that `f(c)` returns `false`.
+    /// This method operates in place, visiting each character exactly once in the
+    /// original order, and preserves the order of the retained characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("f_o_ob_ar").unwrap();
+    ///
+    /// s.retain(|c| c != '_');
+    ///
+    /// assert_eq!(s, "foobar");
+    /// ```
+    ///
+    /// Because the elements are visited exactly once in the original order,
+    /// external state may be used to decide which elements to keep.
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::try_from("abcde").unwrap();
+    /// let keep = [false, true, true, false, true];
+    /// let mut iter = keep.iter();
+    /// s.retain(|_| *iter.next().unwrap());
+    /// assert_eq!(s, "bce");
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        // Truncates the string to the processed and retained part if `f` panics.
+        struct SetLenOnDrop<'a, S: Storage> {
+            s: &'a mut StringInner<S>,
+            idx: usize,
+            del_bytes: usize,
+        }
+
+        impl<S: Storage> Drop for SetLenOnDrop<'_, S> {
+            fn drop(&mut self) {
+                let new_len = self.idx - self.del_bytes;
+                debug_assert!(new_len <= self.s.len());
+                unsafe { self.s.vec.set_len(new_len) };
+            }
+        }
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop {
+            s: self,
+            idx: 0,
+            del_bytes: 0,
+        };
+
+        while guard.idx < len {
+            // SAFETY: `guard.idx` is positive-or-zero and less that len so the `get_unchecked`
+            // is in bound. `self` is valid UTF-8 like string and the returned slice starts at
+            // a unicode code point so the `Chars` always return one character.
+            let ch = unsafe {
+                guard
+                    .s
+                    .get_unchecked(guard.idx..len)
+                    .chars()
+                    .next()
+                    .unwrap_unchecked()
+            };
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.del_bytes += ch_len;
+            } else if guard.del_bytes > 0 {
+                // SAFETY: `guard.idx` is in bound and `guard.del_bytes` represent the number of
+                // bytes that are erased from the string so the resulting `guard.idx -
+                // guard.del_bytes` always represent a valid unicode code point.
+                //
+                // `guard.del_bytes` >= `ch.len_utf8()`, so taking a slice with `ch.len_utf8()`
+                // len is safe.
+                ch.encode_utf8(unsafe {
+                    core::slice::from_raw_parts_mut(
+                        guard.s.vec.as_mut_ptr().add(guard.idx - guard.del_bytes),
+                        ch_len,
+                    )
+                });
+            }
+
+            // Point idx to the next char
+            guard.idx += ch_len;
+        }
+
+        drop(guard);
+    }
+
+    /// Truncates this `String`, removing all contents.
+    ///
+    /// While this means the `String` will have a length of zero, it does not
     /// touch its capacity.
     ///
     /// # Examples
@@ -625,6 +1239,184 @@ impl<S: Storage> StringInner<S> {
     pub fn clear(&mut self) {
         self.vec.clear()
     }
+
+    /// Returns the uppercase equivalent of this string, as a new `String`.
+    ///
+    /// 'Uppercase' is defined according to the terms of the Unicode Derived Core Property
+    /// `Uppercase`, like [`str::to_uppercase`].
+    ///
+    /// Returns `Err(())` if the result doesn't fit in a `String<M>`. Note that case mapping can
+    /// make a string longer.
+    ///
+    /// To uppercase ASCII characters in place, use [`str::make_ascii_uppercase`].
+    ///
+    /// [`str::to_uppercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_uppercase
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<8> = String::try_from("tschüß").unwrap();
+    /// let upper: String<16> = s.to_uppercase().unwrap();
+    /// assert_eq!(upper, "TSCHÜSS");
+    ///
+    /// assert!(s.to_uppercase::<7>().is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_uppercase<const M: usize>(&self) -> Result<String<M>, ()> {
+        let mut s = String::new();
+        for c in self.chars().flat_map(char::to_uppercase) {
+            s.push(c)?;
+        }
+        Ok(s)
+    }
+
+    /// Returns the lowercase equivalent of this string, as a new `String`.
+    ///
+    /// 'Lowercase' is defined according to the terms of the Unicode Derived Core Property
+    /// `Lowercase`. Unlike [`str::to_lowercase`], each character is mapped on its own, so
+    /// a final `Σ` is mapped to `σ` rather than `ς`.
+    ///
+    /// Returns `Err(())` if the result doesn't fit in a `String<M>`. Note that case mapping can
+    /// make a string longer.
+    ///
+    /// To lowercase ASCII characters in place, use [`str::make_ascii_lowercase`].
+    ///
+    /// [`str::to_lowercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<8> = String::try_from("HeLLo").unwrap();
+    /// let lower: String<8> = s.to_lowercase().unwrap();
+    /// assert_eq!(lower, "hello");
+    ///
+    /// // 'İ' lowercases to 'i' followed by a combining dot
+    /// let s: String<8> = String::try_from("İİ").unwrap();
+    /// assert!(s.to_lowercase::<4>().is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_lowercase<const M: usize>(&self) -> Result<String<M>, ()> {
+        let mut s = String::new();
+        for c in self.chars().flat_map(char::to_lowercase) {
+            s.push(c)?;
+        }
+        Ok(s)
+    }
+
+    /// Replaces all matches of a pattern with another string, returning the result as a new
+    /// `String`.
+    ///
+    /// Returns `Err(())` if the result doesn't fit in a `String<M>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::try_from("AT+X=$,$").unwrap();
+    /// let cmd: String<16> = s.replace("$", "1").unwrap();
+    /// assert_eq!(cmd, "AT+X=1,1");
+    ///
+    /// assert!(s.replace::<8>("$", "10").is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn replace<const M: usize>(&self, from: &str, to: &str) -> Result<String<M>, ()> {
+        self.replacen(from, to, usize::MAX)
+    }
+
+    /// Replaces the first `count` matches of a pattern with another string, returning the
+    /// result as a new `String`.
+    ///
+    /// Returns `Err(())` if the result doesn't fit in a `String<M>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::try_from("foo foo 123 foo").unwrap();
+    /// let new: String<16> = s.replacen("foo", "new", 2).unwrap();
+    /// assert_eq!(new, "new new 123 foo");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn replacen<const M: usize>(
+        &self,
+        from: &str,
+        to: &str,
+        count: usize,
+    ) -> Result<String<M>, ()> {
+        let mut result = String::new();
+        let mut last_end = 0;
+        for (start, part) in self.match_indices(from).take(count) {
+            // SAFETY: `start` and `last_end` are on char boundaries, as returned by
+            // `match_indices`.
+            result.push_str(unsafe { self.get_unchecked(last_end..start) })?;
+            result.push_str(to)?;
+            last_end = start + part.len();
+        }
+        // SAFETY: `last_end` is on a char boundary
+        result.push_str(unsafe { self.get_unchecked(last_end..self.len()) })?;
+        Ok(result)
+    }
+
+    /// Splits the string by `delimiter` into a `Vec` of at most `M` fields.
+    ///
+    /// See [`str_split_collect`] for details.
+    ///
+    /// Returns `Err(())` if the string has more than `M` fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, Vec};
+    ///
+    /// let line: String<32> = String::try_from("12.5;8;ok").unwrap();
+    /// let fields: Vec<&str, 3> = line.split_collect(";").unwrap();
+    /// assert_eq!(fields, ["12.5", "8", "ok"]);
+    ///
+    /// assert!(line.split_collect::<2>(";").is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn split_collect<const M: usize>(&self, delimiter: &str) -> Result<Vec<&str, M>, ()> {
+        str_split_collect(self, delimiter)
+    }
+
+    /// Creates a new `String` by repeating this string `n` times.
+    ///
+    /// Returns `Err(())` if the result doesn't fit in a `String<M>`, instead of truncating it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<4> = String::try_from("=-").unwrap();
+    /// let separator: String<16> = s.repeat(5).unwrap();
+    /// assert_eq!(separator, "=-=-=-=-=-");
+    ///
+    /// assert!(s.repeat::<16>(9).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn repeat<const M: usize>(&self, n: usize) -> Result<String<M>, ()> {
+        match self.len().checked_mul(n) {
+            Some(len) if len <= M => {}
+            _ => return Err(()),
+        }
+
+        let mut result = String::new();
+        if self.is_empty() {
+            return Ok(result);
+        }
+        for _ in 0..n {
+            // NOTE(unwrap) the capacity was checked above
+            result.push_str(self).unwrap();
+        }
+        Ok(result)
+    }
 }
 
 impl<const N: usize> Default for String<N> {
@@ -682,6 +1474,71 @@ impl<'a, const N: usize> iter::FromIterator<&'a str> for String<N> {
     }
 }
 
+/// Returns the number of items consumed when an item didn't fit in the string.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{
+///     iter::{TryCollect, TryFromIterator},
+///     string::TryFromIterError,
+///     String,
+/// };
+///
+/// let s: String<8> = "hello".chars().try_collect().unwrap();
+/// assert_eq!(s, "hello");
+///
+/// assert_eq!(
+///     String::<4>::try_from_iter("hello".chars()),
+///     Err(TryFromIterError { consumed: 5 })
+/// );
+/// ```
+impl<const N: usize> TryFromIterator<char> for String<N> {
+    type Error = TryFromIterError;
+
+    fn try_from_iter<T: IntoIterator<Item = char>>(iter: T) -> Result<Self, Self::Error> {
+        let mut new = String::new();
+        for (i, c) in iter.into_iter().enumerate() {
+            new.push(c)
+                .map_err(|()| TryFromIterError { consumed: i + 1 })?;
+        }
+        Ok(new)
+    }
+}
+
+/// Returns the number of items consumed when an item didn't fit in the string.
+impl<'a, const N: usize> TryFromIterator<&'a char> for String<N> {
+    type Error = TryFromIterError;
+
+    fn try_from_iter<T: IntoIterator<Item = &'a char>>(iter: T) -> Result<Self, Self::Error> {
+        Self::try_from_iter(iter.into_iter().copied())
+    }
+}
+
+/// Returns the number of items consumed when an item didn't fit in the string.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{iter::TryFromIterator, string::TryFromIterError, String};
+///
+/// let words = ["abc", "de", "fgh"];
+/// let s = String::<6>::try_from_iter(words.iter().copied()).unwrap_err();
+/// assert_eq!(s, TryFromIterError { consumed: 3 });
+/// ```
+impl<'a, const N: usize> TryFromIterator<&'a str> for String<N> {
+    type Error = TryFromIterError;
+
+    fn try_from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Result<Self, Self::Error> {
+        let mut new = String::new();
+        for (i, s) in iter.into_iter().enumerate() {
+            new.push_str(s)
+                .map_err(|()| TryFromIterError { consumed: i + 1 })?;
+        }
+        Ok(new)
+    }
+}
+
 #[cfg(feature="copy")]
 impl<const N: usize> Copy for String<N> {
 }
@@ -723,6 +1580,37 @@ impl<S: Storage> fmt::Write for StringInner<S> {
     }
 }
 
+/// # Panics
+///
+/// Panics if the string cannot hold all the characters of the iterator.
+impl<S: Storage> Extend<char> for StringInner<S> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        for c in iter {
+            self.push(c).unwrap();
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the string cannot hold all the characters of the iterator.
+impl<'a, S: Storage> Extend<&'a char> for StringInner<S> {
+    fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+/// # Panics
+///
+/// Panics if the string cannot hold all the strings of the iterator.
+impl<'a, S: Storage> Extend<&'a str> for StringInner<S> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_str(s).unwrap();
+        }
+    }
+}
+
 impl<S: Storage> ops::Deref for StringInner<S> {
     type Target = str;
 
@@ -816,6 +1704,36 @@ impl<S: Storage> Ord for StringInner<S> {
     }
 }
 
+/// Splits `s` by `delimiter` into a `Vec` of at most `M` fields.
+///
+/// The fields are the same as those returned by [`str::split`]: an empty string yields a single
+/// empty field, and consecutive delimiters yield empty fields between them.
+///
+/// Returns `Err(())` if `s` has more than `M` fields.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{string::str_split_collect, Vec};
+///
+/// let fields: Vec<&str, 4> = str_split_collect("+CSQ: 21,99", ",").unwrap();
+/// assert_eq!(fields, ["+CSQ: 21", "99"]);
+///
+/// assert_eq!(str_split_collect::<4>("a,,b", ",").unwrap(), ["a", "", "b"]);
+/// assert!(str_split_collect::<2>("a,b,c", ",").is_err());
+/// ```
+#[allow(clippy::result_unit_err)]
+pub fn str_split_collect<'a, const M: usize>(
+    s: &'a str,
+    delimiter: &str,
+) -> Result<Vec<&'a str, M>, ()> {
+    let mut fields = Vec::new();
+    for field in s.split(delimiter) {
+        fields.push(field).map_err(drop)?;
+    }
+    Ok(fields)
+}
+
 /// Equivalent to [`format`](https://doc.rust-lang.org/std/fmt/fn.format.html).
 ///
 /// Please note that using [`format!`] might be preferable.
@@ -882,15 +1800,75 @@ macro_rules! format {
     // Without semicolon as separator to disambiguate between arms, Rust just
     // chooses the first so that the format string would land in $max.
     ($max:expr; $($arg:tt)*) => {{
-        let res = $crate::_export::format::<$max>(core::format_args!($($arg)*));
+        let res = $crate::_export::format::<$max>(::core::format_args!($($arg)*));
         res
     }};
     ($($arg:tt)*) => {{
-        let res = $crate::_export::format(core::format_args!($($arg)*));
+        let res = $crate::_export::format(::core::format_args!($($arg)*));
         res
     }};
 }
 
+/// Implementation detail of [`str!`](crate::str!).
+///
+/// Panics, or fails to compile when evaluated in a const context, if `s` is longer than `N`
+/// bytes.
+#[doc(hidden)]
+pub const fn str_literal<const N: usize>(s: &str) -> String<N> {
+    if s.len() > N {
+        panic!("string literal exceeds the capacity");
+    }
+    // NOTE(unsafe) the bytes were copied from a `str`
+    unsafe { String::from_utf8_unchecked(Vec::from_copy_slice(s.as_bytes())) }
+}
+
+/// Macro that creates a fixed capacity [`String`] from a string literal, evaluated at compile
+/// time.
+///
+/// By default the capacity of the returned string is the length of the literal in bytes, so it
+/// never needs to be counted by hand. A larger capacity can be specified as the first argument,
+/// separated by a semicolon like with [`format!`](crate::format!). A compile-time error is
+/// produced if the literal doesn't fit.
+///
+/// The argument can be any constant expression of type `&'static str`, like a `const` item or
+/// [`concat!`].
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{str, String};
+///
+/// let s = str!("ĝis revido");
+/// assert_eq!(s, "ĝis revido");
+/// assert_eq!(s.capacity(), 11);
+///
+/// const DEVICE: &str = "heapless";
+/// static GREETING: String<32> = str!(32; concat!("hello from ", "heapless"));
+/// assert_eq!(GREETING, "hello from heapless");
+///
+/// let mut status = str!(16; DEVICE);
+/// status.push_str(": ok").unwrap();
+/// assert_eq!(status, "heapless: ok");
+/// ```
+///
+/// ```compile_fail
+/// use heapless::str;
+///
+/// let s = str!(4; "hello");
+/// ```
+#[macro_export]
+macro_rules! str {
+    ($max:expr; $s:expr) => {{
+        const STRING: $crate::String<{ $max }> = $crate::_export::str_literal($s);
+        STRING
+    }};
+    ($s:expr $(,)?) => {{
+        const S: &str = $s;
+        const STRING: $crate::String<{ S.len() }> = $crate::_export::str_literal(S);
+        STRING
+    }};
+}
+
 macro_rules! impl_try_from_num {
     ($num:ty, $size:expr) => {
         impl<const N: usize> core::convert::TryFrom<$num> for String<N> {
@@ -916,6 +1894,7 @@ impl_try_from_num!(u64, 20);
 
 #[cfg(test)]
 mod tests {
+    use super::TryFromIterError;
     use crate::{String, Vec};
 
     #[test]
@@ -1012,6 +1991,33 @@ mod tests {
         assert_eq!(string2, "hello");
     }
 
+    #[test]
+    fn try_from_iter() {
+        use crate::iter::{TryCollect, TryFromIterator};
+
+        let v = ['a', 'ĝ', 'c'];
+        let s: String<4> = v.iter().try_collect().unwrap();
+        assert_eq!(s, "aĝc");
+        assert_eq!(
+            String::<2>::try_from_iter(v.iter()),
+            Err(TryFromIterError { consumed: 2 })
+        );
+
+        let s: String<5> = ["ab", "", "cde"].into_iter().try_collect().unwrap();
+        assert_eq!(s, "abcde");
+        assert_eq!(
+            String::<4>::try_from_iter(["ab", "", "cde", "f"]),
+            Err(TryFromIterError { consumed: 3 })
+        );
+
+        let s: String<0> = "".chars().try_collect().unwrap();
+        assert_eq!(s, "");
+        assert_eq!(
+            "x".chars().cycle().try_collect::<String<8>>(),
+            Err(TryFromIterError { consumed: 9 })
+        );
+    }
+
     #[test]
     #[should_panic]
     fn from_panic() {
@@ -1152,6 +2158,373 @@ mod tests {
         assert_eq!(s.as_str(), "hey");
     }
 
+    #[test]
+    fn insert() {
+        let mut s: String<6> = String::try_from("ac").unwrap();
+        s.insert(1, 'b').unwrap();
+        assert_eq!(s, "abc");
+        s.insert(3, 'ė').unwrap();
+        assert_eq!(s, "abcė");
+        assert!(s.insert(0, 'ė').is_err());
+        s.insert(0, '_').unwrap();
+        assert_eq!(s, "_abcė");
+        assert!(s.insert(0, 'x').is_err());
+        assert_eq!(s, "_abcė");
+    }
+
+    #[test]
+    fn insert_str() {
+        let mut s: String<8> = String::try_from("ĝē").unwrap();
+        s.insert_str(2, "ab").unwrap();
+        assert_eq!(s, "ĝabē");
+        s.insert_str(6, "").unwrap();
+        assert!(s.insert_str(0, "xyz").is_err());
+        assert_eq!(s, "ĝabē");
+        s.insert_str(0, "xy").unwrap();
+        assert_eq!(s, "xyĝabē");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_not_char_boundary() {
+        let mut s: String<8> = String::try_from("ĝ").unwrap();
+        let _ = s.insert(1, 'a');
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_str_out_of_bounds() {
+        let mut s: String<8> = String::try_from("a").unwrap();
+        let _ = s.insert_str(2, "b");
+    }
+
+    #[test]
+    fn replace_range() {
+        let mut s: String<8> = String::try_from("aĝb").unwrap();
+        s.replace_range(1..3, "xyz").unwrap();
+        assert_eq!(s, "axyzb");
+        s.replace_range(..1, "").unwrap();
+        assert_eq!(s, "xyzb");
+        assert!(s.replace_range(4.., "12345").is_err());
+        assert_eq!(s, "xyzb");
+        s.replace_range(.., "ēē").unwrap();
+        assert_eq!(s, "ēē");
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_range_not_char_boundary() {
+        let mut s: String<8> = String::try_from("aĝb").unwrap();
+        let _ = s.replace_range(..2, "x");
+    }
+
+    #[test]
+    fn split_off() {
+        let mut s: String<8> = String::try_from("aĝbc").unwrap();
+        let t: String<4> = s.split_off(1).unwrap();
+        assert_eq!(s, "a");
+        assert_eq!(t, "ĝbc");
+
+        let mut t = t;
+        assert!(t.split_off::<3>(0).is_err());
+        assert_eq!(t, "ĝbc");
+        let u: String<0> = t.split_off(4).unwrap();
+        assert_eq!(u, "");
+        assert_eq!(t, "ĝbc");
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_not_char_boundary() {
+        let mut s: String<8> = String::try_from("ĝ").unwrap();
+        let _ = s.split_off::<8>(1);
+    }
+
+    #[test]
+    fn from_utf16() {
+        let v: std::vec::Vec<u16> = "ĝ𝄞x".encode_utf16().collect();
+        let s: String<8> = String::from_utf16(&v).unwrap();
+        assert_eq!(s, "ĝ𝄞x");
+        assert!(matches!(
+            String::<6>::from_utf16(&v),
+            Err(super::FromUtf16Error::Capacity)
+        ));
+        assert!(matches!(
+            String::<8>::from_utf16(&[0xD800]),
+            Err(super::FromUtf16Error::DecodeUtf16Error(_))
+        ));
+    }
+
+    #[test]
+    fn from_utf16_lossy() {
+        let s: String<8> = String::from_utf16_lossy(&[0x61, 0xDC00, 0x62]).unwrap();
+        assert_eq!(s, "a\u{FFFD}b");
+        let s: String<8> = String::from_utf16_lossy(&[]).unwrap();
+        assert_eq!(s, "");
+        assert!(String::<4>::from_utf16_lossy(&[0x61, 0xDC00, 0x62]).is_err());
+    }
+
+    #[test]
+    fn push_str_lossy() {
+        let mut s: String<6> = String::new();
+        assert_eq!(s.push_str_lossy("ab"), 2);
+        assert_eq!(s.push_str_lossy(""), 0);
+        assert_eq!(s.push_str_lossy("c𝄞"), 1);
+        assert_eq!(s, "abc");
+        assert_eq!(s.push_str_lossy("ĝēx"), 2);
+        assert_eq!(s, "abcĝ");
+        assert_eq!(s.push_str_lossy("x"), 1);
+        assert_eq!(s.push_str_lossy("x"), 0);
+        assert_eq!(s, "abcĝx");
+    }
+
+    #[test]
+    fn push_lossy() {
+        let mut s: String<3> = String::new();
+        assert_eq!(s.push_lossy('ĝ'), 2);
+        assert_eq!(s.push_lossy('ĝ'), 0);
+        assert_eq!(s.push_lossy('a'), 1);
+        assert_eq!(s, "ĝa");
+    }
+
+    #[test]
+    fn to_uppercase() {
+        let s: String<8> = String::try_from("aß1é").unwrap();
+        let upper: String<8> = s.to_uppercase().unwrap();
+        assert_eq!(upper, "ASS1É");
+        assert!(s.to_uppercase::<5>().is_err());
+        let view: String<8> = s.as_view().to_uppercase().unwrap();
+        assert_eq!(view, "ASS1É");
+    }
+
+    #[test]
+    fn to_lowercase() {
+        let s: String<8> = String::try_from("AΣ1É").unwrap();
+        let lower: String<8> = s.to_lowercase().unwrap();
+        assert_eq!(lower, "aσ1é");
+        assert!(s.to_lowercase::<5>().is_err());
+    }
+
+    #[test]
+    fn to_ascii_case() {
+        let mut s: String<8> = String::try_from("aB-É").unwrap();
+        assert_eq!(s.to_ascii_uppercase(), "AB-É");
+        assert_eq!(s.to_ascii_lowercase(), "ab-É");
+        s.make_ascii_uppercase();
+        assert_eq!(s, "AB-É");
+    }
+
+    #[test]
+    fn replace() {
+        let s: String<16> = String::try_from("aĝbĝc").unwrap();
+        let r: String<16> = s.replace("ĝ", "--").unwrap();
+        assert_eq!(r, "a--b--c");
+        let r: String<16> = s.replace("x", "--").unwrap();
+        assert_eq!(r, "aĝbĝc");
+        let r: String<3> = s.replace("ĝ", "").unwrap();
+        assert_eq!(r, "abc");
+        let r: String<16> = s.replace("", "|").unwrap();
+        assert_eq!(r, "|a|ĝ|b|ĝ|c|");
+        assert!(s.replace::<6>("ĝ", "--").is_err());
+    }
+
+    #[test]
+    fn replacen() {
+        let s: String<16> = String::try_from("aaaa").unwrap();
+        let r: String<16> = s.replacen("a", "b", 0).unwrap();
+        assert_eq!(r, "aaaa");
+        let r: String<16> = s.replacen("aa", "b", 1).unwrap();
+        assert_eq!(r, "baa");
+        let r: String<16> = s.replacen("a", "bc", 3).unwrap();
+        assert_eq!(r, "bcbcbca");
+    }
+
+    #[test]
+    fn view_mutation() {
+        use super::StringView;
+
+        fn edit(s: &mut StringView) {
+            s.push_str("abcĝ").unwrap();
+            s.push('e').unwrap();
+            assert_eq!(s.pop(), Some('e'));
+            s.insert(0, '_').unwrap();
+            s.insert_str(1, "xy").unwrap();
+            assert_eq!(s.remove(1), 'x');
+            s.retain(|c| c != 'b');
+            s.truncate(s.len() - 2);
+            s.extend(['1', '2']);
+            s.extend(["3", "4"]);
+            s.replace_range(0..1, "-").unwrap();
+            let drained: String<4> = s.drain(1..3).collect();
+            assert_eq!(drained, "ya");
+            s.remove_matches("3");
+        }
+
+        let mut s: String<16> = String::new();
+        edit(&mut s);
+        assert_eq!(s, "-c124");
+        assert_eq!(s.as_mut_view().capacity(), 16);
+
+        let mut s: String<32> = String::new();
+        edit(s.as_mut_view());
+        assert_eq!(s, "-c124");
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_overflow() {
+        let mut s: String<4> = String::new();
+        s.extend(["ab", "cd", "e"]);
+    }
+
+    #[test]
+    fn concat() {
+        let a: String<4> = String::try_from("aĝ").unwrap();
+        let b: String<2> = String::try_from("bc").unwrap();
+        let ab: String<6> = String::concat(&a, &b);
+        assert_eq!(ab, "aĝbc");
+        let empty: String<0> = String::new();
+        let a2: String<4> = String::concat(&a, &empty);
+        assert_eq!(a2, a);
+    }
+
+    #[test]
+    fn try_concat() {
+        let s: String<5> = String::try_concat(&["aĝ", "", "bc"]).unwrap();
+        assert_eq!(s, "aĝbc");
+        let s: String<0> = String::try_concat(&[]).unwrap();
+        assert_eq!(s, "");
+        assert!(String::<4>::try_concat(&["aĝ", "bc"]).is_err());
+    }
+
+    #[test]
+    fn str_macro() {
+        let s = crate::str!("aĝ");
+        let _: &String<3> = &s;
+        assert_eq!(s, "aĝ");
+
+        let empty = crate::str!("");
+        assert_eq!(empty.capacity(), 0);
+
+        const PADDED: String<8> = crate::str!(8; "abc");
+        let mut padded = PADDED;
+        padded.push_str("defgh").unwrap();
+        assert_eq!(padded, "abcdefgh");
+        assert_eq!(PADDED, "abc");
+
+        assert_eq!(super::str_literal::<3>("abc"), "abc");
+    }
+
+    #[test]
+    #[should_panic]
+    fn str_literal_overflow() {
+        let s = "abcde";
+        let _ = super::str_literal::<4>(core::hint::black_box(s));
+    }
+
+    #[test]
+    fn split_collect() {
+        use super::str_split_collect;
+
+        assert_eq!(str_split_collect::<1>("", ",").unwrap(), [""]);
+        assert!(str_split_collect::<0>("", ",").is_err());
+        assert_eq!(str_split_collect::<3>(",a,", ",").unwrap(), ["", "a", ""]);
+        assert_eq!(str_split_collect::<2>("aĝ--b", "--").unwrap(), ["aĝ", "b"]);
+        assert!(str_split_collect::<2>("abc", "").is_err());
+
+        let s: String<16> = String::try_from("1 2 3").unwrap();
+        let fields: Vec<&str, 3> = s.split_collect(" ").unwrap();
+        assert_eq!(fields, ["1", "2", "3"]);
+        assert!(s.split_collect::<2>(" ").is_err());
+    }
+
+    #[test]
+    fn repeat() {
+        let s: String<4> = String::try_from("aĝ").unwrap();
+        let r: String<9> = s.repeat(3).unwrap();
+        assert_eq!(r, "aĝaĝaĝ");
+        let r: String<0> = s.repeat(0).unwrap();
+        assert_eq!(r, "");
+        assert!(s.repeat::<8>(3).is_err());
+        assert!(s.repeat::<8>(usize::MAX).is_err());
+
+        let empty: String<4> = String::new();
+        let r: String<0> = empty.repeat(usize::MAX).unwrap();
+        assert_eq!(r, "");
+    }
+
+    #[test]
+    fn truncate_to_boundary() {
+        let mut s: String<16> = String::try_from("aĝ𝄞").unwrap();
+        s.truncate_to_boundary(16);
+        assert_eq!(s, "aĝ𝄞");
+        s.truncate_to_boundary(6);
+        assert_eq!(s, "aĝ");
+        s.truncate_to_boundary(2);
+        assert_eq!(s, "a");
+        s.truncate_to_boundary(0);
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn char_boundary() {
+        let s: String<16> = String::try_from("aĝ𝄞").unwrap();
+        let floor: std::vec::Vec<usize> = (0..9).map(|i| s.floor_char_boundary(i)).collect();
+        assert_eq!(floor, [0, 1, 1, 3, 3, 3, 3, 7, 7]);
+        let ceil: std::vec::Vec<usize> = (0..9).map(|i| s.ceil_char_boundary(i)).collect();
+        assert_eq!(ceil, [0, 1, 3, 3, 7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn remove_matches() {
+        let mut s: String<16> = String::try_from("ababĝab").unwrap();
+        s.remove_matches("ab");
+        assert_eq!(s, "ĝ");
+
+        let mut s: String<16> = String::try_from("aaa").unwrap();
+        s.remove_matches("aa");
+        assert_eq!(s, "a");
+
+        let mut s: String<16> = String::try_from("xĝyĝ").unwrap();
+        s.remove_matches("");
+        assert_eq!(s, "xĝyĝ");
+        s.remove_matches("ĝ");
+        assert_eq!(s, "xy");
+        s.remove_matches("z");
+        assert_eq!(s, "xy");
+        s.remove_matches("xy");
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn retain() {
+        let mut s: String<16> = String::try_from("a\r\nb\x07c").unwrap();
+        s.retain(|c| !c.is_control());
+        assert_eq!(s, "abc");
+
+        let mut s: String<16> = String::try_from("ĝxėyē").unwrap();
+        s.retain(|c| !c.is_ascii());
+        assert_eq!(s, "ĝėē");
+
+        s.retain(|_| false);
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn retain_panic() {
+        let mut s: String<16> = String::try_from("aβcd").unwrap();
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            s.retain(|c| match c {
+                'a' => false,
+                'c' => panic!(),
+                _ => true,
+            })
+        }));
+        assert!(res.is_err());
+        // Only the processed and retained part is kept
+        assert_eq!(s, "β");
+    }
+
     #[test]
     fn format() {
         let number = 5;
@@ -1167,6 +2540,15 @@ mod tests {
         assert_eq!(formatted, "005 plus 3.12")
     }
 
+    #[test]
+    fn format_shadowed_core() {
+        #[allow(dead_code)]
+        mod core {}
+
+        let formatted: String<8> = format!("{}", 42).unwrap();
+        assert_eq!(formatted, "42");
+    }
+
     #[test]
     fn format_overflow() {
         let i = 1234567;
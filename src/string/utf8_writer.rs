@@ -0,0 +1,255 @@
+use core::{fmt, str};
+
+use super::StringView;
+
+/// The error type for [`Utf8Writer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Utf8WriteError {
+    /// The capacity of the `String` is too small for the bytes written.
+    Capacity,
+    /// The bytes written are not valid UTF-8.
+    InvalidSequence,
+    /// The stream ended in the middle of a character.
+    Incomplete,
+}
+
+impl fmt::Display for Utf8WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capacity => "insufficient capacity".fmt(f),
+            Self::InvalidSequence => "invalid UTF-8 sequence".fmt(f),
+            Self::Incomplete => "incomplete UTF-8 sequence".fmt(f),
+        }
+    }
+}
+
+/// A writer that validates a stream of bytes as UTF-8 and appends it to a [`String`].
+///
+/// The stream may be split into chunks at any position, including in the middle of a
+/// character: the bytes of an incomplete character at the end of a chunk are kept until the
+/// next chunk completes it.
+///
+/// [`String`]: super::String
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{string::Utf8Writer, String};
+///
+/// let mut s: String<16> = String::new();
+/// let mut writer = Utf8Writer::new(&mut s);
+///
+/// // "ĝ" is encoded as [0xc4, 0x9d]
+/// writer.write(b"a\xc4").unwrap();
+/// writer.write(b"\x9db").unwrap();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(s, "aĝb");
+/// ```
+pub struct Utf8Writer<'a> {
+    string: &'a mut StringView,
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+/// Returns the length of the UTF-8 encoding of a character starting with `first`.
+fn char_width(first: u8) -> usize {
+    match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        _ => 4,
+    }
+}
+
+impl<'a> Utf8Writer<'a> {
+    /// Creates a writer appending to `string`.
+    pub fn new(string: &'a mut StringView) -> Self {
+        Self {
+            string,
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    /// Validates `bytes` and appends the complete characters to the string.
+    ///
+    /// The bytes of an incomplete character at the end of `bytes` are kept until the next call.
+    ///
+    /// # Errors
+    ///
+    /// - [`Utf8WriteError::InvalidSequence`] if `bytes` contain an invalid UTF-8 sequence. The
+    ///   characters before it are appended, and the rest of `bytes` is discarded.
+    /// - [`Utf8WriteError::Capacity`] if the string cannot hold all the characters. As many
+    ///   characters as fit are appended, and the rest of `bytes` is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{
+    ///     string::{Utf8WriteError, Utf8Writer},
+    ///     String,
+    /// };
+    ///
+    /// let mut s: String<4> = String::new();
+    /// let mut writer = Utf8Writer::new(&mut s);
+    ///
+    /// assert_eq!(writer.write(b"a\xffb"), Err(Utf8WriteError::InvalidSequence));
+    /// assert_eq!(writer.write(b"cdef"), Err(Utf8WriteError::Capacity));
+    /// assert_eq!(s, "acde");
+    /// ```
+    pub fn write(&mut self, mut bytes: &[u8]) -> Result<(), Utf8WriteError> {
+        if self.pending_len > 0 {
+            let width = char_width(self.pending[0]);
+            let take = (width - self.pending_len).min(bytes.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&bytes[..take]);
+            self.pending_len += take;
+            bytes = &bytes[take..];
+
+            let pending = self.pending;
+            match str::from_utf8(&pending[..self.pending_len]) {
+                Ok(c) => {
+                    self.pending_len = 0;
+                    self.push_str(c)?;
+                }
+                // Still incomplete
+                Err(e) if e.error_len().is_none() => return Ok(()),
+                Err(_) => {
+                    self.pending_len = 0;
+                    return Err(Utf8WriteError::InvalidSequence);
+                }
+            }
+        }
+
+        match str::from_utf8(bytes) {
+            Ok(s) => self.push_str(s),
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                // NOTE(unsafe) `valid` is valid UTF-8, as reported by `from_utf8`
+                self.push_str(unsafe { str::from_utf8_unchecked(valid) })?;
+                match e.error_len() {
+                    Some(_) => Err(Utf8WriteError::InvalidSequence),
+                    None => {
+                        // `rest` is the start of a character, so at most 3 bytes long
+                        self.pending[..rest.len()].copy_from_slice(rest);
+                        self.pending_len = rest.len();
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    fn push_str(&mut self, s: &str) -> Result<(), Utf8WriteError> {
+        if self.string.push_str_lossy(s) == s.len() {
+            Ok(())
+        } else {
+            Err(Utf8WriteError::Capacity)
+        }
+    }
+
+    /// Returns `true` if the writer holds the bytes of an incomplete character.
+    pub fn has_pending(&self) -> bool {
+        self.pending_len > 0
+    }
+
+    /// Ends the stream, returning [`Utf8WriteError::Incomplete`] if it ended in the middle of a
+    /// character.
+    pub fn finish(self) -> Result<(), Utf8WriteError> {
+        if self.has_pending() {
+            Err(Utf8WriteError::Incomplete)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Debug for Utf8Writer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Utf8Writer")
+            .field("string", &self.string)
+            .field("pending", &&self.pending[..self.pending_len])
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Utf8WriteError, Utf8Writer};
+    use crate::String;
+
+    #[test]
+    fn split_chars() {
+        let text = "aĝ€𝄞z";
+        // Split the text at every possible position
+        for i in 0..=text.len() {
+            for j in i..=text.len() {
+                let bytes = text.as_bytes();
+                let mut s: String<16> = String::new();
+                let mut writer = Utf8Writer::new(&mut s);
+                writer.write(&bytes[..i]).unwrap();
+                writer.write(&bytes[i..j]).unwrap();
+                writer.write(&bytes[j..]).unwrap();
+                writer.finish().unwrap();
+                assert_eq!(s, text);
+            }
+        }
+    }
+
+    #[test]
+    fn byte_by_byte() {
+        let mut s: String<16> = String::new();
+        let mut writer = Utf8Writer::new(&mut s);
+        for b in "€𝄞".bytes() {
+            writer.write(&[b]).unwrap();
+        }
+        assert!(!writer.has_pending());
+        assert_eq!(s, "€𝄞");
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut s: String<16> = String::new();
+        let mut writer = Utf8Writer::new(&mut s);
+        writer.write(b"a\xf0\x9d").unwrap();
+        assert!(writer.has_pending());
+        assert_eq!(writer.finish(), Err(Utf8WriteError::Incomplete));
+        assert_eq!(s, "a");
+    }
+
+    #[test]
+    fn invalid() {
+        let mut s: String<16> = String::new();
+        let mut writer = Utf8Writer::new(&mut s);
+        writer.write(b"a\xc4").unwrap();
+        // Not a continuation byte
+        assert_eq!(writer.write(b"b"), Err(Utf8WriteError::InvalidSequence));
+        assert!(!writer.has_pending());
+        writer.write(b"c").unwrap();
+        // Overlong encoding
+        assert_eq!(
+            writer.write(b"\xe0\x80"),
+            Err(Utf8WriteError::InvalidSequence)
+        );
+        // Surrogate, split across chunks
+        writer.write(b"\xed").unwrap();
+        assert_eq!(
+            writer.write(b"\xa0\x80"),
+            Err(Utf8WriteError::InvalidSequence)
+        );
+        writer.finish().unwrap();
+        assert_eq!(s, "ac");
+    }
+
+    #[test]
+    fn capacity() {
+        let mut s: String<4> = String::new();
+        let mut writer = Utf8Writer::new(&mut s);
+        writer.write(b"ab\xe2\x82").unwrap();
+        assert_eq!(writer.write(b"\xac"), Err(Utf8WriteError::Capacity));
+        writer.write(b"c").unwrap();
+        assert_eq!(writer.write(b"\xc4\x9d"), Err(Utf8WriteError::Capacity));
+        writer.write(b"d").unwrap();
+        assert_eq!(s, "abcd");
+    }
+}
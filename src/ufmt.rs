@@ -11,7 +11,7 @@ impl<S: Storage> uWrite for StringInner<S> {
 impl<S: Storage> uWrite for VecInner<u8, S> {
     type Error = ();
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
-        self.extend_from_slice(s.as_bytes())
+        self.extend_from_copy_slice(s.as_bytes())
     }
 }
 
@@ -0,0 +1,156 @@
+use core::{fmt, ptr, slice};
+
+use super::VecView;
+
+/// An iterator which uses a closure to determine if an element should be removed.
+///
+/// This struct is created by [`Vec::extract_if`](super::Vec::extract_if).
+/// See its documentation for more.
+///
+/// # Example
+///
+/// ```
+/// use heapless::{vec, Vec};
+///
+/// let mut v = Vec::<_, 4>::from_array([0, 1, 2]);
+/// let iter: vec::ExtractIf<'_, _, _> = v.extract_if(.., |x| *x % 2 == 0);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, T, F> {
+    pub(super) vec: &'a mut VecView<T>,
+    /// The index of the item that will be inspected by the next call to `next`.
+    pub(super) idx: usize,
+    /// Elements at and beyond this point will be retained. Must be equal or smaller than `old_len`.
+    pub(super) end: usize,
+    /// The number of items that have been drained (removed) thus far.
+    pub(super) del: usize,
+    /// The original length of `vec` prior to draining.
+    pub(super) old_len: usize,
+    /// The filter test predicate.
+    pub(super) pred: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.idx;
+                let v = slice::from_raw_parts_mut(self.vec.as_mut_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                // Update the index *after* the predicate is called. If the index
+                // is updated prior and the predicate panics, the element at this
+                // index would be leaked.
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let del = self.del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<T, F> Drop for ExtractIf<'_, T, F> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let ptr = self.vec.as_mut_ptr();
+                let src = ptr.add(self.idx);
+                let dst = src.sub(self.del);
+                let tail_len = self.old_len - self.idx;
+                ptr::copy(src, dst, tail_len);
+            }
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for ExtractIf<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // NOTE(unsafe) `idx..end` contains elements that haven't been visited yet
+        let remaining =
+            unsafe { slice::from_raw_parts(self.vec.as_ptr().add(self.idx), self.end - self.idx) };
+        f.debug_tuple("ExtractIf").field(&remaining).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Vec;
+
+    #[test]
+    fn extract_if_all() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5, 6]);
+        let evens: Vec<_, 8> = vec.extract_if(.., |x| *x % 2 == 0).collect();
+        assert_eq!(evens, &[2, 4, 6]);
+        assert_eq!(vec, &[1, 3, 5]);
+    }
+
+    #[test]
+    fn extract_if_range() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5, 6]);
+        let evens: Vec<_, 8> = vec.extract_if(1..4, |x| *x % 2 == 0).collect();
+        assert_eq!(evens, &[2, 4]);
+        assert_eq!(vec, &[1, 3, 5, 6]);
+    }
+
+    #[test]
+    fn extract_if_partial() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5, 6]);
+        let mut iter = vec.extract_if(.., |x| *x % 2 == 0);
+        assert_eq!(iter.next(), Some(2));
+        drop(iter);
+        assert_eq!(vec, &[1, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn extract_if_mutate() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4]);
+        let removed: Vec<_, 8> = vec
+            .extract_if(.., |x| {
+                *x -= 1;
+                *x == 0
+            })
+            .collect();
+        assert_eq!(removed, &[0]);
+        assert_eq!(vec, &[1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn extract_if_drop() {
+        droppable!();
+
+        let mut vec = Vec::<_, 8>::from_array([
+            Droppable::new(),
+            Droppable::new(),
+            Droppable::new(),
+            Droppable::new(),
+        ]);
+        assert_eq!(Droppable::count(), 4);
+
+        vec.extract_if(.., |x| x.0 % 2 == 0).for_each(drop);
+        assert_eq!(Droppable::count(), 2);
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec[0].0, 1);
+        assert_eq!(vec[1].0, 3);
+
+        drop(vec);
+        assert_eq!(Droppable::count(), 0);
+    }
+}
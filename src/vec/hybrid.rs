@@ -0,0 +1,561 @@
+use alloc::vec::Vec as HeapVec;
+use core::{fmt, hash, iter::FusedIterator, mem, ops};
+
+use super::Vec;
+
+/// A vector that stores up to `N` elements inline and spills to the heap beyond that.
+///
+/// `HybridVec` mirrors the [`Vec`] API, except that operations that would fail on a full
+/// [`Vec`] move the elements to a heap allocated [`alloc::vec::Vec`] instead. Once spilled, the
+/// elements stay on the heap.
+///
+/// This type is only available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::vec::HybridVec;
+///
+/// let mut vec: HybridVec<u8, 2> = HybridVec::new();
+/// vec.push(1);
+/// vec.push(2);
+/// assert!(!vec.spilled());
+///
+/// vec.push(3);
+/// assert!(vec.spilled());
+/// assert_eq!(vec, [1, 2, 3]);
+/// ```
+pub struct HybridVec<T, const N: usize> {
+    inner: Inner<T, N>,
+}
+
+enum Inner<T, const N: usize> {
+    Inline(Vec<T, N>),
+    Heap(HeapVec<T>),
+}
+
+impl<T, const N: usize> HybridVec<T, N> {
+    /// Constructs a new, empty vector with an inline capacity of `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::vec::HybridVec;
+    ///
+    /// // allocate the vector on the stack
+    /// let mut x: HybridVec<u8, 16> = HybridVec::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            inner: Inner::Inline(Vec::new()),
+        }
+    }
+
+    /// Constructs a new vector and fills it with the provided slice.
+    ///
+    /// The elements are stored inline if they fit, on the heap otherwise.
+    pub fn from_slice(other: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let mut v = Self::new();
+        v.extend_from_slice(other);
+        v
+    }
+
+    /// Returns `true` if the elements have been moved to the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.inner, Inner::Heap(_))
+    }
+
+    /// Returns the number of elements the vector can hold without (re)allocating.
+    pub fn capacity(&self) -> usize {
+        match &self.inner {
+            Inner::Inline(_) => N,
+            Inner::Heap(v) => v.capacity(),
+        }
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        match &self.inner {
+            Inner::Inline(v) => v.len(),
+            Inner::Heap(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Extracts a slice containing the entire vector.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.inner {
+            Inner::Inline(v) => v,
+            Inner::Heap(v) => v,
+        }
+    }
+
+    /// Extracts a mutable slice containing the entire vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.inner {
+            Inner::Inline(v) => v,
+            Inner::Heap(v) => v,
+        }
+    }
+
+    /// Returns a mutable reference to the heap allocated vector, moving the elements to the heap
+    /// first if they are stored inline.
+    fn spill(&mut self, additional: usize) -> &mut HeapVec<T> {
+        if let Inner::Inline(v) = &mut self.inner {
+            let mut heap = HeapVec::with_capacity((v.len() + additional).max(2 * N));
+            heap.extend(mem::take(v));
+            self.inner = Inner::Heap(heap);
+        }
+
+        match &mut self.inner {
+            Inner::Heap(v) => v,
+            Inner::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Appends an `item` to the back of the collection, spilling to the heap if the inline
+    /// storage is full.
+    pub fn push(&mut self, item: T) {
+        match &mut self.inner {
+            Inner::Inline(v) => {
+                if let Err(item) = v.push(item) {
+                    self.spill(1).push(item);
+                }
+            }
+            Inner::Heap(v) => v.push(item),
+        }
+    }
+
+    /// Removes the last element from a vector and returns it, or `None` if it's empty.
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.inner {
+            Inner::Inline(v) => v.pop(),
+            Inner::Heap(v) => v.pop(),
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all elements after it
+    /// to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        match &mut self.inner {
+            Inner::Inline(v) => {
+                if let Err(element) = v.insert(index, element) {
+                    self.spill(1).insert(index, element);
+                }
+            }
+            Inner::Heap(v) => v.insert(index, element),
+        }
+    }
+
+    /// Removes and returns the element at position `index` within the vector, shifting all
+    /// elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.inner {
+            Inner::Inline(v) => v.remove(index),
+            Inner::Heap(v) => v.remove(index),
+        }
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match &mut self.inner {
+            Inner::Inline(v) => v.swap_remove(index),
+            Inner::Heap(v) => v.swap_remove(index),
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.inner {
+            Inner::Inline(v) => v.truncate(len),
+            Inner::Heap(v) => v.truncate(len),
+        }
+    }
+
+    /// Clears the vector, removing all values.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Clones and appends all elements in a slice to the vector, spilling to the heap if they
+    /// don't fit in the inline storage.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        match &mut self.inner {
+            Inner::Inline(v) => {
+                if v.extend_from_slice(other).is_err() {
+                    self.spill(other.len()).extend_from_slice(other);
+                }
+            }
+            Inner::Heap(v) => v.extend_from_slice(other),
+        }
+    }
+
+    /// Resizes the vector in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the vector is extended by the difference, with each
+    /// additional slot filled with `value`. If `new_len` is less than `len`, the vector is simply
+    /// truncated.
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        match &mut self.inner {
+            Inner::Inline(v) if new_len <= N => {
+                // NOTE(unwrap) `new_len` fits in the inline storage
+                v.resize(new_len, value).unwrap();
+            }
+            _ => {
+                let additional = new_len.saturating_sub(self.len());
+                self.spill(additional).resize(new_len, value);
+            }
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match &mut self.inner {
+            Inner::Inline(v) => v.retain(f),
+            Inner::Heap(v) => v.retain(f),
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, passing a mutable reference to it.
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        match &mut self.inner {
+            Inner::Inline(v) => v.retain_mut(f),
+            Inner::Heap(v) => v.retain_mut(f),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for HybridVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> From<Vec<T, N>> for HybridVec<T, N> {
+    fn from(vec: Vec<T, N>) -> Self {
+        Self {
+            inner: Inner::Inline(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> From<HeapVec<T>> for HybridVec<T, N> {
+    fn from(vec: HeapVec<T>) -> Self {
+        Self {
+            inner: Inner::Heap(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> Clone for HybridVec<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let inner = match &self.inner {
+            Inner::Inline(v) => Inner::Inline(v.clone()),
+            Inner::Heap(v) => Inner::Heap(v.clone()),
+        };
+        Self { inner }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for HybridVec<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl<T, const N: usize> ops::Deref for HybridVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> ops::DerefMut for HybridVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for HybridVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for HybridVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> hash::Hash for HybridVec<T, N>
+where
+    T: hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        <[T] as hash::Hash>::hash(self, state)
+    }
+}
+
+impl<A, B, const N1: usize, const N2: usize> PartialEq<HybridVec<B, N2>> for HybridVec<A, N1>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &HybridVec<B, N2>) -> bool {
+        <[A]>::eq(self, &**other)
+    }
+}
+
+impl<A, B, const N: usize, const M: usize> PartialEq<[B; M]> for HybridVec<A, N>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B; M]) -> bool {
+        <[A]>::eq(self, other)
+    }
+}
+
+impl<A, B, const N: usize> PartialEq<[B]> for HybridVec<A, N>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B]) -> bool {
+        <[A]>::eq(self, other)
+    }
+}
+
+impl<T, const N: usize> Eq for HybridVec<T, N> where T: Eq {}
+
+impl<T, const N: usize> Extend<T> for HybridVec<T, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.push(elem)
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for HybridVec<T, N>
+where
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for HybridVec<T, N> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a HybridVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut HybridVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for HybridVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self.inner {
+            Inner::Inline(v) => IntoIterInner::Inline(v.into_iter()),
+            Inner::Heap(v) => IntoIterInner::Heap(v.into_iter()),
+        };
+        IntoIter { inner }
+    }
+}
+
+/// An iterator that moves out of a [`HybridVec`].
+///
+/// This struct is created by calling the `into_iter` method on [`HybridVec`].
+pub struct IntoIter<T, const N: usize> {
+    inner: IntoIterInner<T, N>,
+}
+
+enum IntoIterInner<T, const N: usize> {
+    Inline(super::IntoIter<T, N>),
+    Heap(alloc::vec::IntoIter<T>),
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.inner {
+            IntoIterInner::Inline(it) => it.next(),
+            IntoIterInner::Heap(it) => it.next(),
+        }
+    }
+}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec as HeapVec;
+
+    use super::HybridVec;
+
+    #[test]
+    fn push_spills() {
+        let mut v: HybridVec<u8, 2> = HybridVec::new();
+        assert_eq!(v.capacity(), 2);
+        v.push(1);
+        v.push(2);
+        assert!(!v.spilled());
+        v.push(3);
+        assert!(v.spilled());
+        assert!(v.capacity() >= 3);
+        assert_eq!(v, [1, 2, 3]);
+
+        assert_eq!(v.pop(), Some(3));
+        assert!(v.spilled());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut v: HybridVec<u8, 2> = HybridVec::from_slice(&[1, 3]);
+        v.insert(1, 2);
+        assert!(v.spilled());
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(v.remove(0), 1);
+        assert_eq!(v.swap_remove(0), 2);
+        assert_eq!(v, [3]);
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut v: HybridVec<u8, 4> = HybridVec::new();
+        v.extend_from_slice(&[1, 2, 3]);
+        assert!(!v.spilled());
+        v.extend_from_slice(&[4, 5]);
+        assert!(v.spilled());
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn resize() {
+        let mut v: HybridVec<u8, 4> = HybridVec::new();
+        v.resize(4, 1);
+        assert!(!v.spilled());
+        v.resize(6, 2);
+        assert!(v.spilled());
+        assert_eq!(v, [1, 1, 1, 1, 2, 2]);
+        v.resize(1, 0);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn retain() {
+        let mut v: HybridVec<u8, 2> = (0..6).collect();
+        v.retain(|x| x % 2 == 0);
+        assert_eq!(v, [0, 2, 4]);
+        v.retain_mut(|x| {
+            *x += 1;
+            *x > 1
+        });
+        assert_eq!(v, [3, 5]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let v: HybridVec<u8, 4> = (0..3).collect();
+        assert_eq!(v.into_iter().collect::<HeapVec<_>>(), [0, 1, 2]);
+
+        let v: HybridVec<u8, 2> = (0..3).collect();
+        assert_eq!(v.into_iter().collect::<HeapVec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn drop() {
+        droppable!();
+
+        {
+            let mut v: HybridVec<Droppable, 2> = HybridVec::new();
+            v.push(Droppable::new());
+            v.push(Droppable::new());
+            v.push(Droppable::new());
+            assert_eq!(Droppable::count(), 3);
+            v.pop().unwrap();
+            assert_eq!(Droppable::count(), 2);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+
+        {
+            let mut v: HybridVec<Droppable, 2> = HybridVec::new();
+            v.push(Droppable::new());
+            let mut it = v.into_iter();
+            it.next().unwrap();
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+}
@@ -5,16 +5,34 @@ use core::{
     borrow::{Borrow, BorrowMut},
     cmp::Ordering,
     fmt, hash,
+    marker::PhantomData,
     mem::{self, ManuallyDrop, MaybeUninit},
     ops::{self, Range, RangeBounds},
     ptr::{self, NonNull},
     slice,
 };
 
-use crate::storage::{OwnedStorage, Storage, ViewStorage};
+use crate::{
+    iter::TryFromIterator,
+    storage::{Aligned, AlignedStorage, Alignment, OwnedStorage, Storage, ViewStorage},
+};
 
 mod drain;
+mod extract_if;
+#[cfg(feature = "alloc")]
+mod hybrid;
+#[cfg(feature = "embedded-io")]
+mod reader;
+mod splice;
+mod swap_drain;
 pub use drain::Drain;
+pub use extract_if::ExtractIf;
+#[cfg(feature = "alloc")]
+pub use hybrid::{HybridVec, IntoIter as HybridIntoIter};
+#[cfg(feature = "embedded-io")]
+pub use reader::VecReader;
+pub use splice::Splice;
+pub use swap_drain::SwapDrain;
 
 /// Base struct for [`Vec`] and [`VecView`], generic over the [`Storage`].
 ///
@@ -87,6 +105,27 @@ pub type Vec<T, const N: usize> = VecInner<T, OwnedStorage<N>>;
 /// ```
 pub type VecView<T> = VecInner<T, ViewStorage>;
 
+/// A [`Vec`] whose buffer is aligned to the [`Alignment`] `A`, regardless of the alignment of `T`.
+///
+/// This is useful for buffers with stricter alignment requirements than their elements, for
+/// example DMA buffers, cache-line aligned buffers or sector-sized flash writes.
+///
+/// All the methods available on [`VecInner`] are available on `AlignedVec`. Unlike [`Vec`],
+/// `AlignedVec` cannot be coerced into a [`VecView`].
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{storage::Align512, vec::AlignedVec};
+///
+/// let mut sector: AlignedVec<u8, 512, Align512> = AlignedVec::new();
+/// sector.extend_from_slice(&[0xAA; 16]).unwrap();
+///
+/// assert_eq!(sector.as_ptr() as usize % 512, 0);
+/// assert_eq!(sector.len(), 16);
+/// ```
+pub type AlignedVec<T, const N: usize, A> = VecInner<T, AlignedStorage<N, A>>;
+
 impl<T, const N: usize> Vec<T, N> {
     const ELEM: MaybeUninit<T> = MaybeUninit::uninit();
     const INIT: [MaybeUninit<T>; N] = [Self::ELEM; N]; // important for optimization of `new`
@@ -132,6 +171,62 @@ impl<T, const N: usize> Vec<T, N> {
         Ok(v)
     }
 
+    /// Constructs a new vector of length `len` where each element is produced by calling `f` with
+    /// that element's index.
+    ///
+    /// Returns `Err(())` without calling `f` if `len` is greater than the capacity of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<u32, 8> = Vec::from_fn(4, |i| (i * i) as u32).unwrap();
+    /// assert_eq!(v, [0, 1, 4, 9]);
+    ///
+    /// assert!(Vec::<u32, 8>::from_fn(9, |i| i as u32).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn from_fn<F>(len: usize, mut f: F) -> Result<Self, ()>
+    where
+        F: FnMut(usize) -> T,
+    {
+        if len > N {
+            return Err(());
+        }
+
+        let mut v = Vec::new();
+        for i in 0..len {
+            // NOTE(unsafe) `len <= N`, so the vector is never full here
+            unsafe { v.push_unchecked(f(i)) }
+        }
+        Ok(v)
+    }
+
+    /// Constructs a new vector filled to capacity, where each element is produced by calling `f`
+    /// with that element's index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// // A table of one period of a triangle wave
+    /// let table: Vec<i8, 8> = Vec::full_from_fn(|i| if i < 4 { i as i8 } else { 8 - i as i8 });
+    /// assert_eq!(table, [0, 1, 2, 3, 4, 3, 2, 1]);
+    /// ```
+    pub fn full_from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        let mut v = Vec::new();
+        for i in 0..N {
+            // NOTE(unsafe) `i < N`, so the vector is never full here
+            unsafe { v.push_unchecked(f(i)) }
+        }
+        v
+    }
+
     /// Constructs a new vector with a fixed capacity of `N`, initializing
     /// it with the provided array.
     ///
@@ -140,7 +235,16 @@ impl<T, const N: usize> Vec<T, N> {
     ///
     /// If the length of the provided array is greater than the capacity of the
     /// vector a compile-time error will be produced.
-    pub fn from_array<const M: usize>(src: [T; M]) -> Self {
+    ///
+    /// This function can be used in `const` contexts:
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// static TABLE: Vec<u16, 8> = Vec::from_array([1, 2, 4, 8]);
+    /// assert_eq!(TABLE, [1, 2, 4, 8]);
+    /// ```
+    pub const fn from_array<const M: usize>(src: [T; M]) -> Self {
         // Const assert M >= 0
         crate::sealed::greater_than_eq_0::<M>();
         // Const assert N >= M
@@ -150,24 +254,83 @@ impl<T, const N: usize> Vec<T, N> {
         // any Drop code for T.
         let src = ManuallyDrop::new(src);
 
-        if N == M {
-            Self {
-                len: N,
-                // NOTE(unsafe) ManuallyDrop<[T; M]> and [MaybeUninit<T>; N]
-                // have the same layout when N == M.
-                buffer: unsafe { mem::transmute_copy(&src) },
-            }
-        } else {
-            let mut v = Vec::<T, N>::new();
+        let mut v = Vec::<T, N>::new();
+        // NOTE(unsafe) src elements are not going to drop as src itself
+        // is wrapped in a ManuallyDrop, and `N >= M`.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &src as *const ManuallyDrop<[T; M]> as *const T,
+                v.buffer.as_mut_ptr() as *mut T,
+                M,
+            );
+        }
+        v.len = M;
+        v
+    }
 
-            for (src_elem, dst_elem) in src.iter().zip(v.buffer.iter_mut()) {
-                // NOTE(unsafe) src element is not going to drop as src itself
-                // is wrapped in a ManuallyDrop.
-                dst_elem.write(unsafe { ptr::read(src_elem) });
-            }
+    /// Constructs a new vector with a fixed capacity of `N` and fills it
+    /// with a copy of the provided slice.
+    ///
+    /// Unlike [`from_slice`](Self::from_slice), this function can be used in `const` contexts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector cannot hold all the elements of `other`. When evaluated in a `const`
+    /// context, this results in a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// const GREETING: Vec<u8, 16> = Vec::from_copy_slice(b"hello");
+    /// assert_eq!(GREETING, *b"hello");
+    /// ```
+    pub const fn from_copy_slice(other: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        if other.len() > N {
+            panic!("slice is longer than the capacity of the vector");
+        }
 
-            v.len = M;
-            v
+        let mut v = Vec::<T, N>::new();
+        // NOTE(unsafe) `T: Copy` and the vector can hold all the elements of `other`
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), v.buffer.as_mut_ptr() as *mut T, other.len());
+        }
+        v.len = other.len();
+        v
+    }
+
+    /// Appends an `item` to the back of the collection.
+    ///
+    /// Returns back the `item` if the vector is full.
+    ///
+    /// Unlike [`push`](VecInner::push), this function can be used in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// const SQUARES: Vec<u32, 8> = {
+    ///     let mut v = Vec::new();
+    ///     let mut i = 0;
+    ///     while v.push_const(i * i).is_ok() {
+    ///         i += 1;
+    ///     }
+    ///     v
+    /// };
+    /// assert_eq!(SQUARES, [0, 1, 4, 9, 16, 25, 36, 49]);
+    /// ```
+    pub const fn push_const(&mut self, item: T) -> Result<(), T> {
+        if self.len < N {
+            self.buffer[self.len] = MaybeUninit::new(item);
+            self.len += 1;
+            Ok(())
+        } else {
+            Err(item)
         }
     }
 
@@ -181,6 +344,11 @@ impl<T, const N: usize> Vec<T, N> {
     /// let buffer: Vec<u8, 42> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
     /// let array: [u8; 5] = buffer.into_array().unwrap();
     /// assert_eq!(array, [1, 2, 3, 5, 8]);
+    ///
+    /// // The vector is handed back if its length doesn't match
+    /// let buffer: Vec<u8, 42> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let buffer = buffer.into_array::<4>().unwrap_err();
+    /// assert_eq!(buffer, [1, 2, 3]);
     /// ```
     pub fn into_array<const M: usize>(self) -> Result<[T; M], Self> {
         if self.len() == M {
@@ -197,6 +365,85 @@ impl<T, const N: usize> Vec<T, N> {
         }
     }
 
+    /// Converts the vector into a vector of `U` by calling `f` on every element.
+    ///
+    /// When `T` and `U` have the same size and `U`'s alignment is no larger than `T`'s, the
+    /// elements are mapped in place, so that no second buffer is needed while mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let vec: Vec<u32, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let vec: Vec<f32, 8> = vec.map_into(|x| x as f32 / 2.0);
+    /// assert_eq!(vec, [0.5, 1.0, 1.5]);
+    /// ```
+    pub fn map_into<U, F>(self, mut f: F) -> Vec<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        if mem::size_of::<T>() != mem::size_of::<U>() || mem::align_of::<T>() < mem::align_of::<U>()
+        {
+            let mut mapped = Vec::new();
+            for item in self {
+                // NOTE(unsafe) `mapped` has the same capacity as `self`
+                unsafe { mapped.push_unchecked(f(item)) }
+            }
+            return mapped;
+        }
+
+        /// Drops the mapped and not yet mapped elements if `f` panics.
+        struct Guard<T, U> {
+            ptr: *mut T,
+            idx: usize,
+            len: usize,
+            _marker: PhantomData<U>,
+        }
+
+        impl<T, U> Drop for Guard<T, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr as *mut U, self.idx));
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.ptr.add(self.idx + 1),
+                        self.len - self.idx - 1,
+                    ));
+                }
+            }
+        }
+
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len;
+        let mut guard = Guard::<T, U> {
+            ptr: this.as_mut_ptr(),
+            idx: 0,
+            len,
+            _marker: PhantomData,
+        };
+
+        while guard.idx < len {
+            // NOTE(unsafe) `T` and `U` have the same size and `U`'s alignment is no larger than
+            // `T`'s, so a `U` can be written in the slot of the `T` just moved out
+            unsafe {
+                let slot = guard.ptr.add(guard.idx);
+                let item = f(ptr::read(slot));
+                ptr::write(slot as *mut U, item);
+            }
+            guard.idx += 1;
+        }
+        mem::forget(guard);
+
+        Vec {
+            len,
+            // NOTE(unsafe) the first `len` slots now hold initialized `U`s, and the buffers of
+            // `T`s and `U`s have the same layout
+            buffer: unsafe {
+                ptr::read(&this.buffer as *const [MaybeUninit<T>; N] as *const [MaybeUninit<U>; N])
+            },
+        }
+    }
+
     /// Clones a vec into a new vec
     pub(crate) fn _clone(&self) -> Self
     where
@@ -292,6 +539,120 @@ impl<T, const N: usize> Vec<T, N> {
         self.as_mut_view().drain(range)
     }
 
+    /// Creates a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    /// `replace_with` does not need to be the same length as `range`.
+    ///
+    /// `range` is removed even if the iterator is not consumed until the end.
+    ///
+    /// It is unspecified how many elements are removed from the vector
+    /// if the `Splice` value is leaked.
+    ///
+    /// The input iterator `replace_with` is only consumed when the `Splice` value is dropped.
+    /// If the vector cannot hold all of its elements, it is only consumed until the vector is
+    /// full, and the rest of its elements are left in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v = Vec::<_, 8>::from_array([1, 2, 3, 4]);
+    /// let new = [7, 8, 9];
+    /// let u: Vec<_, 8> = v.splice(1..3, new).collect();
+    /// assert_eq!(v, &[1, 7, 8, 9, 4]);
+    /// assert_eq!(u, &[2, 3]);
+    ///
+    /// // Only the elements that fit are inserted
+    /// v.splice(..1, [5, 6, 7, 8, 9]);
+    /// assert_eq!(v, &[5, 6, 7, 8, 7, 8, 9, 4]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        self.as_mut_view().splice(range, replace_with)
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element in the range should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed from the vector
+    /// and yielded. If the closure returns `false`, or panics, the element
+    /// remains in the vector and will not be yielded.
+    ///
+    /// Only elements that fall in the provided range are considered for extraction, but any elements
+    /// after the range will still have to be moved if any element has been extracted.
+    ///
+    /// If the returned `ExtractIf` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    /// Use [`retain_mut`](VecInner::retain_mut) with a negated predicate if you do not need the returned iterator.
+    ///
+    /// Note that `extract_if` also lets you mutate the elements passed to the filter closure,
+    /// regardless of whether you choose to keep or remove them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut numbers = Vec::<_, 16>::from_array([1, 2, 3, 4, 5, 6, 8, 9, 11, 13, 14, 15]);
+    /// let evens: Vec<_, 16> = numbers.extract_if(.., |x| *x % 2 == 0).collect();
+    /// let odds = numbers;
+    ///
+    /// assert_eq!(evens, &[2, 4, 6, 8, 14]);
+    /// assert_eq!(odds, &[1, 3, 5, 9, 11, 13, 15]);
+    /// ```
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        self.as_mut_view().extract_if(range, filter)
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element should be removed,
+    /// without preserving the order of the remaining elements.
+    ///
+    /// If the closure returns `true`, the element is removed from the vector and yielded, and
+    /// the last element of the vector takes its place, like [`swap_remove`](VecInner::swap_remove)
+    /// does. If the closure returns `false`, the element remains in the vector and will not be
+    /// yielded.
+    ///
+    /// Each removal is *O*(1), as the elements after the removed one aren't shifted. Use
+    /// [`extract_if`](Self::extract_if) if the order of the elements must be preserved.
+    ///
+    /// If the returned `SwapDrain` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut connections = Vec::<_, 8>::from_array([(1, true), (2, false), (3, true), (4, false)]);
+    /// let closed: Vec<_, 8> = connections.swap_drain(|(_, open)| !*open).collect();
+    ///
+    /// assert_eq!(closed, [(2, false), (4, false)]);
+    /// assert_eq!(connections, [(1, true), (3, true)]);
+    /// ```
+    pub fn swap_drain<F>(&mut self, filter: F) -> SwapDrain<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.as_mut_view().swap_drain(filter)
+    }
+
     /// Returns the maximum number of elements the vector can hold.
     ///
     /// This method is not available on a `VecView`, use [`storage_len`](VecInner::storage_capacity) instead
@@ -300,6 +661,45 @@ impl<T, const N: usize> Vec<T, N> {
     }
 }
 
+impl<T, const N: usize, A: Alignment> AlignedVec<T, N, A> {
+    /// Constructs a new, empty vector with a fixed capacity of `N` and a buffer aligned to `A`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{storage::Align32, vec::AlignedVec};
+    ///
+    /// // allocate the vector in a static variable
+    /// static mut X: AlignedVec<u8, 64, Align32> = AlignedVec::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            buffer: Aligned {
+                _align: [],
+                buffer: Vec::<T, N>::INIT,
+            },
+        }
+    }
+
+    /// Constructs a new aligned vector with a fixed capacity of `N` and fills it
+    /// with the provided slice.
+    #[allow(clippy::result_unit_err)]
+    pub fn from_slice(other: &[T]) -> Result<Self, ()>
+    where
+        T: Clone,
+    {
+        let mut v = Self::new();
+        v.extend_from_slice(other)?;
+        Ok(v)
+    }
+
+    /// Returns the maximum number of elements the vector can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
 impl<T> VecView<T> {
     /// Removes the specified range from the vector in bulk, returning all
     /// removed elements as an iterator. If the iterator is dropped before
@@ -363,48 +763,183 @@ impl<T> VecView<T> {
             }
         }
     }
-}
-
-impl<T, S: Storage> VecInner<T, S> {
-    /// Returns a raw pointer to the vector’s buffer.
-    pub fn as_ptr(&self) -> *const T {
-        self.buffer.borrow().as_ptr() as *const T
-    }
-
-    /// Returns a raw pointer to the vector’s buffer, which may be mutated through.
-    pub fn as_mut_ptr(&mut self) -> *mut T {
-        self.buffer.borrow_mut().as_mut_ptr() as *mut T
-    }
 
-    /// Extracts a slice containing the entire vector.
+    /// Creates a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    /// `replace_with` does not need to be the same length as `range`.
     ///
-    /// Equivalent to `&s[..]`.
+    /// `range` is removed even if the iterator is not consumed until the end.
+    ///
+    /// It is unspecified how many elements are removed from the vector
+    /// if the `Splice` value is leaked.
+    ///
+    /// The input iterator `replace_with` is only consumed when the `Splice` value is dropped.
+    /// If the vector cannot hold all of its elements, it is only consumed until the vector is
+    /// full, and the rest of its elements are left in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
     ///
     /// # Examples
     ///
     /// ```
     /// use heapless::Vec;
-    /// let buffer: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
-    /// assert_eq!(buffer.as_slice(), &[1, 2, 3, 5, 8]);
+    ///
+    /// let mut v = Vec::<_, 8>::from_array([1, 2, 3, 4]);
+    /// let new = [7, 8, 9];
+    /// let u: Vec<_, 8> = v.splice(1..3, new).collect();
+    /// assert_eq!(v, &[1, 7, 8, 9, 4]);
+    /// assert_eq!(u, &[2, 3]);
+    ///
+    /// // Only the elements that fit are inserted
+    /// v.splice(..1, [5, 6, 7, 8, 9]);
+    /// assert_eq!(v, &[5, 6, 7, 8, 7, 8, 9, 4]);
     /// ```
-    pub fn as_slice(&self) -> &[T] {
-        // NOTE(unsafe) avoid bound checks in the slicing operation
-        // &buffer[..self.len]
-        unsafe { slice::from_raw_parts(self.buffer.borrow().as_ptr() as *const T, self.len) }
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
     }
 
-    /// Extracts a mutable slice containing the entire vector.
+    /// Creates an iterator which uses a closure to determine if an element in the range should be removed.
     ///
-    /// Equivalent to `&mut s[..]`.
+    /// If the closure returns `true`, the element is removed from the vector
+    /// and yielded. If the closure returns `false`, or panics, the element
+    /// remains in the vector and will not be yielded.
     ///
-    /// # Examples
+    /// Only elements that fall in the provided range are considered for extraction, but any elements
+    /// after the range will still have to be moved if any element has been extracted.
     ///
-    /// ```
-    /// use heapless::Vec;
-    /// let mut buffer: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
-    /// let buffer_slice = buffer.as_mut_slice();
-    /// buffer_slice[0] = 9;
-    /// assert_eq!(buffer.as_slice(), &[9, 2, 3, 5, 8]);
+    /// If the returned `ExtractIf` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    /// Use [`retain_mut`](VecInner::retain_mut) with a negated predicate if you do not need the returned iterator.
+    ///
+    /// Note that `extract_if` also lets you mutate the elements passed to the filter closure,
+    /// regardless of whether you choose to keep or remove them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut numbers = Vec::<_, 16>::from_array([1, 2, 3, 4, 5, 6, 8, 9, 11, 13, 14, 15]);
+    /// let evens: Vec<_, 16> = numbers.extract_if(.., |x| *x % 2 == 0).collect();
+    /// let odds = numbers;
+    ///
+    /// assert_eq!(evens, &[2, 4, 6, 8, 14]);
+    /// assert_eq!(odds, &[1, 3, 5, 9, 11, 13, 15]);
+    /// ```
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        let old_len = self.len();
+        let Range { start, end } = crate::slice::range(range, ..old_len);
+
+        // Guard against the vec getting leaked (leak amplification)
+        unsafe {
+            self.set_len(0);
+        }
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            pred: filter,
+        }
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element should be removed,
+    /// without preserving the order of the remaining elements.
+    ///
+    /// If the closure returns `true`, the element is removed from the vector and yielded, and
+    /// the last element of the vector takes its place, like [`swap_remove`](VecInner::swap_remove)
+    /// does. If the closure returns `false`, the element remains in the vector and will not be
+    /// yielded.
+    ///
+    /// Each removal is *O*(1), as the elements after the removed one aren't shifted. Use
+    /// [`extract_if`](Self::extract_if) if the order of the elements must be preserved.
+    ///
+    /// If the returned `SwapDrain` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut connections = Vec::<_, 8>::from_array([(1, true), (2, false), (3, true), (4, false)]);
+    /// let closed: Vec<_, 8> = connections.swap_drain(|(_, open)| !*open).collect();
+    ///
+    /// assert_eq!(closed, [(2, false), (4, false)]);
+    /// assert_eq!(connections, [(1, true), (3, true)]);
+    /// ```
+    pub fn swap_drain<F>(&mut self, filter: F) -> SwapDrain<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        SwapDrain {
+            vec: self,
+            idx: 0,
+            pred: filter,
+        }
+    }
+}
+
+impl<T, S: Storage> VecInner<T, S> {
+    /// Returns a raw pointer to the vector’s buffer.
+    pub fn as_ptr(&self) -> *const T {
+        self.buffer.borrow().as_ptr() as *const T
+    }
+
+    /// Returns a raw pointer to the vector’s buffer, which may be mutated through.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buffer.borrow_mut().as_mut_ptr() as *mut T
+    }
+
+    /// Extracts a slice containing the entire vector.
+    ///
+    /// Equivalent to `&s[..]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    /// let buffer: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
+    /// assert_eq!(buffer.as_slice(), &[1, 2, 3, 5, 8]);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        // NOTE(unsafe) avoid bound checks in the slicing operation
+        // &buffer[..self.len]
+        unsafe { slice::from_raw_parts(self.buffer.borrow().as_ptr() as *const T, self.len) }
+    }
+
+    /// Extracts a mutable slice containing the entire vector.
+    ///
+    /// Equivalent to `&mut s[..]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    /// let mut buffer: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
+    /// let buffer_slice = buffer.as_mut_slice();
+    /// buffer_slice[0] = 9;
+    /// assert_eq!(buffer.as_slice(), &[9, 2, 3, 5, 8]);
     /// ```
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         // NOTE(unsafe) avoid bound checks in the slicing operation
@@ -414,6 +949,38 @@ impl<T, S: Storage> VecInner<T, S> {
         }
     }
 
+    /// Takes the elements out of the vector and returns them as a mutable slice that borrows
+    /// the vector's buffer, leaving the vector empty.
+    ///
+    /// The returned elements are never dropped by the vector. When the vector lives in a
+    /// `static` (or is otherwise borrowed for `'static`), this hands out a plain
+    /// `&'static mut [T]`, for example to pass a buffer initialized once at startup to a driver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// static mut BUFFER: Vec<u8, 8> = Vec::new();
+    ///
+    /// // SAFETY: this is the only reference to `BUFFER` ever created.
+    /// let buffer: &'static mut Vec<u8, 8> = unsafe { &mut *core::ptr::addr_of_mut!(BUFFER) };
+    /// buffer.extend_from_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// let slice: &'static mut [u8] = buffer.leak();
+    /// slice[0] = 4;
+    /// assert_eq!(slice, &[4, 2, 3]);
+    /// ```
+    pub fn leak(&mut self) -> &mut [T] {
+        let len = self.len;
+        // NOTE(unsafe) the elements are now owned by the returned slice; the vector
+        // can't be used again while the slice is alive.
+        unsafe {
+            self.set_len(0);
+            slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+        }
+    }
+
     /// Returns the maximum number of elements the vector can hold.
     pub fn storage_capacity(&self) -> usize {
         self.buffer.borrow().len()
@@ -481,6 +1048,232 @@ impl<T, S: Storage> VecInner<T, S> {
         extend_from_slice_inner(&mut self.len, self.buffer.borrow_mut(), other)
     }
 
+    /// Copies and appends all elements in a slice to the `Vec`.
+    ///
+    /// This is equivalent to [`extend_from_slice`](Self::extend_from_slice) but, as `T: Copy`,
+    /// the elements are copied with a single `memcpy` instead of being cloned one by one.
+    ///
+    /// Returns `Err(())` without modifying the vector if it cannot hold all the elements of
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec = Vec::<u8, 8>::new();
+    /// vec.push(1).unwrap();
+    /// vec.extend_from_copy_slice(&[2, 3, 4]).unwrap();
+    /// assert_eq!(*vec, [1, 2, 3, 4]);
+    /// assert!(vec.extend_from_copy_slice(&[0; 5]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn extend_from_copy_slice(&mut self, other: &[T]) -> Result<(), ()>
+    where
+        T: Copy,
+    {
+        let len = self.len;
+        if other.len() > self.storage_capacity() - len {
+            return Err(());
+        }
+
+        // NOTE(unsafe) there's room for `other.len()` more elements, and a `&mut self` can't
+        // overlap with `other`
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.as_mut_ptr().add(len), other.len());
+        }
+        self.len = len + other.len();
+        Ok(())
+    }
+
+    /// Clones the elements from `src` range and appends them to the end of the vector.
+    ///
+    /// Returns an error, leaving the vector unmodified, if the vector cannot hold the
+    /// cloned elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut characters: Vec<_, 16> = Vec::from_slice(b"abcde").unwrap();
+    /// characters.extend_from_within(2..).unwrap();
+    /// assert_eq!(characters, *b"abcdecde");
+    ///
+    /// let mut numbers: Vec<_, 8> = Vec::from_slice(&[0, 1, 2, 3, 4]).unwrap();
+    /// numbers.extend_from_within(..2).unwrap();
+    /// assert_eq!(numbers, [0, 1, 2, 3, 4, 0, 1]);
+    ///
+    /// assert!(numbers.extend_from_within(..2).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn extend_from_within<R>(&mut self, src: R) -> Result<(), ()>
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        let Range { start, end } = crate::slice::range(src, ..self.len());
+        if end - start > self.storage_capacity() - self.len {
+            return Err(());
+        }
+
+        for i in start..end {
+            // NOTE(unsafe) `i < end <= len` and there's room for `end - start` more elements.
+            // Pushing never moves the elements that are already in the vector.
+            unsafe {
+                let elem = (*self.as_ptr().add(i)).clone();
+                self.push_unchecked(elem);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// Returns an error, leaving both vectors unmodified, if `self` cannot hold all the
+    /// elements of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let mut vec2: Vec<_, 4> = Vec::from_slice(&[4, 5, 6]).unwrap();
+    /// vec.append(&mut vec2).unwrap();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(vec2, []);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn append(&mut self, other: &mut VecView<T>) -> Result<(), ()> {
+        let len = self.len();
+        let count = other.len();
+        if count > self.storage_capacity() - len {
+            return Err(());
+        }
+
+        // NOTE(unsafe) there's room for `count` more elements in `self`; the elements are
+        // moved out of `other`, whose length is reset so that they are not dropped twice.
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.as_mut_ptr().add(len), count);
+            other.set_len(0);
+            self.set_len(len + count);
+        }
+        Ok(())
+    }
+
+    /// Moves the elements in `range` to the end of `other`, shifting the elements after the
+    /// range to the left.
+    ///
+    /// This is equivalent to `other.extend(self.drain(range))`, but moves all the elements at
+    /// once, and fails instead of panicking if `other` lacks room.
+    ///
+    /// Returns an error, leaving both vectors unmodified, if `other` cannot hold all the
+    /// elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+    /// let mut other: Vec<_, 4> = Vec::from_slice(&[0]).unwrap();
+    /// vec.drain_into(1..4, &mut other).unwrap();
+    /// assert_eq!(vec, [1, 5]);
+    /// assert_eq!(other, [0, 2, 3, 4]);
+    ///
+    /// assert!(vec.drain_into(.., &mut other).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn drain_into<R>(&mut self, range: R, other: &mut VecView<T>) -> Result<(), ()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let Range { start, end } = crate::slice::range(range, ..len);
+        let count = end - start;
+        let other_len = other.len();
+        if count > other.storage_capacity() - other_len {
+            return Err(());
+        }
+
+        // NOTE(unsafe) the elements in `range` are moved into `other`, which has enough
+        // capacity to hold them, then the tail of `self` is shifted to fill the gap.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.as_ptr().add(start),
+                other.as_mut_ptr().add(other_len),
+                count,
+            );
+            other.set_len(other_len + count);
+
+            let p = self.as_mut_ptr();
+            ptr::copy(p.add(end), p.add(start), len - end);
+            self.set_len(len - count);
+        }
+        Ok(())
+    }
+
+    /// Splits the vector into two at the given index.
+    ///
+    /// Returns a newly allocated vector containing the elements in the range
+    /// `[at, len)`. After the call, the original vector will be left containing
+    /// the elements `[0, at)`.
+    ///
+    /// Returns an error, leaving the original vector unmodified, if the returned vector
+    /// cannot hold `len - at` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// let vec2: Vec<_, 4> = vec.split_off(1).unwrap();
+    /// assert_eq!(vec, [1]);
+    /// assert_eq!(vec2, [2, 3, 4]);
+    ///
+    /// // The tail doesn't fit in a `Vec` of capacity 2
+    /// assert!(vec2.clone().split_off::<2>(0).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<Vec<T, M>, ()> {
+        let len = self.len();
+        if at > len {
+            panic!("`at` split index (is {}) should be <= len (is {})", at, len);
+        }
+
+        let other_len = len - at;
+        if other_len > M {
+            return Err(());
+        }
+
+        let mut other = Vec::new();
+        // NOTE(unsafe) the elements in `at..len` are moved into `other`, which has enough
+        // capacity to hold them, and are no longer reachable from `self`.
+        unsafe {
+            self.set_len(at);
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), other.as_mut_ptr(), other_len);
+            other.set_len(other_len);
+        }
+        Ok(other)
+    }
+
     /// Removes the last element from a vector and returns it, or `None` if it's empty
     pub fn pop(&mut self) -> Option<T> {
         if self.len != 0 {
@@ -502,6 +1295,27 @@ impl<T, S: Storage> VecInner<T, S> {
         }
     }
 
+    /// Appends an `item` to the back of the collection and returns a mutable reference to it
+    ///
+    /// Returns back the `item` if the vector is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<(u8, u8), 8> = Vec::new();
+    /// let last = vec.push_mut((1, 0)).unwrap();
+    /// last.1 = 2;
+    /// assert_eq!(vec, [(1, 2)]);
+    /// ```
+    pub fn push_mut(&mut self, item: T) -> Result<&mut T, T> {
+        let len = self.len;
+        self.push(item)?;
+        // NOTE(unsafe) the element at `len` was just initialized
+        Ok(unsafe { self.get_unchecked_mut(len) })
+    }
+
     /// Removes the last element from a vector and returns it
     ///
     /// # Safety
@@ -562,7 +1376,7 @@ impl<T, S: Storage> VecInner<T, S> {
     /// difference, with each additional slot filled with value. If
     /// new_len is less than len, the Vec is simply truncated.
     ///
-    /// See also [`resize_default`](Self::resize_default).
+    /// See also [`resize_default`](Self::resize_default) and [`resize_with`](Self::resize_with).
     #[allow(clippy::result_unit_err)]
     pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), ()>
     where
@@ -598,29 +1412,81 @@ impl<T, S: Storage> VecInner<T, S> {
         self.resize(new_len, T::default())
     }
 
-    /// Forces the length of the vector to `new_len`.
-    ///
-    /// This is a low-level operation that maintains none of the normal
-    /// invariants of the type. Normally changing the length of a vector
-    /// is done using one of the safe operations instead, such as
-    /// [`truncate`], [`resize`], [`extend`], or [`clear`].
+    /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
-    /// [`truncate`]: Self::truncate
-    /// [`resize`]: Self::resize
-    /// [`extend`]: core::iter::Extend
-    /// [`clear`]: Self::clear
+    /// If `new_len` is greater than `len`, the `Vec` is extended by the
+    /// difference, with each additional slot filled with the result of
+    /// calling the closure `f`. The return values from `f` will end up
+    /// in the `Vec` in the order they have been generated.
     ///
-    /// # Safety
+    /// If `new_len` is less than `len`, the `Vec` is simply truncated.
     ///
-    /// - `new_len` must be less than or equal to [`capacity()`].
-    /// - The elements at `old_len..new_len` must be initialized.
+    /// Returns an error, leaving the vector unmodified, if `new_len` is greater than the capacity.
     ///
-    /// [`capacity()`]: Self::capacity
+    /// See also [`resize`](Self::resize).
     ///
     /// # Examples
     ///
-    /// This method can be useful for situations in which the vector
-    /// is serving as a buffer for other code, particularly over FFI:
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// vec.resize_with(5, Default::default).unwrap();
+    /// assert_eq!(vec, [1, 2, 3, 0, 0]);
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::new();
+    /// let mut p = 1;
+    /// vec.resize_with(4, || {
+    ///     p *= 2;
+    ///     p
+    /// })
+    /// .unwrap();
+    /// assert_eq!(vec, [2, 4, 8, 16]);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F) -> Result<(), ()>
+    where
+        F: FnMut() -> T,
+    {
+        if new_len > self.storage_capacity() {
+            return Err(());
+        }
+
+        if new_len > self.len {
+            while self.len < new_len {
+                // NOTE(unsafe) `new_len` is within the capacity
+                unsafe { self.push_unchecked(f()) };
+            }
+        } else {
+            self.truncate(new_len);
+        }
+
+        Ok(())
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// This is a low-level operation that maintains none of the normal
+    /// invariants of the type. Normally changing the length of a vector
+    /// is done using one of the safe operations instead, such as
+    /// [`truncate`], [`resize`], [`extend`], or [`clear`].
+    ///
+    /// [`truncate`]: Self::truncate
+    /// [`resize`]: Self::resize
+    /// [`extend`]: core::iter::Extend
+    /// [`clear`]: Self::clear
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity()`].
+    /// - The elements at `old_len..new_len` must be initialized.
+    ///
+    /// [`capacity()`]: Self::capacity
+    ///
+    /// # Examples
+    ///
+    /// This method can be useful for situations in which the vector
+    /// is serving as a buffer for other code, particularly over FFI:
     ///
     /// ```no_run
     /// # #![allow(dead_code)]
@@ -868,6 +1734,271 @@ impl<T, S: Storage> VecInner<T, S> {
         Ok(())
     }
 
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right, and returns a mutable reference to it.
+    ///
+    /// Returns back the `element` if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let x = vec.insert_mut(1, 4).unwrap();
+    /// *x += 1;
+    /// assert_eq!(vec, [1, 5, 2, 3]);
+    /// ```
+    pub fn insert_mut(&mut self, index: usize, element: T) -> Result<&mut T, T> {
+        self.insert(index, element)?;
+        // NOTE(unsafe) the element at `index` was just initialized
+        Ok(unsafe { self.get_unchecked_mut(index) })
+    }
+
+    /// Inserts an element into a sorted vector, keeping it sorted.
+    ///
+    /// The insertion point is found with a binary search. The element is inserted after any
+    /// elements that compare equal to it, so elements with the same priority keep their
+    /// insertion order.
+    ///
+    /// Returns the index where the element was inserted, or back the `element` if the
+    /// vector is full.
+    ///
+    /// If the vector is not sorted, the element is inserted at an unspecified position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 3, 5]).unwrap();
+    /// assert_eq!(vec.insert_sorted(4), Ok(2));
+    /// assert_eq!(vec.insert_sorted(0), Ok(0));
+    /// assert_eq!(vec, [0, 1, 3, 4, 5]);
+    /// ```
+    pub fn insert_sorted(&mut self, element: T) -> Result<usize, T>
+    where
+        T: Ord,
+    {
+        self.insert_sorted_by(element, T::cmp)
+    }
+
+    /// Inserts an element into a vector sorted with the comparator function `compare`,
+    /// keeping it sorted.
+    ///
+    /// See [`insert_sorted`](Self::insert_sorted) for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[5, 3, 1]).unwrap();
+    /// assert_eq!(vec.insert_sorted_by(4, |a, b| b.cmp(a)), Ok(1));
+    /// assert_eq!(vec, [5, 4, 3, 1]);
+    /// ```
+    pub fn insert_sorted_by<F>(&mut self, element: T, mut compare: F) -> Result<usize, T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.is_full() {
+            return Err(element);
+        }
+
+        let index = self.partition_point(|x| compare(x, &element) != Ordering::Greater);
+        // NOTE(unwrap) the vector is not full
+        self.insert(index, element).ok().unwrap();
+        Ok(index)
+    }
+
+    /// Inserts an element into a vector sorted with the key extraction function `f`,
+    /// keeping it sorted.
+    ///
+    /// See [`insert_sorted`](Self::insert_sorted) for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut tasks: Vec<_, 8> = Vec::from_slice(&[(1, "a"), (3, "b")]).unwrap();
+    /// tasks.insert_sorted_by_key((1, "c"), |&(priority, _)| priority).unwrap();
+    /// tasks.insert_sorted_by_key((2, "d"), |&(priority, _)| priority).unwrap();
+    /// assert_eq!(tasks, [(1, "a"), (1, "c"), (2, "d"), (3, "b")]);
+    /// ```
+    pub fn insert_sorted_by_key<K, F>(&mut self, element: T, mut f: F) -> Result<usize, T>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.insert_sorted_by(element, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Clones and inserts all elements in a slice at position `index` within the vector,
+    /// shifting all elements after it to the right.
+    ///
+    /// Unlike calling [`insert`](Self::insert) for each element, the elements after `index`
+    /// are only shifted once.
+    ///
+    /// Returns an error, leaving the vector unmodified, if the vector cannot hold all the
+    /// elements of `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// vec.insert_from_slice(1, &[7, 8, 9]).unwrap();
+    /// assert_eq!(vec, [1, 7, 8, 9, 2, 3]);
+    /// assert!(vec.insert_from_slice(0, &[4, 5, 6]).is_err());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn insert_from_slice(&mut self, index: usize, other: &[T]) -> Result<(), ()>
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        if index > len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+
+        self.replace_range(index..index, other)
+    }
+
+    /// Removes the first `M` elements of the vector and returns them as an array, shifting the
+    /// remaining elements to the front with a single move.
+    ///
+    /// Returns `None` without modifying the vector if it holds fewer than `M` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut buf: Vec<u8, 16> = Vec::from_slice(&[0xab, 0xcd, 3, 1, 2, 3]).unwrap();
+    /// let header: [u8; 2] = buf.drain_array().unwrap();
+    /// assert_eq!(header, [0xab, 0xcd]);
+    /// assert_eq!(buf, [3, 1, 2, 3]);
+    /// assert_eq!(buf.drain_array::<5>(), None);
+    /// ```
+    pub fn drain_array<const M: usize>(&mut self) -> Option<[T; M]> {
+        let len = self.len();
+        if len < M {
+            return None;
+        }
+
+        unsafe {
+            let p = self.as_mut_ptr();
+            // NOTE(unsafe) the first `M` elements are initialized, and are forgotten by the
+            // vector by shifting the tail over them
+            let array = (p as *const [T; M]).read();
+            ptr::copy(p.add(M), p, len - M);
+            self.set_len(len - M);
+            Some(array)
+        }
+    }
+
+    /// Replaces the elements in `range` with clones of the elements of `replace_with`, shifting
+    /// the elements after the range only once.
+    ///
+    /// `replace_with` doesn't need to have the same length as `range`.
+    ///
+    /// Returns `Err(())` without modifying the vector if the result wouldn't fit in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 6> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// vec.replace_range(1..3, &[7, 8, 9]).unwrap();
+    /// assert_eq!(vec, [1, 7, 8, 9, 4]);
+    /// vec.replace_range(..4, &[0]).unwrap();
+    /// assert_eq!(vec, [0, 4]);
+    /// assert!(vec.replace_range(1.., &[5; 6]).is_err());
+    /// assert_eq!(vec, [0, 4]);
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &[T]) -> Result<(), ()>
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        let len = self.len();
+        let Range { start, end } = crate::slice::range(range, ..len);
+
+        let count = replace_with.len();
+        if count > self.storage_capacity() - (len - (end - start)) {
+            return Err(());
+        }
+
+        // This drop guard will be invoked if `T::clone` panicked. It shifts the tail
+        // back to cover the part of the gap that was not filled yet.
+        struct FillGapOnDrop<'a, T, S: Storage> {
+            vec: &'a mut VecInner<T, S>,
+            index: usize,
+            filled: usize,
+            count: usize,
+            tail_len: usize,
+        }
+
+        impl<T, S: Storage> Drop for FillGapOnDrop<'_, T, S> {
+            fn drop(&mut self) {
+                unsafe {
+                    let p = self.vec.as_mut_ptr().add(self.index);
+                    ptr::copy(p.add(self.count), p.add(self.filled), self.tail_len);
+                    self.vec.set_len(self.index + self.filled + self.tail_len);
+                }
+            }
+        }
+
+        unsafe {
+            // Avoid double drop if the drop guard is not executed.
+            self.set_len(start);
+            let p = self.as_mut_ptr().add(start);
+            // Drop the replaced elements. Should this panic, the tail is leaked.
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, end - start));
+            // Shift the tail over to make space for the new elements.
+            ptr::copy(p.add(end - start), p.add(count), len - end);
+
+            let mut guard = FillGapOnDrop {
+                vec: self,
+                index: start,
+                filled: 0,
+                count,
+                tail_len: len - end,
+            };
+            // Reborrowing `self` into the guard invalidated `p`
+            let p = guard.vec.as_mut_ptr().add(start);
+            for elem in replace_with {
+                ptr::write(p.add(guard.filled), elem.clone());
+                guard.filled += 1;
+            }
+
+            guard.vec.set_len(start + count + len - end);
+            mem::forget(guard);
+        }
+
+        Ok(())
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
@@ -1074,58 +2205,408 @@ impl<T, S: Storage> VecInner<T, S> {
         drop(g);
     }
 
-    /// Returns the remaining spare capacity of the vector as a slice of `MaybeUninit<T>`.
+    /// Replaces every element of the vector with the result of calling `f` on it, in place.
     ///
-    /// The returned slice can be used to fill the vector with data before marking the data as
-    /// initialized using the `set_len` method.
+    /// If `f` returns an error, the elements that have already been mapped are kept, the remaining
+    /// ones are dropped, and the error is returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use heapless::Vec;
     ///
-    /// // Allocate vector big enough for 10 elements.
-    /// let mut v: Vec<_, 10> = Vec::new();
+    /// let mut vec: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// vec.try_map_in_place(|x| x.checked_mul(50).ok_or(x)).unwrap();
+    /// assert_eq!(vec, [50, 100, 150]);
+    /// assert_eq!(vec.try_map_in_place(|x| x.checked_mul(2).ok_or(x)), Err(150));
+    /// assert_eq!(vec, [100, 200]);
+    /// ```
+    pub fn try_map_in_place<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(T) -> Result<T, E>,
+    {
+        /// Drops the elements that haven't been mapped yet if `f` fails or panics.
+        struct DropTail<T> {
+            ptr: *mut T,
+            len: usize,
+        }
+
+        impl<T> Drop for DropTail<T> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr, self.len)) }
+            }
+        }
+
+        let len = self.len;
+        let p = self.as_mut_ptr();
+        // Only the mapped prefix is owned by the vector while elements are being moved out
+        self.len = 0;
+
+        for i in 0..len {
+            unsafe {
+                let tail = DropTail {
+                    ptr: p.add(i + 1),
+                    len: len - i - 1,
+                };
+                let item = f(ptr::read(p.add(i)))?;
+                ptr::write(p.add(i), item);
+                mem::forget(tail);
+            }
+            self.len = i + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
     ///
-    /// // Fill in the first 3 elements.
-    /// let uninit = v.spare_capacity_mut();
-    /// uninit[0].write(0);
-    /// uninit[1].write(1);
-    /// uninit[2].write(2);
+    /// If the vector is sorted, this removes all duplicates.
     ///
-    /// // Mark the first 3 elements of the vector as being initialized.
-    /// unsafe {
-    ///     v.set_len(3);
-    /// }
+    /// # Examples
     ///
-    /// assert_eq!(&v, &[0, 1, 2]);
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 2, 3, 2]).unwrap();
+    /// vec.dedup();
+    /// assert_eq!(vec, [1, 2, 3, 2]);
     /// ```
     #[inline]
-    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
-        &mut self.buffer.borrow_mut()[self.len..]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
     }
-}
-
-// Trait implementations
 
-impl<T, const N: usize> Default for Vec<T, N> {
-    fn default() -> Self {
-        Self::new()
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[10, 20, 21, 30, 20]).unwrap();
+    /// vec.dedup_by_key(|i| *i / 10);
+    /// assert_eq!(vec, [10, 20, 30, 20]);
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
     }
-}
 
-impl<T, S: Storage> fmt::Debug for VecInner<T, S>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <[T] as fmt::Debug>::fmt(self, f)
+    /// Removes all but the first of consecutive elements in the vector satisfying a given equality
+    /// relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&["foo", "bar", "Bar", "baz", "bar"]).unwrap();
+    /// vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        // Check if we ever want to remove anything.
+        // This allows to use copy_nonoverlapping in next cycle.
+        // And avoids any memory writes if we don't need to remove anything.
+        let ptr = self.as_mut_ptr();
+        let mut first_duplicate_idx: usize = 1;
+        unsafe {
+            // SAFETY: `first_duplicate_idx` is always in range [1..len)
+            // Note that we start iteration from 1 so we never overflow.
+            while first_duplicate_idx != len {
+                let found_duplicate = {
+                    let prev = &mut *ptr.add(first_duplicate_idx - 1);
+                    let current = &mut *ptr.add(first_duplicate_idx);
+                    // We explicitly say in docs that references are reversed.
+                    same_bucket(current, prev)
+                };
+                if found_duplicate {
+                    break;
+                }
+                first_duplicate_idx += 1;
+            }
+            // Don't need to remove anything.
+            // We cannot get bigger than len.
+            if first_duplicate_idx == len {
+                return;
+            }
+        }
+
+        // INVARIANT: vec.len() > read > write > write-1 >= 0
+        struct FillGapOnDrop<'a, T, S: Storage> {
+            // Offset of the element we want to check if it is duplicate
+            read: usize,
+            // Offset of the place where we want to place the non-duplicate
+            // when we find it.
+            write: usize,
+            // The Vec that would need correction if `same_bucket` panicked
+            vec: &'a mut VecInner<T, S>,
+        }
+
+        impl<T, S: Storage> Drop for FillGapOnDrop<'_, T, S> {
+            fn drop(&mut self) {
+                // This code gets executed when `same_bucket` panics
+
+                // SAFETY: invariant guarantees that `read - write`
+                // and `len - read` never overflow and that the copy is always
+                // in-bounds.
+                unsafe {
+                    let ptr = self.vec.as_mut_ptr();
+                    let len = self.vec.len();
+
+                    // How many items were left when `same_bucket` panicked.
+                    // Basically vec[read..].len()
+                    let items_left = len - self.read;
+
+                    // Pointer to first item in vec[write..write+items_left] slice
+                    let dropped_ptr = ptr.add(self.write);
+                    // Pointer to first item in vec[read..] slice
+                    let valid_ptr = ptr.add(self.read);
+
+                    // Copy `vec[read..]` to `vec[write..write+items_left]`.
+                    // The slices can overlap, so `copy_nonoverlapping` cannot be used
+                    ptr::copy(valid_ptr, dropped_ptr, items_left);
+
+                    // How many items have been already dropped
+                    // Basically vec[read..write].len()
+                    let dropped = self.read - self.write;
+
+                    self.vec.set_len(len - dropped);
+                }
+            }
+        }
+
+        // Construct gap first and then drop item to avoid memory corruption if `T::drop` panics.
+        let mut gap = FillGapOnDrop {
+            read: first_duplicate_idx + 1,
+            write: first_duplicate_idx,
+            vec: self,
+        };
+        // Reborrowing `self` into the guard invalidated `ptr`
+        let ptr = gap.vec.as_mut_ptr();
+        unsafe {
+            // SAFETY: we checked that first_duplicate_idx in bounds before.
+            // If drop panics, `gap` would remove this item without drop.
+            ptr::drop_in_place(ptr.add(first_duplicate_idx));
+        }
+
+        // SAFETY: Because of the invariant, read_ptr, prev_ptr and write_ptr
+        // are always in-bounds and read_ptr never aliases prev_ptr
+        unsafe {
+            while gap.read < len {
+                let read_ptr = ptr.add(gap.read);
+                let prev_ptr = ptr.add(gap.write - 1);
+
+                // We explicitly say in docs that references are reversed.
+                let found_duplicate = same_bucket(&mut *read_ptr, &mut *prev_ptr);
+                if found_duplicate {
+                    // Increase `gap.read` now since the drop may panic.
+                    gap.read += 1;
+                    // We have found duplicate, drop it in-place
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = ptr.add(gap.write);
+
+                    // read_ptr cannot be equal to write_ptr because at this point
+                    // we guaranteed to skip at least one element (before loop starts).
+                    ptr::copy_nonoverlapping(read_ptr, write_ptr, 1);
+
+                    // We have filled that place, so go further
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+
+            // Technically we could let `gap` clean up with its Drop, but
+            // when `same_bucket` is guaranteed to not panic, this bloats a little
+            // the codegen, so we just do it manually
+            gap.vec.set_len(gap.write);
+            mem::forget(gap);
+        }
+    }
+
+    /// Returns the remaining spare capacity of the vector as a slice of `MaybeUninit<T>`.
+    ///
+    /// The returned slice can be used to fill the vector with data before marking the data as
+    /// initialized using the `set_len` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// // Allocate vector big enough for 10 elements.
+    /// let mut v: Vec<_, 10> = Vec::new();
+    ///
+    /// // Fill in the first 3 elements.
+    /// let uninit = v.spare_capacity_mut();
+    /// uninit[0].write(0);
+    /// uninit[1].write(1);
+    /// uninit[2].write(2);
+    ///
+    /// // Mark the first 3 elements of the vector as being initialized.
+    /// unsafe {
+    ///     v.set_len(3);
+    /// }
+    ///
+    /// assert_eq!(&v, &[0, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.buffer.borrow_mut()[self.len..]
+    }
+
+    /// Marks the first `additional` elements of the spare capacity as initialized, appending
+    /// them to the vector.
+    ///
+    /// This is meant to be used after writing into the slice returned by
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut), for example by a DMA transfer.
+    /// Unlike [`set_len`](Self::set_len), the new length doesn't need to be computed by the
+    /// caller, and it is checked in debug builds to stay within the capacity.
+    ///
+    /// See [`fill_spare_capacity`](Self::fill_spare_capacity) for a safe alternative.
+    ///
+    /// # Safety
+    ///
+    /// - `additional` must be less than or equal to `spare_capacity_mut().len()`.
+    /// - The first `additional` elements of the spare capacity must be initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2]).unwrap();
+    ///
+    /// let uninit = v.spare_capacity_mut();
+    /// uninit[0].write(3);
+    /// uninit[1].write(4);
+    ///
+    /// // SAFETY: the first 2 elements of the spare capacity were just initialized.
+    /// unsafe { v.extend_from_spare(2) };
+    /// assert_eq!(v, [1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub unsafe fn extend_from_spare(&mut self, additional: usize) {
+        debug_assert!(additional <= self.storage_capacity() - self.len);
+
+        self.len += additional;
+    }
+
+    /// Fills the spare capacity of the vector with `f` and appends the elements it wrote.
+    ///
+    /// The spare capacity is first filled with `T::default()` and passed to `f`, which
+    /// returns how many elements, starting from the beginning of the slice, it filled in.
+    /// Those elements are appended to the vector, the rest is discarded. This is meant for
+    /// `read`-style APIs that fill a caller-provided buffer.
+    ///
+    /// Returns the number of elements appended to the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns a number greater than the length of the slice it was passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// fn read(buf: &mut [u8]) -> usize {
+    ///     let data = b"hello";
+    ///     let n = data.len().min(buf.len());
+    ///     buf[..n].copy_from_slice(&data[..n]);
+    ///     n
+    /// }
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::from_slice(b"> ").unwrap();
+    /// assert_eq!(v.fill_spare_capacity(read), 5);
+    /// assert_eq!(v, *b"> hello");
+    /// assert_eq!(v.fill_spare_capacity(read), 1);
+    /// assert_eq!(v, *b"> helloh");
+    /// ```
+    pub fn fill_spare_capacity<F>(&mut self, f: F) -> usize
+    where
+        T: Default,
+        F: FnOnce(&mut [T]) -> usize,
+    {
+        let len = self.len;
+        let spare_len = self.storage_capacity() - len;
+        // Temporarily make the initialized spare capacity part of the vector, so that
+        // it gets dropped if `f` panics.
+        for slot in self.spare_capacity_mut() {
+            slot.write(T::default());
+        }
+        self.len += spare_len;
+
+        let written = f(&mut self.as_mut_slice()[len..]);
+        if written > spare_len {
+            self.truncate(len);
+            panic!(
+                "written elements (is {}) should be <= spare capacity (is {})",
+                written, spare_len
+            );
+        }
+
+        self.truncate(len + written);
+        written
+    }
+}
+
+// Trait implementations
+
+impl<T, const N: usize> Default for Vec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, A: Alignment> Default for AlignedVec<T, N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: Storage> fmt::Debug for VecInner<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
     }
 }
 
+/// Appends the UTF-8 bytes of the formatted string to the vector.
+///
+/// Each `write_str` call either appends all of its bytes or fails with [`fmt::Error`], leaving
+/// the vector unmodified, if they don't fit.
 impl<S: Storage> fmt::Write for VecInner<u8, S> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        match self.extend_from_slice(s.as_bytes()) {
+        match self.extend_from_copy_slice(s.as_bytes()) {
             Ok(()) => Ok(()),
             Err(_) => Err(fmt::Error),
         }
@@ -1217,6 +2698,22 @@ impl<T, const N: usize> FromIterator<T> for Vec<T, N> {
     }
 }
 
+/// Returns back the first element that didn't fit in the vector.
+impl<T, const N: usize> TryFromIterator<T> for Vec<T, N> {
+    type Error = T;
+
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Vec::new();
+        for i in iter {
+            vec.push(i)?;
+        }
+        Ok(vec)
+    }
+}
+
 /// An iterator that moves out of an [`Vec`][`Vec`].
 ///
 /// This struct is created by calling the `into_iter` method on [`Vec`][`Vec`].
@@ -1482,13 +2979,32 @@ where
     }
 }
 
+#[cfg(feature = "copy")]
+impl<T, const N: usize, A: Alignment> Copy for AlignedVec<T, N, A> where T: Copy {}
+
+impl<T, const N: usize, A: Alignment> Clone for AlignedVec<T, N, A>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut new = Self::new();
+        for elem in self {
+            unsafe {
+                new.push_unchecked(elem.clone());
+            }
+        }
+        new
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::fmt::Write;
 
     use static_assertions::assert_not_impl_any;
 
-    use super::{Vec, VecView};
+    use super::{AlignedVec, Vec, VecView};
+    use crate::storage::{Align4096, Align64};
 
     // Ensure a `Vec` containing `!Send` values stays `!Send` itself.
     assert_not_impl_any!(Vec<*const (), 4>: Send);
@@ -1580,6 +3096,40 @@ mod tests {
         assert_eq!(Droppable::count(), 0);
     }
 
+    #[test]
+    fn aligned() {
+        let mut v: AlignedVec<u8, 3, Align64> = AlignedVec::new();
+        assert_eq!(v.as_ptr() as usize % 64, 0);
+        assert_eq!(v.capacity(), 3);
+
+        v.extend_from_slice(&[1, 2, 3]).unwrap();
+        assert!(v.push(4).is_err());
+        assert_eq!(v, [1, 2, 3]);
+
+        let v2 = v.clone();
+        assert_eq!(v2.as_ptr() as usize % 64, 0);
+        assert_eq!(v2, v);
+
+        let v: AlignedVec<u32, 1024, Align4096> = AlignedVec::from_slice(&[1, 2]).unwrap();
+        assert_eq!(v.as_ptr() as usize % 4096, 0);
+        assert_eq!(core::mem::align_of_val(&v), 4096);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn aligned_drop() {
+        droppable!();
+
+        {
+            let mut v: AlignedVec<Droppable, 2, Align64> = AlignedVec::new();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            v.pop().unwrap();
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
     #[test]
     fn eq() {
         let mut xs: Vec<i32, 4> = Vec::new();
@@ -1703,6 +3253,20 @@ mod tests {
         let _vec = slice.iter().cloned().collect::<Vec<_, 2>>();
     }
 
+    #[test]
+    fn try_collect() {
+        use crate::iter::{TryCollect, TryFromIterator};
+
+        let v: Vec<i32, 4> = [1, 2, 3].iter().cloned().try_collect().unwrap();
+        assert_eq!(v, [1, 2, 3]);
+
+        let v = Vec::<i32, 3>::try_from_iter([1, 2, 3]).unwrap();
+        assert_eq!(v, [1, 2, 3]);
+
+        assert_eq!(Vec::<i32, 2>::try_from_iter([1, 2, 3]), Err(3));
+        assert_eq!((0..).try_collect::<Vec<i32, 0>>(), Err(0));
+    }
+
     #[test]
     fn iter_move() {
         let mut v: Vec<i32, 4> = Vec::new();
@@ -1845,6 +3409,49 @@ mod tests {
         assert_eq!(v[0], 0);
     }
 
+    #[test]
+    fn resize_with() {
+        struct NotClone(u8);
+
+        let mut v: Vec<NotClone, 4> = Vec::new();
+        let mut n = 0;
+        let mut next = || {
+            n += 1;
+            NotClone(n)
+        };
+
+        v.resize_with(3, &mut next).unwrap();
+        assert_eq!(v.iter().map(|x| x.0).collect::<Vec<_, 4>>(), [1, 2, 3]);
+
+        v.resize_with(5, &mut next).expect_err("full");
+        assert_eq!(v.len(), 3);
+
+        v.resize_with(1, &mut next).unwrap();
+        assert_eq!(v.iter().map(|x| x.0).collect::<Vec<_, 4>>(), [1]);
+
+        v.resize_with(4, &mut next).unwrap();
+        assert_eq!(v.iter().map(|x| x.0).collect::<Vec<_, 4>>(), [1, 4, 5, 6]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn leak() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        v.push(Droppable::new()).ok().unwrap();
+        v.push(Droppable::new()).ok().unwrap();
+
+        let slice = v.leak();
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice[1].0, 2);
+
+        // The leaked elements are no longer owned by the vector
+        assert!(v.is_empty());
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 2);
+    }
+
     #[test]
     fn write() {
         let mut v: Vec<u8, 4> = Vec::new();
@@ -1852,6 +3459,33 @@ mod tests {
         assert_eq!(&v[..], b"4d2");
     }
 
+    #[test]
+    fn write_overflow() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        v.write_str("ab").unwrap();
+        assert!(v.write_str("cde").is_err());
+        assert_eq!(&v[..], b"ab");
+
+        let view: &mut VecView<u8> = &mut v;
+        write!(view, "é").unwrap();
+        assert_eq!(&v[..], "abé".as_bytes());
+    }
+
+    #[test]
+    fn extend_from_copy_slice() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.len(), 0);
+        v.extend_from_copy_slice(&[1, 2]).unwrap();
+        assert_eq!(v, [1, 2]);
+        v.extend_from_copy_slice(&[]).unwrap();
+        v.extend_from_copy_slice(&[3]).unwrap();
+        assert_eq!(v, [1, 2, 3]);
+        assert!(v.extend_from_copy_slice(&[4, 5]).is_err());
+        assert_eq!(v, [1, 2, 3]);
+        v.extend_from_copy_slice(&[4]).unwrap();
+        assert!(v.is_full());
+    }
+
     #[test]
     fn extend_from_slice() {
         let mut v: Vec<u8, 4> = Vec::new();
@@ -1893,6 +3527,255 @@ mod tests {
         assert_eq!(v.as_slice(), &[1, 2, 3]);
     }
 
+    #[test]
+    fn const_constructors() {
+        const ARRAY: Vec<u8, 4> = Vec::from_array([1, 2]);
+        assert_eq!(ARRAY, [1, 2]);
+
+        const EXACT: Vec<u8, 2> = Vec::from_array([1, 2]);
+        assert_eq!(EXACT, [1, 2]);
+
+        const SLICE: Vec<u8, 4> = Vec::from_copy_slice(&[1, 2, 3]);
+        assert_eq!(SLICE, [1, 2, 3]);
+
+        const PUSHED: Vec<u8, 2> = {
+            let mut v = Vec::new();
+            assert!(v.push_const(1).is_ok());
+            assert!(v.push_const(2).is_ok());
+            assert!(v.push_const(3).is_err());
+            v
+        };
+        assert_eq!(PUSHED, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_copy_slice_overflow() {
+        let _: Vec<u8, 2> = Vec::from_copy_slice(&[1, 2, 3]);
+    }
+
+    #[test]
+    fn push_mut() {
+        let mut v: Vec<[u8; 2], 2> = Vec::new();
+        v.push_mut([1, 0]).unwrap()[1] = 2;
+        *v.push_mut([0; 2]).unwrap() = [3, 4];
+        assert_eq!(v, [[1, 2], [3, 4]]);
+        assert_eq!(v.push_mut([5, 6]), Err([5, 6]));
+    }
+
+    #[test]
+    fn insert_mut() {
+        let mut v: Vec<u8, 3> = Vec::from_slice(&[1, 3]).unwrap();
+        *v.insert_mut(1, 0).unwrap() = 2;
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(v.insert_mut(0, 4), Err(4));
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_map_in_place() {
+        let mut v: Vec<i32, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(v.try_map_in_place(|x| Ok::<_, ()>(x * 2)), Ok(()));
+        assert_eq!(v, [2, 4, 6]);
+
+        assert_eq!(
+            v.try_map_in_place(|x| if x < 4 { Ok(-x) } else { Err(x) }),
+            Err(4)
+        );
+        assert_eq!(v, [-2]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn try_map_in_place_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        assert_eq!(Droppable::count(), 4);
+
+        let mut calls = 0;
+        let err = v.try_map_in_place(|d| {
+            calls += 1;
+            if calls == 2 {
+                Err(d)
+            } else {
+                Ok(Droppable::new())
+            }
+        });
+        assert_eq!(v.len(), 1);
+        // The two unmapped elements were dropped, leaving the mapped element in the vector and
+        // the failed one in the error
+        assert_eq!(Droppable::count(), 2);
+        core::mem::drop(err);
+        assert_eq!(Droppable::count(), 1);
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn map_into() {
+        // Same layout, mapped in place
+        let v: Vec<u32, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let v: Vec<i32, 4> = v.map_into(|x| -(x as i32));
+        assert_eq!(v, [-1, -2, -3]);
+
+        // Different layout
+        let v: Vec<u64, 4> = v.map_into(|x| x.unsigned_abs() as u64 + 1);
+        assert_eq!(v, [2, 3, 4]);
+        let v: Vec<u8, 4> = v.map_into(|x| x as u8);
+        assert_eq!(v, [2, 3, 4]);
+
+        let v: Vec<u8, 4> = Vec::new();
+        assert!(v.map_into(|x| x as i8).is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn map_into_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..3 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        let v: Vec<Droppable, 4> = v.map_into(|d| {
+            core::mem::drop(d);
+            Droppable::new()
+        });
+        assert_eq!(Droppable::count(), 3);
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn map_into_panic() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        let mut calls = 0;
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.map_into(|d| {
+                calls += 1;
+                if calls == 3 {
+                    panic!("boom");
+                }
+                d
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn replace_range() {
+        let mut v: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        v.replace_range(1..2, &[4, 5, 6]).unwrap();
+        assert_eq!(v, [1, 4, 5, 6, 3]);
+        assert!(v.replace_range(..1, &[0, 0]).is_err());
+        assert_eq!(v, [1, 4, 5, 6, 3]);
+        v.replace_range(1..=3, &[]).unwrap();
+        assert_eq!(v, [1, 3]);
+        v.replace_range(2.., &[7, 8, 9]).unwrap();
+        assert_eq!(v, [1, 3, 7, 8, 9]);
+        v.replace_range(.., &[0]).unwrap();
+        assert_eq!(v, [0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_range_out_of_bounds() {
+        let mut v: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let _ = v.replace_range(2..4, &[]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn replace_range_drop() {
+        droppable!();
+
+        impl Clone for Droppable {
+            fn clone(&self) -> Self {
+                Droppable::new()
+            }
+        }
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..3 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+            let one = [Droppable::new()];
+            assert_eq!(Droppable::count(), 4);
+            v.replace_range(0..2, &[]).unwrap();
+            assert_eq!(Droppable::count(), 2);
+            v.replace_range(1..1, &one).unwrap();
+            assert_eq!(Droppable::count(), 3);
+            assert_eq!(v.len(), 2);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn drain_array() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(v.drain_array::<0>(), Some([]));
+        assert_eq!(v.drain_array(), Some([1, 2]));
+        assert_eq!(v, [3, 4, 5]);
+        assert_eq!(v.drain_array::<4>(), None);
+        assert_eq!(v, [3, 4, 5]);
+        assert_eq!(v.drain_array(), Some([3, 4, 5]));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn drain_array_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..3 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+            let [a, b] = v.drain_array().unwrap();
+            assert_eq!(v.len(), 1);
+            assert_eq!(Droppable::count(), 3);
+            core::mem::drop((a, b));
+            assert_eq!(Droppable::count(), 1);
+        }
+
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn from_fn() {
+        let v: Vec<usize, 4> = Vec::from_fn(3, |i| i * 2).unwrap();
+        assert_eq!(v, [0, 2, 4]);
+
+        let v: Vec<usize, 4> = Vec::from_fn(0, |_| unreachable!()).unwrap();
+        assert!(v.is_empty());
+
+        assert!(Vec::<usize, 4>::from_fn(5, |_| unreachable!()).is_err());
+    }
+
+    #[test]
+    fn full_from_fn() {
+        let v: Vec<usize, 4> = Vec::full_from_fn(|i| 10 + i);
+        assert_eq!(v, [10, 11, 12, 13]);
+        assert!(v.is_full());
+
+        let v: Vec<usize, 0> = Vec::full_from_fn(|_| unreachable!());
+        assert!(v.is_empty());
+    }
+
     #[test]
     fn from_array_no_drop() {
         struct Drops(Option<u8>);
@@ -1910,6 +3793,35 @@ mod tests {
         assert_eq!(v[2].0, Some(3));
     }
 
+    #[test]
+    fn into_array() {
+        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let v = v.into_array::<2>().unwrap_err();
+        let v = v.into_array::<4>().unwrap_err();
+        assert_eq!(v.into_array::<3>().unwrap(), [1, 2, 3]);
+
+        let v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.into_array::<0>().unwrap(), []);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn into_array_no_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        v.push(Droppable::new()).ok().unwrap();
+        v.push(Droppable::new()).ok().unwrap();
+
+        let array: [Droppable; 2] = v.into_array().ok().unwrap();
+        assert_eq!(Droppable::count(), 2);
+        assert_eq!(array[0].0, 1);
+        assert_eq!(array[1].0, 2);
+
+        core::mem::drop(array);
+        assert_eq!(Droppable::count(), 0);
+    }
+
     #[test]
     fn starts_with() {
         let v: Vec<_, 8> = Vec::from_slice(b"ab").unwrap();
@@ -1959,6 +3871,275 @@ mod tests {
         assert!(v.is_full());
     }
 
+    #[test]
+    fn retain_mut() {
+        let mut v: Vec<(u8, u8), 8> = Vec::from_slice(&[(1, 2), (2, 1), (3, 3), (4, 1)]).unwrap();
+        // Decrement the TTL of each entry and drop the expired ones in a single pass
+        v.retain_mut(|(_, ttl)| {
+            *ttl -= 1;
+            *ttl != 0
+        });
+        assert_eq!(v, [(1, 1), (3, 2)]);
+
+        let view: &mut VecView<_> = &mut v;
+        view.retain_mut(|(_, ttl)| {
+            *ttl -= 1;
+            *ttl != 0
+        });
+        assert_eq!(v, [(3, 1)]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn retain_mut_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        assert_eq!(Droppable::count(), 4);
+
+        let mut visited = 0;
+        v.retain_mut(|d| {
+            visited += 1;
+            d.0 % 2 == 1
+        });
+        assert_eq!(visited, 4);
+        assert_eq!(Droppable::count(), 2);
+        assert_eq!(v.len(), 2);
+        assert_eq!(v[0].0, 1);
+        assert_eq!(v[1].0, 3);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 1, 2, 3, 3, 3, 1, 4]).unwrap();
+        v.dedup();
+        assert_eq!(v, [1, 2, 3, 1, 4]);
+
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        v.dedup();
+        assert_eq!(v, [1, 2, 3]);
+
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[7, 7, 7, 7]).unwrap();
+        v.dedup();
+        assert_eq!(v, [7]);
+
+        let mut v: Vec<u8, 8> = Vec::new();
+        v.dedup();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[1, -1, 2, -2, 3, 4, -4]).unwrap();
+        v.dedup_by_key(|x| x.abs());
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn dedup_by_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        assert_eq!(Droppable::count(), 4);
+
+        // Collapse everything into the first element
+        v.dedup_by(|_, _| true);
+        assert_eq!(Droppable::count(), 1);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].0, 1);
+    }
+
+    #[test]
+    fn extend_from_within() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+
+        v.extend_from_within(1..).unwrap();
+        assert_eq!(v, [1, 2, 3, 2, 3]);
+
+        v.extend_from_within(..0).unwrap();
+        assert_eq!(v, [1, 2, 3, 2, 3]);
+
+        // Not enough room, nothing is copied
+        assert!(v.extend_from_within(..).is_err());
+        assert_eq!(v, [1, 2, 3, 2, 3]);
+
+        v.extend_from_within(2..=4).unwrap();
+        assert_eq!(v, [1, 2, 3, 2, 3, 3, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_from_within_out_of_bounds() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let _ = v.extend_from_within(2..4);
+    }
+
+    #[test]
+    fn append() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2]).unwrap();
+        let mut other: Vec<u8, 8> = Vec::from_slice(&[3, 4, 5]).unwrap();
+
+        // Not enough room, nothing is moved
+        assert!(v.append(&mut other).is_err());
+        assert_eq!(v, [1, 2]);
+        assert_eq!(other, [3, 4, 5]);
+
+        other.pop();
+        v.append(&mut other).unwrap();
+        assert_eq!(v, [1, 2, 3, 4]);
+        assert!(other.is_empty());
+
+        // Appending an empty vector to a full one is fine
+        v.append(&mut other).unwrap();
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.insert_sorted(3), Ok(0));
+        assert_eq!(v.insert_sorted(1), Ok(0));
+        assert_eq!(v.insert_sorted(3), Ok(2));
+        assert_eq!(v.insert_sorted(2), Ok(1));
+        assert_eq!(v, [1, 2, 3, 3]);
+        assert_eq!(v.insert_sorted(0), Err(0));
+        assert_eq!(v, [1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn insert_sorted_by_key_stable() {
+        let mut v: Vec<(u8, char), 8> = Vec::new();
+        for item in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')] {
+            v.insert_sorted_by_key(item, |&(k, _)| k).unwrap();
+        }
+        assert_eq!(v, [(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn insert_from_slice() {
+        let mut v: Vec<u8, 6> = Vec::from_slice(&[1, 2]).unwrap();
+
+        v.insert_from_slice(1, &[3, 4]).unwrap();
+        assert_eq!(v, [1, 3, 4, 2]);
+
+        v.insert_from_slice(4, &[5]).unwrap();
+        assert_eq!(v, [1, 3, 4, 2, 5]);
+
+        v.insert_from_slice(0, &[]).unwrap();
+        assert_eq!(v, [1, 3, 4, 2, 5]);
+
+        // Not enough room, nothing is inserted
+        assert!(v.insert_from_slice(0, &[6, 7]).is_err());
+        assert_eq!(v, [1, 3, 4, 2, 5]);
+
+        v.insert_from_slice(0, &[6]).unwrap();
+        assert_eq!(v, [6, 1, 3, 4, 2, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_from_slice_out_of_bounds() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let _ = v.insert_from_slice(4, &[4]);
+    }
+
+    #[test]
+    fn drain_into() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+        let mut other: Vec<u8, 3> = Vec::new();
+
+        v.drain_into(3.., &mut other).unwrap();
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(other, [4, 5]);
+
+        // Not enough room, nothing is moved
+        assert!(v.drain_into(..2, &mut other).is_err());
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(other, [4, 5]);
+
+        v.drain_into(1..2, &mut other).unwrap();
+        assert_eq!(v, [1, 3]);
+        assert_eq!(other, [4, 5, 2]);
+
+        v.drain_into(0..0, &mut other).unwrap();
+        assert_eq!(v, [1, 3]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn drain_into_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+        let mut other: Vec<Droppable, 4> = Vec::new();
+
+        v.drain_into(1..3, &mut other).unwrap();
+        assert_eq!(Droppable::count(), 4);
+        assert_eq!(v[1].0, 4);
+        assert_eq!(other[0].0, 2);
+
+        core::mem::drop(other);
+        assert_eq!(Droppable::count(), 2);
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 0);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+
+        // Tail doesn't fit, nothing is moved
+        assert!(v.split_off::<2>(2).is_err());
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+
+        let tail: Vec<u8, 3> = v.split_off(2).unwrap();
+        assert_eq!(v, [1, 2]);
+        assert_eq!(tail, [3, 4, 5]);
+
+        let empty: Vec<u8, 0> = v.split_off(2).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(v, [1, 2]);
+
+        let all: Vec<u8, 4> = v.split_off(0).unwrap();
+        assert!(v.is_empty());
+        assert_eq!(all, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let _ = v.split_off::<8>(4);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn split_off_drop() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 4> = Vec::new();
+        for _ in 0..4 {
+            v.push(Droppable::new()).ok().unwrap();
+        }
+
+        let tail: Vec<Droppable, 4> = v.split_off(1).ok().unwrap();
+        assert_eq!(Droppable::count(), 4);
+        core::mem::drop(tail);
+        assert_eq!(Droppable::count(), 1);
+        core::mem::drop(v);
+        assert_eq!(Droppable::count(), 0);
+    }
+
     #[test]
     fn spare_capacity_mut() {
         let mut v: Vec<_, 4> = Vec::new();
@@ -1978,4 +4159,47 @@ mod tests {
 
         assert!(v.spare_capacity_mut().is_empty());
     }
+
+    #[test]
+    fn extend_from_spare() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1]).unwrap();
+        let uninit = v.spare_capacity_mut();
+        uninit[0].write(2);
+        uninit[1].write(3);
+        unsafe { v.extend_from_spare(2) };
+        assert_eq!(v, [1, 2, 3]);
+
+        unsafe { v.extend_from_spare(0) };
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn fill_spare_capacity() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1]).unwrap();
+
+        let written = v.fill_spare_capacity(|buf| {
+            assert_eq!(buf, &[0, 0, 0]);
+            buf[0] = 2;
+            buf[1] = 3;
+            2
+        });
+        assert_eq!(written, 2);
+        assert_eq!(v, [1, 2, 3]);
+
+        assert_eq!(v.fill_spare_capacity(|_| 0), 0);
+        assert_eq!(v, [1, 2, 3]);
+
+        assert_eq!(v.fill_spare_capacity(|buf| buf.len()), 1);
+        assert_eq!(v, [1, 2, 3, 0]);
+
+        // A full vector has no spare capacity to fill
+        assert_eq!(v.fill_spare_capacity(|buf| buf.len()), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_spare_capacity_overflow() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        v.fill_spare_capacity(|buf| buf.len() + 1);
+    }
 }
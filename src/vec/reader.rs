@@ -0,0 +1,85 @@
+use core::convert::Infallible;
+
+use embedded_io::{BufRead, ErrorType, Read, ReadReady};
+
+use super::VecInner;
+use crate::storage::Storage;
+
+/// A cursor reading the bytes of a [`Vec`](super::Vec) through [`embedded_io::Read`].
+///
+/// This struct is created by [`Vec::reader`](super::VecInner::reader).
+/// Reading doesn't modify the vector: the reader only advances its position.
+///
+/// # Example
+///
+/// ```
+/// use embedded_io::Read;
+/// use heapless::Vec;
+///
+/// let v = Vec::<u8, 8>::from_slice(b"hello").unwrap();
+/// let mut reader = v.reader();
+/// let mut buf = [0; 2];
+/// reader.read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"he");
+/// assert_eq!(reader.remaining(), b"llo");
+/// ```
+#[derive(Clone, Debug)]
+pub struct VecReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> VecReader<'a> {
+    /// Returns the number of bytes read so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes that haven't been read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+}
+
+impl<S: Storage> VecInner<u8, S> {
+    /// Returns a reader over the bytes of the vector, starting at the first one.
+    ///
+    /// See [`VecReader`] for an example.
+    pub fn reader(&self) -> VecReader<'_> {
+        VecReader {
+            buf: self.as_slice(),
+            pos: 0,
+        }
+    }
+}
+
+impl ErrorType for VecReader<'_> {
+    type Error = Infallible;
+}
+
+/// Reads the bytes of the vector in order; the reader is at EOF once they have all been read.
+impl Read for VecReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let remaining = self.remaining();
+        let n = buf.len().min(remaining.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl BufRead for VecReader<'_> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt.min(self.buf.len() - self.pos);
+    }
+}
+
+impl ReadReady for VecReader<'_> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
@@ -0,0 +1,185 @@
+use core::{ptr, slice};
+
+use super::Drain;
+
+/// A splicing iterator for [`Vec`](super::Vec).
+///
+/// This struct is created by [`Vec::splice`](super::Vec::splice).
+/// See its documentation for more.
+///
+/// # Example
+///
+/// ```
+/// use heapless::{vec, Vec};
+///
+/// let mut v = Vec::<_, 4>::from_array([0, 1, 2]);
+/// let new = [7, 8];
+/// let iter: vec::Splice<'_, _> = v.splice(1.., new);
+/// ```
+#[derive(Debug)]
+pub struct Splice<'a, I: Iterator + 'a> {
+    pub(super) drain: Drain<'a, I::Item>,
+    pub(super) replace_with: I,
+}
+
+impl<I: Iterator> Iterator for Splice<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator> DoubleEndedIterator for Splice<'_, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for Splice<'_, I> {}
+
+impl<I: Iterator> Drop for Splice<'_, I> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+        // At this point draining is done and the only remaining tasks are splicing
+        // and moving things into the final place.
+        // Which means we can replace the slice::Iter with pointers that won't point to
+        // moved-from memory, so that Drain::drop is still allowed to call iter.len().
+        self.drain.iter = [].iter();
+
+        unsafe {
+            // First fill the range left by drain().
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+
+            // There may be more elements. Move the tail to the end of the buffer
+            // to make room for as many of them as the capacity allows.
+            let new_tail_start = self.drain.vec.as_ref().storage_capacity() - self.drain.tail_len;
+            if new_tail_start > self.drain.tail_start {
+                self.drain.move_tail(new_tail_start);
+                self.drain.fill(&mut self.replace_with);
+            }
+            // The vector is full: the rest of `replace_with` is left unconsumed.
+        }
+        // Let `Drain::drop` move the tail back if necessary and restore `vec.len`.
+    }
+}
+
+/// Private helper methods for `Splice::drop`
+impl<T> Drain<'_, T> {
+    /// The range from `self.vec.len` to `self.tail_start` contains elements
+    /// that have been moved out.
+    /// Fill that range as much as possible with new elements from the `replace_with` iterator.
+    /// Returns `true` if we filled the entire range. (`replace_with.next()` didn’t return `None`.)
+    unsafe fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
+        let vec = self.vec.as_mut();
+        let range_start = vec.len;
+        let range_end = self.tail_start;
+        let range_slice =
+            slice::from_raw_parts_mut(vec.as_mut_ptr().add(range_start), range_end - range_start);
+
+        for place in range_slice {
+            if let Some(new_item) = replace_with.next() {
+                ptr::write(place, new_item);
+                vec.len += 1;
+            } else {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Moves the tail so that it starts at `new_tail_start`.
+    unsafe fn move_tail(&mut self, new_tail_start: usize) {
+        let ptr = self.vec.as_mut().as_mut_ptr();
+        let src = ptr.add(self.tail_start);
+        let dst = ptr.add(new_tail_start);
+        ptr::copy(src, dst, self.tail_len);
+        self.tail_start = new_tail_start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Vec;
+
+    #[test]
+    fn splice_shorter() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5]);
+        let removed: Vec<_, 8> = vec.splice(1..4, [9]).collect();
+        assert_eq!(removed, &[2, 3, 4]);
+        assert_eq!(vec, &[1, 9, 5]);
+    }
+
+    #[test]
+    fn splice_longer() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4]);
+        let removed: Vec<_, 8> = vec.splice(1..2, [7, 8, 9]).collect();
+        assert_eq!(removed, &[2]);
+        assert_eq!(vec, &[1, 7, 8, 9, 3, 4]);
+    }
+
+    #[test]
+    fn splice_end() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3]);
+        vec.splice(3.., [4, 5, 6]);
+        assert_eq!(vec, &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn splice_fill_capacity() {
+        let mut vec = Vec::<_, 5>::from_array([1, 2, 3]);
+        vec.splice(..1, [7, 8, 9]);
+        assert_eq!(vec, &[7, 8, 9, 2, 3]);
+    }
+
+    #[test]
+    fn splice_unknown_size() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3]);
+        vec.splice(1..2, (4..8).filter(|x| x % 2 == 0));
+        assert_eq!(vec, &[1, 4, 6, 3]);
+    }
+
+    #[test]
+    fn splice_overflow() {
+        let mut vec = Vec::<_, 4>::from_array([1, 2, 3]);
+        let mut replace_with = [7, 8, 9].into_iter();
+        vec.splice(1..2, replace_with.by_ref());
+        assert_eq!(vec, &[1, 7, 8, 3]);
+        assert!(replace_with.eq([9]));
+
+        let mut vec = Vec::<_, 4>::from_array([1, 2, 3]);
+        let mut replace_with = [7, 8, 9].into_iter();
+        vec.splice(2.., replace_with.by_ref());
+        assert_eq!(vec, &[1, 2, 7, 8]);
+        assert!(replace_with.eq([9]));
+
+        let mut vec = Vec::<_, 4>::from_array([1, 2, 3, 4]);
+        vec.splice(4.., 0..);
+        assert_eq!(vec, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "copy"))]
+    fn splice_drop_rest() {
+        droppable!();
+
+        let mut vec =
+            Vec::<_, 8>::from_array([Droppable::new(), Droppable::new(), Droppable::new()]);
+        assert_eq!(Droppable::count(), 3);
+
+        let replacement = [Droppable::new()];
+        let mut iter = vec.splice(..2, replacement);
+        assert_eq!(iter.next().unwrap().0, 1);
+        drop(iter);
+        assert_eq!(Droppable::count(), 2);
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec[0].0, 4);
+        assert_eq!(vec[1].0, 3);
+    }
+}
@@ -0,0 +1,100 @@
+use core::{fmt, iter::FusedIterator};
+
+use super::VecView;
+
+/// An iterator which uses a closure to determine if an element should be removed, without
+/// preserving the order of the remaining elements.
+///
+/// This struct is created by [`Vec::swap_drain`](super::Vec::swap_drain).
+/// See its documentation for more.
+///
+/// # Example
+///
+/// ```
+/// use heapless::{vec, Vec};
+///
+/// let mut v = Vec::<_, 4>::from_array([0, 1, 2]);
+/// let iter: vec::SwapDrain<'_, _, _> = v.swap_drain(|x| *x % 2 == 0);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SwapDrain<'a, T, F> {
+    pub(super) vec: &'a mut VecView<T>,
+    /// The index of the item that will be inspected by the next call to `next`.
+    pub(super) idx: usize,
+    /// The filter test predicate.
+    pub(super) pred: F,
+}
+
+impl<T, F> Iterator for SwapDrain<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.idx < self.vec.len() {
+            // NOTE(unsafe) `idx` is within bounds
+            let cur = unsafe { self.vec.get_unchecked_mut(self.idx) };
+            if (self.pred)(cur) {
+                // Don't advance: the last element was swapped into `idx` and must be checked.
+                return Some(unsafe { self.vec.swap_remove_unchecked(self.idx) });
+            }
+            self.idx += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.vec.len() - self.idx))
+    }
+}
+
+impl<T, F> FusedIterator for SwapDrain<'_, T, F> where F: FnMut(&mut T) -> bool {}
+
+impl<T: fmt::Debug, F> fmt::Debug for SwapDrain<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SwapDrain")
+            .field(&&self.vec[self.idx..])
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Vec;
+
+    #[test]
+    fn swap_drain_all() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5, 6]);
+        let mut evens: Vec<_, 8> = vec.swap_drain(|x| *x % 2 == 0).collect();
+        evens.sort();
+        assert_eq!(evens, &[2, 4, 6]);
+        vec.sort();
+        assert_eq!(vec, &[1, 3, 5]);
+    }
+
+    #[test]
+    fn swap_drain_order() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4, 5]);
+        let removed: Vec<_, 8> = vec.swap_drain(|x| *x < 3).collect();
+        // 1 is replaced by 5, 2 by 4
+        assert_eq!(removed, &[1, 2]);
+        assert_eq!(vec, &[5, 4, 3]);
+    }
+
+    #[test]
+    fn swap_drain_partial() {
+        let mut vec = Vec::<_, 8>::from_array([1, 2, 3, 4]);
+        let mut iter = vec.swap_drain(|x| *x % 2 == 0);
+        assert_eq!(iter.next(), Some(2));
+        drop(iter);
+        assert_eq!(vec, &[1, 4, 3]);
+    }
+
+    #[test]
+    fn swap_drain_trailing() {
+        let mut vec = Vec::<_, 8>::from_array([2, 4, 6]);
+        assert_eq!(vec.swap_drain(|_| true).count(), 3);
+        assert!(vec.is_empty());
+    }
+}